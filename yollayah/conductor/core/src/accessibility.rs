@@ -101,6 +101,8 @@ impl Accessible for ConductorMessage {
                 Some(format!("Yollayah says: {final_content}"))
             }
 
+            ConductorMessage::Welcome { content, .. } => Some(format!("Yollayah says: {content}")),
+
             ConductorMessage::StreamError { error, .. } => Some(format!("Error: {error}")),
 
             // Avatar state changes need explicit announcements
@@ -156,6 +158,7 @@ impl Accessible for ConductorMessage {
                 level,
                 title,
                 message,
+                guidance,
             } => {
                 let prefix = match level {
                     NotifyLevel::Error => "Error",
@@ -167,7 +170,10 @@ impl Accessible for ConductorMessage {
                     Some(t) => format!("{prefix}: {t} - {message}"),
                     None => format!("{prefix}: {message}"),
                 };
-                Some(announcement)
+                match guidance {
+                    Some(guidance) => Some(format!("{announcement} Next step: {guidance}")),
+                    None => Some(announcement),
+                }
             }
 
             ConductorMessage::State { state } => {
@@ -224,7 +230,8 @@ impl Accessible for ConductorMessage {
                 Some("Conversation focused".to_string())
             }
 
-            ConductorMessage::ConversationStateChanged { .. } => None, // Too noisy
+            ConductorMessage::ConversationStateChanged { .. }
+            | ConductorMessage::ConversationList { .. } => None, // Too noisy
 
             ConductorMessage::ConversationStreamEnd { final_content, .. } => {
                 Some(format!("Agent says: {final_content}"))
@@ -236,20 +243,45 @@ impl Accessible for ConductorMessage {
 
             ConductorMessage::ConversationRemoved { .. } => Some("Conversation closed".to_string()),
 
+            ConductorMessage::HistoryTruncated { .. } => Some("Messages removed".to_string()),
+
+            ConductorMessage::MessageEdited { .. } => {
+                Some("Message edited, answering again".to_string())
+            }
+
+            ConductorMessage::SurfaceFailed { .. } => {
+                Some("Another display stopped working and was disconnected".to_string())
+            }
+
             // Internal/transport messages - no announcement needed
-            ConductorMessage::Token { .. }
+            ConductorMessage::StreamStart { .. }
+            | ConductorMessage::Token { .. }
+            | ConductorMessage::StreamCancelled { .. }
+            | ConductorMessage::Reasoning { .. }
             | ConductorMessage::ConversationStreamToken { .. }
             | ConductorMessage::QueryCapabilities
             | ConductorMessage::Ack { .. }
             | ConductorMessage::SessionInfo { .. }
+            | ConductorMessage::ModelReady { .. }
+            | ConductorMessage::WarmupProgress { .. }
+            | ConductorMessage::SystemPromptChanged { .. }
             | ConductorMessage::HandshakeAck { .. }
+            | ConductorMessage::NegotiatedCapabilities { .. }
             | ConductorMessage::Ping { .. }
             | ConductorMessage::StateSnapshot { .. }
+            | ConductorMessage::Health { .. }
+            | ConductorMessage::ModelInfo { .. }
+            | ConductorMessage::ExportReady { .. }
             | ConductorMessage::AvatarMoveTo { .. }
+            | ConductorMessage::AvatarSprite { .. }
             | ConductorMessage::AvatarSize { .. }
             | ConductorMessage::AvatarWander { .. }
+            | ConductorMessage::AvatarBlink { .. }
             | ConductorMessage::AvatarPointAt { .. }
-            | ConductorMessage::TaskFocus { .. } => None,
+            | ConductorMessage::AvatarActivity { .. }
+            | ConductorMessage::AvatarReset { .. }
+            | ConductorMessage::TaskFocus { .. }
+            | ConductorMessage::TaskView { .. } => None,
         }
     }
 
@@ -261,9 +293,9 @@ impl Accessible for ConductorMessage {
             },
             ConductorMessage::State { .. } => Some("status"),
             ConductorMessage::TaskUpdated { .. } => Some("progressbar"),
-            ConductorMessage::Message { .. } | ConductorMessage::StreamEnd { .. } => {
-                Some("article")
-            }
+            ConductorMessage::Message { .. }
+            | ConductorMessage::StreamEnd { .. }
+            | ConductorMessage::Welcome { .. } => Some("article"),
             _ => None,
         }
     }
@@ -280,6 +312,7 @@ impl Accessible for ConductorMessage {
             ConductorMessage::Notify { .. }
             | ConductorMessage::Message { .. }
             | ConductorMessage::StreamEnd { .. }
+            | ConductorMessage::Welcome { .. }
             | ConductorMessage::TaskCompleted { .. } => Urgency::Normal,
             ConductorMessage::TaskUpdated { .. }
             | ConductorMessage::State { .. }
@@ -378,6 +411,7 @@ mod tests {
             role: MessageRole::Assistant,
             content: "Hello world".to_string(),
             content_type: crate::messages::ContentType::Plain,
+            resend: false,
         };
         let announcement = msg.screen_reader_announcement().unwrap();
         assert!(announcement.contains("Yollayah says"));
@@ -390,6 +424,7 @@ mod tests {
             level: NotifyLevel::Error,
             title: None,
             message: "Something went wrong".to_string(),
+            guidance: None,
         };
         assert_eq!(msg.urgency(), Urgency::Immediate);
         assert_eq!(msg.aria_role(), Some("alert"));
@@ -409,6 +444,7 @@ mod tests {
         let msg = ConductorMessage::Token {
             message_id: crate::messages::MessageId::new(),
             text: "partial".to_string(),
+            seq: 1,
         };
         assert!(msg.screen_reader_announcement().is_none());
         assert_eq!(msg.urgency(), Urgency::None);
@@ -48,6 +48,8 @@ use super::block::{Mood, SpriteRequest, SpriteResponse};
 use super::cache::{SpriteCache, SpriteData};
 use super::evolution::EvolutionLevel;
 use super::generation::{Accessory, RuleBasedGenerator, SpriteGenerator};
+use super::security::{validate_animation_frames, validate_sprite, SecurityResult, Sprite};
+use crate::animation::AnimationSpec;
 
 // =============================================================================
 // Request ID Generation
@@ -524,6 +526,46 @@ impl SpriteService {
         self.request_sprite_with_options(mood, evolution_level, options)
     }
 
+    /// Number of variants the generator has for a mood
+    #[must_use]
+    pub fn variant_count(&self, mood: Mood, evolution_level: EvolutionLevel) -> u8 {
+        self.generator.variant_count(mood, evolution_level)
+    }
+
+    /// Render every frame of an animation, reusing cached frames
+    ///
+    /// Like [`render_animation_frames`](super::generation::render_animation_frames),
+    /// but each frame goes through the cache, so its budget bounds memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SecurityError` if the frame count or a frame fails validation.
+    pub fn animation_frames(
+        &self,
+        spec: &AnimationSpec,
+        mood: Mood,
+        evolution_level: EvolutionLevel,
+    ) -> SecurityResult<Vec<SpriteResponse>> {
+        validate_animation_frames(spec.frame_count)?;
+
+        (0..spec.frame_count)
+            .map(|i| {
+                // Frame counts are validated above, so this never saturates
+                let variant = u8::try_from(i).unwrap_or(u8::MAX);
+                let options = GenerationOptions::default().with_variant(variant);
+                let key = self.make_cache_key(mood, evolution_level, &options);
+                let frame = self.get_cached(&key).unwrap_or_else(|| {
+                    let frame = self.generate_sprite(mood, evolution_level, &options);
+                    self.cache_sprite(&key, &frame, &options);
+                    frame
+                });
+                let (width, height) = frame.dimensions;
+                validate_sprite(&Sprite::new(width, height, frame.blocks.clone()))?;
+                Ok(frame)
+            })
+            .collect()
+    }
+
     // =========================================================================
     // Asynchronous Generation
     // =========================================================================
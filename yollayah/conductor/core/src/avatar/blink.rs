@@ -0,0 +1,115 @@
+//! Idle Blinking
+//!
+//! A resting avatar that never moves looks like a picture. While idle,
+//! Yollayah blinks every few seconds; [`BlinkTimer`] decides when. Each gap
+//! is the configured interval give or take a quarter, drawn from a seeded
+//! RNG so blinks don't tick like a metronome yet stay reproducible.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Default time between idle blinks in milliseconds
+pub const DEFAULT_BLINK_INTERVAL_MS: u64 = 4000;
+
+/// How long the eyes stay closed in milliseconds
+pub const BLINK_DURATION_MS: u32 = 150;
+
+/// Schedules idle blinks at a jittered interval
+#[derive(Clone, Debug)]
+pub struct BlinkTimer {
+    /// Average time between blinks (0 = never blink)
+    interval_ms: u64,
+    /// When the next blink is due (None = not idle yet)
+    next_at_ms: Option<u64>,
+    rng: StdRng,
+}
+
+impl BlinkTimer {
+    /// Blink about every `interval_ms`, jittered by an RNG seeded with `seed`
+    #[must_use]
+    pub fn new(interval_ms: u64, seed: u64) -> Self {
+        Self {
+            interval_ms,
+            next_at_ms: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Whether a blink is due at `now_ms`
+    ///
+    /// Call this on every tick. The wait starts over whenever the avatar
+    /// isn't `idle`, so a blink never lands right as a gesture ends.
+    pub fn poll(&mut self, now_ms: u64, idle: bool) -> bool {
+        if self.interval_ms == 0 || !idle {
+            self.next_at_ms = None;
+            return false;
+        }
+        match self.next_at_ms {
+            Some(due) if now_ms >= due => {
+                self.next_at_ms = Some(now_ms + self.next_gap());
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.next_at_ms = Some(now_ms + self.next_gap());
+                false
+            }
+        }
+    }
+
+    /// Time until the blink after this one: the interval, ±25%
+    fn next_gap(&mut self) -> u64 {
+        let jitter = self.interval_ms / 4;
+        self.rng
+            .gen_range(self.interval_ms - jitter..=self.interval_ms + jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Times at which blinks happen over `duration_ms`, ticking every 10ms
+    fn blink_times(timer: &mut BlinkTimer, duration_ms: u64) -> Vec<u64> {
+        (0..=duration_ms)
+            .step_by(10)
+            .filter(|&now| timer.poll(now, true))
+            .collect()
+    }
+
+    #[test]
+    fn test_blinks_at_jittered_interval() {
+        let mut timer = BlinkTimer::new(1000, 7);
+        let times = blink_times(&mut timer, 30_000);
+
+        assert!((24..=40).contains(&times.len()), "{times:?}");
+        let gaps: Vec<u64> = times.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(
+            gaps.iter().all(|gap| (750..=1260).contains(gap)),
+            "{gaps:?}"
+        );
+        // Not a metronome
+        assert!(gaps.iter().any(|&gap| gap != gaps[0]));
+    }
+
+    #[test]
+    fn test_same_seed_blinks_the_same() {
+        let a = blink_times(&mut BlinkTimer::new(1000, 42), 10_000);
+        let b = blink_times(&mut BlinkTimer::new(1000, 42), 10_000);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_busy_avatar_restarts_the_wait() {
+        let mut timer = BlinkTimer::new(1000, 1);
+        assert!(!timer.poll(0, true));
+        assert!(!timer.poll(700, false));
+        // The wait started over when idle resumed
+        assert!(!timer.poll(1300, true));
+        assert!(!timer.poll(1300 + 740, true));
+        assert!(timer.poll(1300 + 1250, true));
+
+        let mut off = BlinkTimer::new(0, 1);
+        assert!(blink_times(&mut off, 10_000).is_empty());
+    }
+}
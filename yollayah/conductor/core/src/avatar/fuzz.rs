@@ -0,0 +1,243 @@
+//! Command Parser Fuzzing
+//!
+//! Helpers for hammering [`CommandParser`] with hostile input. [`check_parser`]
+//! asserts the parser's invariants on one input and is meant to be called
+//! from a fuzz target; [`CommandFuzzer`] generates inputs deterministically
+//! from a seed so property tests are reproducible.
+
+use super::CommandParser;
+
+/// Fragments that exercise the parser's edge cases when glued together
+const HOSTILE_FRAGMENTS: &[&str] = &[
+    "[",
+    "]",
+    "[[",
+    "]]",
+    "[yolla:",
+    "yolla:",
+    "[yolla:]",
+    "[yolla:newmsg]",
+    "wave",
+    "react dizzy",
+    "move ",
+    "peek",
+    " ",
+    "\n",
+    "\0",
+    "é",
+    "🦎",
+];
+
+/// Plain text that can't be mistaken for a command
+const PLAIN_FRAGMENTS: &[&str] = &[
+    "Hello",
+    " ",
+    "world",
+    "!",
+    "yolla",
+    ":",
+    "\n",
+    "¡Órale!",
+    "🦎",
+    "日本語",
+];
+
+/// Commands the parser understands
+const VALID_COMMANDS: &[&str] = &[
+    "[yolla:wave]",
+    "[yolla:spin]",
+    "[yolla:peek left]",
+    "[yolla:mood happy]",
+    "[yolla:react dizzy]",
+    "[yolla:move center]",
+    "[yolla:point 10 90]",
+    "[yolla:size large]",
+    "[yolla:wander]",
+    "[yolla:hide]",
+    "[yolla:show]",
+    "[yolla:tada]",
+];
+
+/// Check the parser's invariants on arbitrary bytes
+///
+/// Invalid UTF-8 is decoded lossily, as a backend would. Stripping must
+/// only ever remove text, and stripping the result again must change
+/// nothing and find no further commands.
+///
+/// # Panics
+///
+/// If the parser panics or an invariant doesn't hold.
+pub fn check_parser(data: &[u8]) {
+    let text = String::from_utf8_lossy(data);
+    let stripped = CommandParser::new().parse(&text);
+    assert!(
+        stripped.len() <= text.len(),
+        "Stripping grew {text:?} into {stripped:?}"
+    );
+
+    let mut again = CommandParser::new();
+    assert_eq!(
+        again.parse(&stripped),
+        stripped,
+        "Stripping {text:?} wasn't stable"
+    );
+    assert!(
+        !again.has_commands(),
+        "Stripped output of {text:?} still had commands"
+    );
+}
+
+/// A well-formed input and what stripping it should produce
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WellFormedCase {
+    /// Plain text interleaved with valid commands
+    pub input: String,
+    /// The plain text alone
+    pub expected_text: String,
+    /// How many commands the input holds
+    pub commands: usize,
+}
+
+/// Deterministic generator of parser inputs
+///
+/// The same seed always produces the same sequence of inputs.
+#[derive(Clone, Debug)]
+pub struct CommandFuzzer {
+    state: u64,
+}
+
+impl CommandFuzzer {
+    /// Start a generator from `seed`
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Bytes mixing malformed command fragments, nested brackets, multi-byte
+    /// characters and raw (possibly invalid UTF-8) bytes
+    pub fn arbitrary_bytes(&mut self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..self.below(24) {
+            if self.below(4) == 0 {
+                bytes.push(self.next_byte());
+            } else {
+                bytes.extend_from_slice(self.pick(HOSTILE_FRAGMENTS).as_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Plain text interleaved with valid commands
+    pub fn well_formed(&mut self) -> WellFormedCase {
+        let mut case = WellFormedCase {
+            input: String::new(),
+            expected_text: String::new(),
+            commands: 0,
+        };
+        for _ in 0..self.below(16) {
+            if self.below(3) == 0 {
+                case.input.push_str(self.pick(VALID_COMMANDS));
+                case.commands += 1;
+            } else {
+                let plain = self.pick(PLAIN_FRAGMENTS);
+                case.input.push_str(plain);
+                case.expected_text.push_str(plain);
+            }
+        }
+        case
+    }
+
+    /// Next value of the `SplitMix64` sequence
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        self.next_u64().to_le_bytes()[0]
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        usize::try_from(self.next_u64() % bound as u64).unwrap_or(0)
+    }
+
+    fn pick(&mut self, items: &[&'static str]) -> &'static str {
+        items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASES: u64 = 500;
+
+    #[test]
+    fn test_parser_survives_hostile_input() {
+        for input in [
+            "",
+            "[",
+            "]",
+            "[yolla:",
+            "[yolla:]",
+            "[yolla:[yolla:wave]]",
+            "[a [yolla:wave] b]",
+            "[[yolla:wave]]",
+            "[yolla:wave",
+            "[yolla:🦎]",
+            "[yolla:react]",
+            "[yolla:point 999 -1]",
+        ] {
+            check_parser(input.as_bytes());
+        }
+        check_parser(&[b'[', 0xFF, 0xFE, b']']);
+        check_parser(b"[yolla:\xC3]");
+    }
+
+    #[test]
+    fn test_parser_survives_arbitrary_input() {
+        for seed in 0..CASES {
+            let bytes = CommandFuzzer::new(seed).arbitrary_bytes();
+            check_parser(&bytes);
+        }
+    }
+
+    #[test]
+    fn test_well_formed_input_strips_cleanly() {
+        for seed in 0..CASES {
+            let case = CommandFuzzer::new(seed).well_formed();
+            let mut parser = CommandParser::new();
+            let stripped = parser.parse(&case.input);
+
+            assert_eq!(stripped, case.expected_text, "Input: {:?}", case.input);
+            assert!(!stripped.contains('[') && !stripped.contains(']'));
+            let mut commands = 0;
+            while parser.next_command().is_some() {
+                commands += 1;
+            }
+            assert_eq!(commands, case.commands, "Input: {:?}", case.input);
+
+            // Parsing the stripped text again finds nothing new
+            let mut again = CommandParser::new();
+            assert_eq!(again.parse(&stripped), stripped);
+            assert!(!again.has_commands());
+        }
+    }
+
+    #[test]
+    fn test_fuzzer_is_deterministic() {
+        let mut a = CommandFuzzer::new(42);
+        let mut b = CommandFuzzer::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.arbitrary_bytes(), b.arbitrary_bytes());
+            assert_eq!(a.well_formed(), b.well_formed());
+        }
+        assert_ne!(
+            CommandFuzzer::new(1).arbitrary_bytes(),
+            CommandFuzzer::new(2).arbitrary_bytes()
+        );
+    }
+}
@@ -59,6 +59,8 @@ use serde::{Deserialize, Serialize};
 
 use super::block::{Block, Color, Mood, SpriteResponse};
 use super::evolution::EvolutionLevel;
+use super::security::{validate_animation_frames, validate_sprite, SecurityResult, Sprite};
+use crate::animation::AnimationSpec;
 
 // =============================================================================
 // Sprite Generator Trait (P4.1)
@@ -1030,6 +1032,36 @@ pub fn available_accessories(level: EvolutionLevel) -> Vec<Accessory> {
     Accessory::unlocked_at(level)
 }
 
+/// Render an animation into concrete frames
+///
+/// Frame `i` is expression variant `i` of the mood (wrapping), so every
+/// frame of the spec gets a sprite. Used for surfaces that can't draw
+/// sprites themselves, so each frame is validated before it's returned.
+///
+/// # Errors
+///
+/// Returns a `SecurityError` if the spec has too many frames or a frame
+/// fails sprite validation.
+pub fn render_animation_frames(
+    generator: &dyn SpriteGenerator,
+    spec: &AnimationSpec,
+    mood: Mood,
+    evolution_level: EvolutionLevel,
+) -> SecurityResult<Vec<SpriteResponse>> {
+    validate_animation_frames(spec.frame_count)?;
+
+    (0..spec.frame_count)
+        .map(|i| {
+            // Frame counts are validated above, so this never saturates
+            let variant = u8::try_from(i).unwrap_or(u8::MAX);
+            let frame = generator.generate_variant(mood, evolution_level, variant);
+            let (width, height) = frame.dimensions;
+            validate_sprite(&Sprite::new(width, height, frame.blocks.clone()))?;
+            Ok(frame)
+        })
+        .collect()
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -1548,4 +1580,36 @@ mod tests {
             mid_high
         );
     }
+
+    #[test]
+    fn test_render_animation_frames() {
+        let generator = RuleBasedGenerator::new();
+        let spec = AnimationSpec::looping("happy", 3, 4.0);
+        let frames =
+            render_animation_frames(&generator, &spec, Mood::Happy, EvolutionLevel::Nascent)
+                .unwrap();
+
+        assert_eq!(frames.len(), 3);
+        for (i, frame) in frames.iter().enumerate() {
+            let expected = generator.generate_variant(Mood::Happy, EvolutionLevel::Nascent, i as u8);
+            assert_eq!(frame, &expected);
+            assert_eq!(frame.blocks.len(), 64);
+        }
+    }
+
+    #[test]
+    fn test_render_animation_frames_rejects_long_specs() {
+        use crate::avatar::security::{SecurityError, MAX_ANIMATION_FRAMES};
+
+        let spec = AnimationSpec::looping("happy", MAX_ANIMATION_FRAMES + 1, 4.0);
+        assert!(matches!(
+            render_animation_frames(
+                &RuleBasedGenerator::new(),
+                &spec,
+                Mood::Happy,
+                EvolutionLevel::Nascent
+            ),
+            Err(SecurityError::TooManyAnimationFrames { .. })
+        ));
+    }
 }
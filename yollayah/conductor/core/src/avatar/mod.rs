@@ -22,15 +22,24 @@
 //! # Module Structure
 //!
 //! - [`block`]: Block-based rendering primitives (Color, Block, `SizeHint`, `AnchorPoint`)
+//! - [`motion`]: Sub-cell movement towards a target position
+//! - [`blink`]: When to blink while idle
+//! - `fuzz`: Invariant checks and input generation for fuzzing the command parser
+//!   (tests and the `fuzz` feature only)
 //! - Avatar state types (position, mood, gestures, reactions)
 //! - Command parsing for embedded avatar commands in LLM responses
 
 pub mod api;
+pub mod blink;
 pub mod block;
 pub mod cache;
 pub mod evolution;
+#[cfg(any(test, feature = "fuzz"))]
+pub mod fuzz;
 pub mod generation;
+pub mod motion;
 pub mod security;
+pub mod sentiment;
 pub mod variants;
 
 // Re-export block types at the avatar module level for convenience
@@ -45,10 +54,10 @@ pub use cache::{CacheEntry, CacheError, CacheStats, SpriteCache, SpriteData};
 // Re-export security types
 pub use security::{
     is_allowed_block_char, validate_animation_duration, validate_animation_frames,
-    validate_block_count, validate_sprite, validate_sprite_dimensions, validate_sprite_size,
-    validate_unicode_char, PendingRequestGuard, PendingRequestTracker, SecurityError,
-    SecurityResult, Sprite, SpriteRateLimiter, ALLOWED_UNICODE_RANGES, MAX_ANIMATION_DURATION_MS,
-    MAX_ANIMATION_FRAMES, MAX_BLOCKS_PER_SPRITE, MAX_CACHE_SIZE_BYTES,
+    validate_block_count, validate_cache_size, validate_sprite, validate_sprite_dimensions,
+    validate_sprite_size, validate_unicode_char, PendingRequestGuard, PendingRequestTracker,
+    SecurityError, SecurityResult, Sprite, SpriteRateLimiter, ALLOWED_UNICODE_RANGES,
+    MAX_ANIMATION_DURATION_MS, MAX_ANIMATION_FRAMES, MAX_BLOCKS_PER_SPRITE, MAX_CACHE_SIZE_BYTES,
     MAX_PENDING_REQUESTS_PER_SESSION, MAX_SPRITE_HEIGHT, MAX_SPRITE_REQUESTS_PER_MINUTE,
     MAX_SPRITE_WIDTH,
 };
@@ -62,6 +71,15 @@ pub use evolution::{
     THRESHOLD_TRANSCENDENT_TIME_SECS,
 };
 
+// Re-export motion types
+pub use motion::{MotionInterpolator, DEFAULT_MOVE_SPEED};
+
+// Re-export blink types
+pub use blink::{BlinkTimer, BLINK_DURATION_MS, DEFAULT_BLINK_INTERVAL_MS};
+
+// Re-export sentiment types
+pub use sentiment::UserSentiment;
+
 // Re-export variants types
 pub use variants::{
     available_variants_count, select_variant, AnimationType, AnimationVariant, VariantRegistry,
@@ -69,8 +87,8 @@ pub use variants::{
 
 // Re-export generation types
 pub use generation::{
-    available_accessories, compose_with_accessory, Accessory, AccessorySlot, EyePattern,
-    MoodOverlay, MouthPattern, RuleBasedGenerator, SpriteGenerator,
+    available_accessories, compose_with_accessory, render_animation_frames, Accessory,
+    AccessorySlot, EyePattern, MoodOverlay, MouthPattern, RuleBasedGenerator, SpriteGenerator,
 };
 
 // Re-export API types (P4.4)
@@ -79,7 +97,7 @@ pub use api::{
     RequestId, SpriteService,
 };
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
@@ -108,8 +126,20 @@ pub enum AvatarPosition {
     },
 }
 
+impl AvatarPosition {
+    /// Whether a `Percent` position is within 0-100 on both axes (named
+    /// positions always are)
+    #[must_use]
+    pub fn is_in_range(&self) -> bool {
+        match self {
+            Self::Percent { x, y } => *x <= 100 && *y <= 100,
+            _ => true,
+        }
+    }
+}
+
 /// Avatar emotional moods
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum AvatarMood {
     /// Default happy state
     #[default]
@@ -143,6 +173,81 @@ impl AvatarMood {
             Self::Curious => "waiting",
         }
     }
+
+    /// Mood by name or synonym (e.g. "curious" or "interested")
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "happy" | "joy" => Some(Self::Happy),
+            "thinking" | "think" | "ponder" => Some(Self::Thinking),
+            "playful" | "silly" => Some(Self::Playful),
+            "shy" | "bashful" => Some(Self::Shy),
+            "excited" | "eager" => Some(Self::Excited),
+            "confused" | "puzzled" => Some(Self::Confused),
+            "calm" | "peaceful" => Some(Self::Calm),
+            "curious" | "interested" => Some(Self::Curious),
+            _ => None,
+        }
+    }
+}
+
+/// Which animation each mood plays, where it differs from
+/// [`AvatarMood::suggested_animation`]
+///
+/// Lets a custom animation (e.g. a "wonder" variant) stand in for a mood's
+/// default. Moods without an override keep their default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoodAnimations {
+    overrides: HashMap<AvatarMood, String>,
+}
+
+impl MoodAnimations {
+    /// Play `animation` for `mood`
+    #[must_use]
+    pub fn with(mut self, mood: AvatarMood, animation: impl Into<String>) -> Self {
+        self.overrides.insert(mood, animation.into());
+        self
+    }
+
+    /// Parse `mood=animation` pairs separated by commas
+    ///
+    /// Pairs with an unknown mood or no animation are skipped.
+    #[must_use]
+    pub fn parse(spec: &str) -> Self {
+        spec.split(',')
+            .filter_map(|pair| {
+                let (mood, animation) = pair.split_once('=')?;
+                let mood = AvatarMood::from_name(&mood.trim().to_lowercase())?;
+                let animation = animation.trim();
+                (!animation.is_empty()).then_some((mood, animation))
+            })
+            .fold(Self::default(), |overrides, (mood, animation)| {
+                overrides.with(mood, animation)
+            })
+    }
+
+    /// Animation to play for `mood`
+    #[must_use]
+    pub fn animation_for(&self, mood: AvatarMood) -> &str {
+        self.overrides
+            .get(&mood)
+            .map_or_else(|| mood.suggested_animation(), String::as_str)
+    }
+}
+
+impl From<AvatarMood> for Mood {
+    fn from(mood: AvatarMood) -> Self {
+        match mood {
+            AvatarMood::Happy => Self::Happy,
+            AvatarMood::Thinking => Self::Thinking,
+            AvatarMood::Playful => Self::Playful,
+            AvatarMood::Shy => Self::Shy,
+            AvatarMood::Excited => Self::Excited,
+            AvatarMood::Confused => Self::Confused,
+            AvatarMood::Calm => Self::Calm,
+            AvatarMood::Curious => Self::Curious,
+        }
+    }
 }
 
 /// Avatar sizes
@@ -159,6 +264,18 @@ pub enum AvatarSize {
     Large,
 }
 
+/// Ongoing activity shown as an overlay on the avatar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AvatarActivity {
+    /// No overlay
+    #[default]
+    None,
+    /// Thinking dots while waiting for the model
+    Thinking,
+    /// Talking while a response streams in
+    Talking,
+}
+
 /// Gesture animations (short, one-time)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AvatarGesture {
@@ -216,6 +333,33 @@ impl AvatarGesture {
             Self::Stretch | Self::Yawn | Self::Peek(_) => "idle",
         }
     }
+
+    /// Command name of this gesture (e.g., "wave"; any peek is "peek")
+    #[must_use]
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            Self::Wave => "wave",
+            Self::Nod => "nod",
+            Self::Shake => "shake",
+            Self::Peek(_) => "peek",
+            Self::Bounce => "bounce",
+            Self::Spin => "spin",
+            Self::Dance => "dance",
+            Self::Swim => "swim",
+            Self::Stretch => "stretch",
+            Self::Yawn => "yawn",
+            Self::Wiggle => "wiggle",
+        }
+    }
+
+    /// Parse a gesture from its command name (e.g., "wave", "peek left")
+    #[must_use]
+    pub fn from_command(command: &str) -> Option<Self> {
+        match CommandParser::new().parse_command(command.trim())? {
+            AvatarCommand::Gesture(gesture) => Some(gesture),
+            _ => None,
+        }
+    }
 }
 
 /// Direction for peek gesture
@@ -293,6 +437,45 @@ impl AvatarReaction {
             Self::Dizzy => "swimming",
         }
     }
+
+    /// Command name of this reaction (as in `[yolla:react dizzy]`)
+    #[must_use]
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            Self::Laugh => "laugh",
+            Self::Gasp => "gasp",
+            Self::Hmm => "hmm",
+            Self::Tada => "tada",
+            Self::Oops => "oops",
+            Self::Love => "love",
+            Self::Blush => "blush",
+            Self::Wink => "wink",
+            Self::Cry => "cry",
+            Self::Angry => "angry",
+            Self::Sleepy => "sleepy",
+            Self::Dizzy => "dizzy",
+        }
+    }
+}
+
+/// A gesture or reaction that plays once and then ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OneShotAnimation {
+    /// A gesture (wave, nod, ...)
+    Gesture(AvatarGesture),
+    /// A reaction (tada, oops, ...)
+    Reaction(AvatarReaction),
+}
+
+impl OneShotAnimation {
+    /// Command name of the gesture or reaction
+    #[must_use]
+    pub fn command_name(&self) -> &'static str {
+        match self {
+            Self::Gesture(gesture) => gesture.command_name(),
+            Self::Reaction(reaction) => reaction.command_name(),
+        }
+    }
 }
 
 /// Avatar commands that can be embedded in text responses
@@ -383,6 +566,177 @@ pub enum TaskCommand {
     },
 }
 
+/// Name and syntax of an avatar command, for command listings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandSpec {
+    /// Keyword after `yolla:`, e.g. "move"
+    pub name: &'static str,
+    /// Shorthand keywords for one form of this command (e.g. "laugh" for
+    /// "react laugh")
+    pub aliases: &'static [&'static str],
+    /// Arguments taken, empty if none
+    pub args: &'static str,
+    /// What the command does
+    pub description: &'static str,
+}
+
+impl CommandSpec {
+    /// Syntax as written in a response, e.g. `[yolla:mood <mood>]`
+    #[must_use]
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            format!("[yolla:{}]", self.name)
+        } else {
+            format!("[yolla:{} {}]", self.name, self.args)
+        }
+    }
+}
+
+/// Every command `CommandParser` understands; keep in sync with `parse_command`
+const SUPPORTED_COMMANDS: &[CommandSpec] = &[
+    // Movement
+    CommandSpec {
+        name: "move",
+        aliases: &["follow"],
+        args: "<tl|tr|bl|br|center|follow> or <x%> <y%>",
+        description: "Move to a corner, the center, or a position",
+    },
+    CommandSpec {
+        name: "wander",
+        aliases: &[],
+        args: "",
+        description: "Start wandering around",
+    },
+    CommandSpec {
+        name: "stop",
+        aliases: &[],
+        args: "",
+        description: "Stop wandering",
+    },
+    CommandSpec {
+        name: "point",
+        aliases: &[],
+        args: "<x%> <y%>",
+        description: "Point at a position",
+    },
+    // Expression
+    CommandSpec {
+        name: "mood",
+        aliases: &[],
+        args: "<happy|thinking|playful|shy|excited|confused|calm|curious>",
+        description: "Change mood",
+    },
+    CommandSpec {
+        name: "size",
+        aliases: &[],
+        args: "<tiny|small|medium|large>",
+        description: "Change size",
+    },
+    CommandSpec {
+        name: "hide",
+        aliases: &[],
+        args: "",
+        description: "Hide the avatar",
+    },
+    CommandSpec {
+        name: "show",
+        aliases: &[],
+        args: "",
+        description: "Show the avatar",
+    },
+    // Gestures
+    CommandSpec {
+        name: "wave",
+        aliases: &[],
+        args: "",
+        description: "Wave",
+    },
+    CommandSpec {
+        name: "nod",
+        aliases: &[],
+        args: "",
+        description: "Nod",
+    },
+    CommandSpec {
+        name: "shake",
+        aliases: &[],
+        args: "",
+        description: "Shake head",
+    },
+    CommandSpec {
+        name: "bounce",
+        aliases: &[],
+        args: "",
+        description: "Bounce",
+    },
+    CommandSpec {
+        name: "spin",
+        aliases: &[],
+        args: "",
+        description: "Spin around",
+    },
+    CommandSpec {
+        name: "dance",
+        aliases: &[],
+        args: "",
+        description: "Dance",
+    },
+    CommandSpec {
+        name: "swim",
+        aliases: &[],
+        args: "",
+        description: "Swim",
+    },
+    CommandSpec {
+        name: "stretch",
+        aliases: &[],
+        args: "",
+        description: "Stretch",
+    },
+    CommandSpec {
+        name: "yawn",
+        aliases: &[],
+        args: "",
+        description: "Yawn",
+    },
+    CommandSpec {
+        name: "wiggle",
+        aliases: &[],
+        args: "",
+        description: "Wiggle",
+    },
+    CommandSpec {
+        name: "peek",
+        aliases: &[],
+        args: "[left|right|top|bottom]",
+        description: "Peek in from an edge (right by default)",
+    },
+    // Reactions
+    CommandSpec {
+        name: "react",
+        aliases: &["laugh", "gasp", "tada", "oops", "love", "wink"],
+        args: "<laugh|gasp|hmm|tada|oops|love|blush|wink|cry|angry|sleepy|dizzy>",
+        description: "Play a quick reaction",
+    },
+    // Custom sprites
+    CommandSpec {
+        name: "sprite",
+        aliases: &[],
+        args: "<name>",
+        description: "Show a custom sprite",
+    },
+    // Task management
+    CommandSpec {
+        name: "task",
+        aliases: &[],
+        args: concat!(
+            "start <agent> <description> | progress <id> <percent> | ",
+            "<done|fail|focus|point|hover|celebrate> <id>"
+        ),
+        description: "Show or react to a background task",
+    },
+];
+
 /// Parser for avatar commands embedded in text
 ///
 /// Extracts `[yolla:command arg1 arg2]` patterns from text,
@@ -401,6 +755,21 @@ impl CommandParser {
         }
     }
 
+    /// Every command the parser understands, with aliases and arguments
+    #[must_use]
+    pub fn supported_commands() -> Vec<CommandSpec> {
+        SUPPORTED_COMMANDS.to_vec()
+    }
+
+    /// Remove all commands from a complete text, discarding them
+    ///
+    /// Unlike per-token `parse()`, this sees commands that were split
+    /// across streaming tokens.
+    #[must_use]
+    pub fn strip_commands(text: &str) -> String {
+        Self::new().parse(text)
+    }
+
     /// Parse text, extract commands, return cleaned text
     ///
     /// Commands are queued and can be retrieved with `next_command()`.
@@ -546,23 +915,7 @@ impl CommandParser {
     }
 
     fn parse_mood(&self, args: &[&str]) -> Option<AvatarCommand> {
-        if args.is_empty() {
-            return None;
-        }
-
-        let mood = match args[0] {
-            "happy" | "joy" => AvatarMood::Happy,
-            "thinking" | "think" | "ponder" => AvatarMood::Thinking,
-            "playful" | "silly" => AvatarMood::Playful,
-            "shy" | "bashful" => AvatarMood::Shy,
-            "excited" | "eager" => AvatarMood::Excited,
-            "confused" | "puzzled" => AvatarMood::Confused,
-            "calm" | "peaceful" => AvatarMood::Calm,
-            "curious" | "interested" => AvatarMood::Curious,
-            _ => return None,
-        };
-
-        Some(AvatarCommand::Mood(mood))
+        AvatarMood::from_name(args.first()?).map(AvatarCommand::Mood)
     }
 
     fn parse_size(&self, args: &[&str]) -> Option<AvatarCommand> {
@@ -701,6 +1054,9 @@ impl Default for CommandParser {
     }
 }
 
+/// Minimum time between automatic task-completion celebrations
+pub const CELEBRATION_COOLDOWN_MS: u64 = 10_000;
+
 /// Avatar state that the Conductor maintains
 ///
 /// This represents the current state of the avatar that UI surfaces
@@ -724,6 +1080,14 @@ pub struct AvatarState {
     pub current_gesture: Option<AvatarGesture>,
     /// Current reaction (if any)
     pub current_reaction: Option<AvatarReaction>,
+    /// Gestures waiting to play after the current one finishes
+    pub gesture_queue: VecDeque<AvatarGesture>,
+    /// When the current queued gesture finishes (monotonic ms)
+    pub gesture_ends_at_ms: Option<u64>,
+    /// When the last automatic celebration started (monotonic ms)
+    pub last_celebration_ms: Option<u64>,
+    /// Activity overlay derived from the conductor state
+    pub activity: AvatarActivity,
 }
 
 impl Default for AvatarState {
@@ -737,6 +1101,10 @@ impl Default for AvatarState {
             wandering: true,
             current_gesture: None,
             current_reaction: None,
+            gesture_queue: VecDeque::new(),
+            gesture_ends_at_ms: None,
+            last_celebration_ms: None,
+            activity: AvatarActivity::None,
         }
     }
 }
@@ -748,6 +1116,17 @@ impl AvatarState {
         Self::default()
     }
 
+    /// Create an avatar state that starts at `position` feeling `mood`
+    #[must_use]
+    pub fn with_defaults(position: AvatarPosition, mood: AvatarMood) -> Self {
+        Self {
+            position,
+            target_position: position,
+            mood,
+            ..Self::default()
+        }
+    }
+
     /// Apply an avatar command to this state
     pub fn apply_command(&mut self, cmd: &AvatarCommand) {
         match cmd {
@@ -779,8 +1158,11 @@ impl AvatarState {
                 self.current_reaction = None;
             }
             AvatarCommand::React(reaction) => {
+                // Reactions take priority and interrupt any queued gestures
                 self.current_reaction = Some(*reaction);
                 self.current_gesture = None;
+                self.gesture_queue.clear();
+                self.gesture_ends_at_ms = None;
             }
             AvatarCommand::Hide => {
                 self.visible = false;
@@ -798,10 +1180,123 @@ impl AvatarState {
         }
     }
 
+    /// Go back to the defaults, keeping the activity overlay
+    ///
+    /// The overlay follows the Conductor's state rather than anything the
+    /// avatar did, so it stays.
+    pub fn reset(&mut self) {
+        self.reset_to(AvatarPosition::default(), AvatarMood::default());
+    }
+
+    /// Like [`reset`](Self::reset), but back to a configured position and mood
+    pub fn reset_to(&mut self, position: AvatarPosition, mood: AvatarMood) {
+        *self = Self {
+            activity: self.activity,
+            ..Self::with_defaults(position, mood)
+        };
+    }
+
     /// Clear any active gesture or reaction
     pub fn clear_animation(&mut self) {
         self.current_gesture = None;
         self.current_reaction = None;
+        self.gesture_queue.clear();
+        self.gesture_ends_at_ms = None;
+    }
+
+    /// Queue a gesture for sequential playback
+    ///
+    /// If no queued gesture is playing, the gesture starts immediately and
+    /// `true` is returned. Otherwise it is appended to the queue and will
+    /// start once the gestures ahead of it have run for their
+    /// `default_duration_ms`.
+    pub fn enqueue_gesture(&mut self, gesture: AvatarGesture, now_ms: u64) -> bool {
+        self.advance_gestures(now_ms);
+
+        if self.gesture_ends_at_ms.is_some() {
+            self.gesture_queue.push_back(gesture);
+            return false;
+        }
+
+        self.start_gesture(gesture, now_ms);
+        true
+    }
+
+    /// Advance the gesture queue to `now_ms`
+    ///
+    /// Returns the gesture that started playing, if the current one finished
+    /// and another was waiting. When the queue runs dry the current gesture
+    /// is cleared.
+    pub fn advance_gestures(&mut self, now_ms: u64) -> Option<AvatarGesture> {
+        let ends_at = self.gesture_ends_at_ms?;
+        if now_ms < ends_at {
+            return None;
+        }
+
+        if let Some(next) = self.gesture_queue.pop_front() {
+            // Start from the scheduled end so late ticks don't stretch the sequence
+            self.start_gesture(next, ends_at);
+            Some(next)
+        } else {
+            self.current_gesture = None;
+            self.gesture_ends_at_ms = None;
+            None
+        }
+    }
+
+    /// A surface finished playing `animation` at `now_ms`
+    ///
+    /// Clears the animation if it's still the current one and returns the
+    /// next queued gesture, which starts right away. Reports for anything
+    /// that's no longer current (it was interrupted, or the queue already
+    /// moved on by its own timing) are ignored.
+    pub fn complete_animation(
+        &mut self,
+        animation: OneShotAnimation,
+        now_ms: u64,
+    ) -> Option<AvatarGesture> {
+        match animation {
+            OneShotAnimation::Gesture(gesture) if self.current_gesture == Some(gesture) => {
+                if let Some(next) = self.gesture_queue.pop_front() {
+                    self.start_gesture(next, now_ms);
+                    return Some(next);
+                }
+                self.current_gesture = None;
+                self.gesture_ends_at_ms = None;
+            }
+            OneShotAnimation::Reaction(reaction) if self.current_reaction == Some(reaction) => {
+                self.current_reaction = None;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Check if a queued gesture is playing or waiting
+    #[must_use]
+    pub fn has_queued_gestures(&self) -> bool {
+        self.gesture_ends_at_ms.is_some() || !self.gesture_queue.is_empty()
+    }
+
+    /// Claim an automatic celebration at `now_ms`
+    ///
+    /// Returns false while the last one is within
+    /// [`CELEBRATION_COOLDOWN_MS`], so a batch of tasks finishing together
+    /// only celebrates once.
+    pub fn try_celebrate(&mut self, now_ms: u64) -> bool {
+        if let Some(last) = self.last_celebration_ms {
+            if now_ms < last + CELEBRATION_COOLDOWN_MS {
+                return false;
+            }
+        }
+        self.last_celebration_ms = Some(now_ms);
+        true
+    }
+
+    fn start_gesture(&mut self, gesture: AvatarGesture, start_ms: u64) {
+        self.current_gesture = Some(gesture);
+        self.current_reaction = None;
+        self.gesture_ends_at_ms = Some(start_ms + u64::from(gesture.default_duration_ms()));
     }
 
     /// Get the suggested animation name for current state
@@ -885,4 +1380,203 @@ mod tests {
         state.apply_command(&AvatarCommand::Mood(AvatarMood::Thinking));
         assert_eq!(state.mood, AvatarMood::Thinking);
     }
+
+    #[test]
+    fn test_mood_animation_overrides() {
+        let overrides =
+            MoodAnimations::parse("interested=wonder, Playful = dance, sleepy=nap, calm=");
+        assert_eq!(overrides.animation_for(AvatarMood::Curious), "wonder");
+        assert_eq!(overrides.animation_for(AvatarMood::Playful), "dance");
+        // Unmapped moods keep their default
+        for mood in [AvatarMood::Happy, AvatarMood::Thinking, AvatarMood::Calm] {
+            assert_eq!(overrides.animation_for(mood), mood.suggested_animation());
+        }
+        assert_eq!(
+            MoodAnimations::default().animation_for(AvatarMood::Curious),
+            "waiting"
+        );
+    }
+
+    #[test]
+    fn test_gesture_from_command() {
+        assert_eq!(
+            AvatarGesture::from_command("peek left"),
+            Some(AvatarGesture::Peek(PeekDirection::Left))
+        );
+        assert_eq!(
+            AvatarGesture::from_command(" wave "),
+            Some(AvatarGesture::Wave)
+        );
+        assert_eq!(AvatarGesture::from_command("mood happy"), None);
+        assert_eq!(AvatarGesture::from_command("moonwalk"), None);
+    }
+
+    #[test]
+    fn test_command_names_round_trip() {
+        let parser = CommandParser::new();
+        for name in ["wave", "spin", "bounce", "wiggle"] {
+            let Some(AvatarCommand::Gesture(gesture)) = parser.parse_command(name) else {
+                panic!("{name} should parse as a gesture");
+            };
+            assert_eq!(gesture.command_name(), name);
+        }
+        for name in ["dizzy", "laugh", "oops", "sleepy"] {
+            let Some(AvatarCommand::React(reaction)) =
+                parser.parse_command(&format!("react {name}"))
+            else {
+                panic!("{name} should parse as a reaction");
+            };
+            assert_eq!(reaction.command_name(), name);
+        }
+        assert_eq!(
+            AvatarGesture::Peek(PeekDirection::Top).command_name(),
+            "peek"
+        );
+    }
+
+    #[test]
+    fn test_supported_commands_cover_parser() {
+        let specs = CommandParser::supported_commands();
+        let listed: Vec<&str> = specs
+            .iter()
+            .flat_map(|spec| std::iter::once(spec.name).chain(spec.aliases.iter().copied()))
+            .collect();
+
+        // Every keyword parse_command matches
+        for keyword in [
+            "move", "wander", "stop", "point", "follow", "mood", "size", "hide", "show", "wave",
+            "nod", "shake", "bounce", "spin", "dance", "swim", "stretch", "yawn", "wiggle", "peek",
+            "react", "laugh", "gasp", "tada", "oops", "love", "wink", "sprite", "task",
+        ] {
+            assert!(listed.contains(&keyword), "{keyword} isn't listed");
+        }
+        assert!(
+            listed
+                .iter()
+                .enumerate()
+                .all(|(i, k)| !listed[..i].contains(k)),
+            "Duplicate keyword in {listed:?}"
+        );
+
+        // ...and every listed keyword parses
+        let parser = CommandParser::new();
+        for spec in &specs {
+            let example = match spec.name {
+                "move" | "point" => format!("{} 10 20", spec.name),
+                "mood" => "mood happy".to_string(),
+                "size" => "size tiny".to_string(),
+                "react" => "react hmm".to_string(),
+                "sprite" => "sprite cactus".to_string(),
+                "task" => "task done t1".to_string(),
+                name => name.to_string(),
+            };
+            assert!(parser.parse_command(&example).is_some(), "{example}");
+            for alias in spec.aliases {
+                assert!(parser.parse_command(alias).is_some(), "{alias}");
+            }
+        }
+        assert_eq!(specs[0].usage(), format!("[yolla:move {}]", specs[0].args));
+    }
+
+    #[test]
+    fn test_gesture_queue_plays_sequentially() {
+        let mut state = AvatarState::new();
+        let wave_ms = u64::from(AvatarGesture::Wave.default_duration_ms());
+
+        assert!(state.enqueue_gesture(AvatarGesture::Wave, 0));
+        assert!(!state.enqueue_gesture(AvatarGesture::Nod, 10));
+        assert_eq!(state.current_gesture, Some(AvatarGesture::Wave));
+
+        // Nod must not start before the wave has finished
+        assert_eq!(state.advance_gestures(wave_ms - 1), None);
+        assert_eq!(state.current_gesture, Some(AvatarGesture::Wave));
+
+        assert_eq!(state.advance_gestures(wave_ms), Some(AvatarGesture::Nod));
+        assert_eq!(state.current_gesture, Some(AvatarGesture::Nod));
+
+        let nod_ms = u64::from(AvatarGesture::Nod.default_duration_ms());
+        assert_eq!(state.advance_gestures(wave_ms + nod_ms), None);
+        assert_eq!(state.current_gesture, None);
+        assert!(!state.has_queued_gestures());
+    }
+
+    #[test]
+    fn test_reaction_interrupts_gesture_queue() {
+        let mut state = AvatarState::new();
+        state.enqueue_gesture(AvatarGesture::Wave, 0);
+        state.enqueue_gesture(AvatarGesture::Nod, 0);
+
+        state.apply_command(&AvatarCommand::React(AvatarReaction::Gasp));
+
+        assert_eq!(state.current_reaction, Some(AvatarReaction::Gasp));
+        assert_eq!(state.current_gesture, None);
+        assert!(!state.has_queued_gestures());
+    }
+
+    #[test]
+    fn test_animation_complete_starts_next_queued_gesture() {
+        let mut state = AvatarState::new();
+        state.enqueue_gesture(AvatarGesture::Wave, 0);
+        state.enqueue_gesture(AvatarGesture::Nod, 0);
+
+        // Finished early: the next gesture starts now, not at Wave's end
+        let next = state.complete_animation(OneShotAnimation::Gesture(AvatarGesture::Wave), 100);
+        assert_eq!(next, Some(AvatarGesture::Nod));
+        assert_eq!(state.current_gesture, Some(AvatarGesture::Nod));
+        assert_eq!(
+            state.gesture_ends_at_ms,
+            Some(100 + u64::from(AvatarGesture::Nod.default_duration_ms()))
+        );
+
+        let next = state.complete_animation(OneShotAnimation::Gesture(AvatarGesture::Nod), 200);
+        assert_eq!(next, None);
+        assert_eq!(state.current_gesture, None);
+        assert!(!state.has_queued_gestures());
+    }
+
+    #[test]
+    fn test_animation_complete_ignores_stale_reports() {
+        let mut state = AvatarState::new();
+        state.enqueue_gesture(AvatarGesture::Wave, 0);
+        state.enqueue_gesture(AvatarGesture::Nod, 0);
+
+        let next = state.complete_animation(OneShotAnimation::Gesture(AvatarGesture::Bounce), 100);
+        assert_eq!(next, None);
+        assert_eq!(state.current_gesture, Some(AvatarGesture::Wave));
+        assert_eq!(state.gesture_queue, [AvatarGesture::Nod]);
+
+        state.apply_command(&AvatarCommand::React(AvatarReaction::Tada));
+        state.complete_animation(OneShotAnimation::Reaction(AvatarReaction::Oops), 100);
+        assert_eq!(state.current_reaction, Some(AvatarReaction::Tada));
+        state.complete_animation(OneShotAnimation::Reaction(AvatarReaction::Tada), 100);
+        assert_eq!(state.current_reaction, None);
+    }
+
+    #[test]
+    fn test_celebration_cooldown() {
+        let mut state = AvatarState::new();
+        assert!(state.try_celebrate(1_000));
+        assert!(!state.try_celebrate(1_000));
+        assert!(!state.try_celebrate(1_000 + CELEBRATION_COOLDOWN_MS - 1));
+        assert!(state.try_celebrate(1_000 + CELEBRATION_COOLDOWN_MS));
+    }
+
+    #[test]
+    fn test_reset_keeps_only_activity() {
+        let mut state = AvatarState::new();
+        state.apply_command(&AvatarCommand::MoveTo(AvatarPosition::Center));
+        state.apply_command(&AvatarCommand::Mood(AvatarMood::Thinking));
+        state.apply_command(&AvatarCommand::Hide);
+        state.enqueue_gesture(AvatarGesture::Wave, 0);
+        state.enqueue_gesture(AvatarGesture::Nod, 0);
+        state.activity = AvatarActivity::Thinking;
+
+        state.reset();
+        assert_eq!(state.target_position, AvatarPosition::BottomRight);
+        assert_eq!(state.mood, AvatarMood::Happy);
+        assert!(state.visible);
+        assert_eq!(state.current_gesture, None);
+        assert!(!state.has_queued_gestures());
+        assert_eq!(state.activity, AvatarActivity::Thinking);
+    }
 }
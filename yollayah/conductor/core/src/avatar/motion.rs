@@ -0,0 +1,186 @@
+//! Avatar Motion
+//!
+//! Surfaces place the avatar on a grid of cells, but stepping it a whole
+//! cell per frame ties its speed to the frame rate and looks jerky.
+//! [`MotionInterpolator`] tracks the position in fractional cells and moves
+//! it towards the target at a fixed speed; surfaces draw the avatar at
+//! [`MotionInterpolator::nearest_cell`] each frame.
+
+use std::time::Duration;
+
+/// Default avatar movement speed in cells per second
+pub const DEFAULT_MOVE_SPEED: f32 = 10.0;
+
+/// Sub-cell avatar position moving towards a target at constant speed
+#[derive(Clone, Debug, PartialEq)]
+pub struct MotionInterpolator {
+    /// Current position in cells
+    position: (f32, f32),
+    /// Where the avatar is heading
+    target: (f32, f32),
+    /// Cells per second along the straight line to the target
+    speed: f32,
+}
+
+impl MotionInterpolator {
+    /// Start at rest at the given position
+    ///
+    /// A speed that isn't a positive number falls back to
+    /// [`DEFAULT_MOVE_SPEED`].
+    #[must_use]
+    pub fn new(x: f32, y: f32, speed: f32) -> Self {
+        let mut motion = Self {
+            position: (x, y),
+            target: (x, y),
+            speed: DEFAULT_MOVE_SPEED,
+        };
+        motion.set_speed(speed);
+        motion
+    }
+
+    /// Change the movement speed (cells per second)
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = if speed.is_finite() && speed > 0.0 {
+            speed
+        } else {
+            DEFAULT_MOVE_SPEED
+        };
+    }
+
+    /// Movement speed in cells per second
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Head towards a new position
+    pub fn set_target(&mut self, x: f32, y: f32) {
+        self.target = (x, y);
+    }
+
+    /// Where the avatar is heading
+    #[must_use]
+    pub fn target(&self) -> (f32, f32) {
+        self.target
+    }
+
+    /// Current position in fractional cells
+    #[must_use]
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    /// Whether the avatar hasn't reached its target yet
+    #[must_use]
+    pub fn is_moving(&self) -> bool {
+        self.position != self.target
+    }
+
+    /// Keep both the position and the target within `0..=max` (after a resize)
+    pub fn clamp_to(&mut self, max_x: f32, max_y: f32) {
+        let clamp = |(x, y): (f32, f32)| (x.clamp(0.0, max_x), y.clamp(0.0, max_y));
+        self.position = clamp(self.position);
+        self.target = clamp(self.target);
+    }
+
+    /// Advance by `elapsed`, stopping exactly at the target
+    pub fn step(&mut self, elapsed: Duration) {
+        let (dx, dy) = (
+            self.target.0 - self.position.0,
+            self.target.1 - self.position.1,
+        );
+        let distance = dx.hypot(dy);
+        let travel = self.speed * elapsed.as_secs_f32();
+        if distance <= travel {
+            self.position = self.target;
+        } else {
+            let t = travel / distance;
+            self.position = (self.position.0 + dx * t, self.position.1 + dy * t);
+        }
+    }
+
+    /// Cell to draw the avatar in
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn nearest_cell(&self) -> (u16, u16) {
+        let round = |v: f32| v.round().clamp(0.0, f32::from(u16::MAX)) as u16;
+        (round(self.position.0), round(self.position.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn test_position_advances_smoothly() {
+        let mut motion = MotionInterpolator::new(0.0, 0.0, 10.0);
+        motion.set_target(3.0, 4.0);
+
+        // Half a cell per 50ms frame along the line to (3, 4)
+        let mut previous = motion.position();
+        for _ in 0..9 {
+            motion.step(FRAME);
+            let position = motion.position();
+            let moved = (position.0 - previous.0).hypot(position.1 - previous.1);
+            assert!((moved - 0.5).abs() < 1e-4, "moved {moved}");
+            previous = position;
+        }
+        assert!(motion.is_moving());
+
+        motion.step(FRAME);
+        assert_eq!(motion.position(), (3.0, 4.0));
+        assert!(!motion.is_moving());
+    }
+
+    #[test]
+    fn test_rendered_cell_lags_without_overshoot() {
+        // 0.4 cells per frame: the drawn cell trails until the position is nearer the next one
+        let mut motion = MotionInterpolator::new(2.0, 5.0, 8.0);
+        motion.set_target(6.0, 5.0);
+
+        let mut cells = vec![motion.nearest_cell()];
+        for _ in 0..20 {
+            motion.step(FRAME);
+            let (x, _) = motion.position();
+            let cell = motion.nearest_cell();
+            // Never more than half a cell from the true position
+            assert!((f32::from(cell.0) - x).abs() <= 0.5);
+            assert!(cell.0 <= 6);
+            cells.push(cell);
+        }
+
+        // The cell only ever moves forward, one at a time, and settles
+        assert!(cells.windows(2).all(|w| w[1].0 - w[0].0 <= 1));
+        assert_eq!(&cells[..4], &[(2, 5), (2, 5), (3, 5), (3, 5)]);
+        assert_eq!(*cells.last().unwrap(), (6, 5));
+    }
+
+    #[test]
+    fn test_large_step_stops_at_target() {
+        let mut motion = MotionInterpolator::new(10.0, 10.0, 10.0);
+        motion.set_target(0.0, 10.0);
+        motion.step(Duration::from_secs(5));
+        assert_eq!(motion.position(), (0.0, 10.0));
+        assert_eq!(motion.nearest_cell(), (0, 10));
+    }
+
+    #[test]
+    fn test_invalid_speed_uses_default() {
+        for speed in [0.0, -3.0, f32::NAN, f32::INFINITY] {
+            let motion = MotionInterpolator::new(0.0, 0.0, speed);
+            assert!((motion.speed() - DEFAULT_MOVE_SPEED).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_clamp_keeps_position_and_target_in_bounds() {
+        let mut motion = MotionInterpolator::new(40.0, 20.0, 10.0);
+        motion.set_target(50.0, 2.0);
+        motion.clamp_to(30.0, 10.0);
+        assert_eq!(motion.position(), (30.0, 10.0));
+        assert_eq!(motion.target(), (30.0, 2.0));
+    }
+}
@@ -359,6 +359,22 @@ pub fn validate_animation_duration(duration: Duration) -> SecurityResult<()> {
     Ok(())
 }
 
+/// Validate a sprite cache budget
+///
+/// # Arguments
+///
+/// * `size` - The cache budget in bytes
+///
+/// # Returns
+///
+/// `Ok(())` if the budget is within the hard ceiling, `Err(SecurityError::CacheSizeExceeded)` otherwise
+pub fn validate_cache_size(size: usize) -> SecurityResult<()> {
+    if size > MAX_CACHE_SIZE_BYTES {
+        return Err(SecurityError::CacheSizeExceeded { size });
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Sprite Validation
 // =============================================================================
@@ -1287,6 +1303,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_validate_cache_size() {
+        assert!(validate_cache_size(0).is_ok());
+        assert!(validate_cache_size(MAX_CACHE_SIZE_BYTES).is_ok());
+        assert!(matches!(
+            validate_cache_size(MAX_CACHE_SIZE_BYTES + 1),
+            Err(SecurityError::CacheSizeExceeded { .. })
+        ));
+    }
+
     // =========================================================================
     // Rate Limiter Tests
     // =========================================================================
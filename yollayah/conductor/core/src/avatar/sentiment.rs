@@ -0,0 +1,122 @@
+//! User Sentiment Detection
+//!
+//! Cheap keyword matching on incoming user messages so Yollayah can react
+//! before the LLM has produced a single token. This is intentionally not
+//! clever: it only needs to catch the obvious "ugh, this is broken" cases.
+
+use super::AvatarMood;
+
+/// Phrases that suggest the user is frustrated
+const FRUSTRATED_PHRASES: &[&str] = &[
+    "this is broken",
+    "still broken",
+    "doesn't work",
+    "does not work",
+    "not working",
+    "hate this",
+    "give up",
+];
+
+/// Single words that suggest the user is frustrated
+const FRUSTRATED_WORDS: &[&str] = &[
+    "argh",
+    "wtf",
+    "useless",
+    "annoying",
+    "frustrating",
+    "frustrated",
+];
+
+/// Rough sentiment of a user message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserSentiment {
+    /// Nothing notable
+    Neutral,
+    /// The user seems frustrated
+    Frustrated,
+}
+
+impl UserSentiment {
+    /// Detect sentiment from message text
+    #[must_use]
+    pub fn detect(text: &str) -> Self {
+        let text = text.to_lowercase();
+        let frustrated = FRUSTRATED_PHRASES
+            .iter()
+            .any(|phrase| text.contains(phrase))
+            || text
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| FRUSTRATED_WORDS.contains(&word) || is_ugh(word));
+
+        if frustrated {
+            Self::Frustrated
+        } else {
+            Self::Neutral
+        }
+    }
+
+    /// Mood the avatar should take on in response, if any
+    #[must_use]
+    pub fn empathetic_mood(self) -> Option<AvatarMood> {
+        match self {
+            Self::Neutral => None,
+            Self::Frustrated => Some(AvatarMood::Calm),
+        }
+    }
+}
+
+/// "ugh", "ughh", "uugh", ... but not "laugh" or "though"
+fn is_ugh(word: &str) -> bool {
+    let after_u = word.trim_start_matches('u');
+    let after_g = after_u.trim_start_matches('g');
+    let after_h = after_g.trim_start_matches('h');
+    after_u.len() < word.len()
+        && after_g.len() < after_u.len()
+        && after_h.len() < after_g.len()
+        && after_h.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_frustration() {
+        for text in [
+            "Ugh, this is broken again",
+            "uuugghhh",
+            "The build still doesn't work!!",
+            "WTF is going on",
+        ] {
+            assert_eq!(
+                UserSentiment::detect(text),
+                UserSentiment::Frustrated,
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ignores_neutral_messages() {
+        for text in [
+            "Can you help me write a test?",
+            "That made me laugh, though",
+            "Is the worker thread broken up into chunks?",
+        ] {
+            assert_eq!(
+                UserSentiment::detect(text),
+                UserSentiment::Neutral,
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_empathetic_mood() {
+        assert_eq!(
+            UserSentiment::Frustrated.empathetic_mood(),
+            Some(AvatarMood::Calm)
+        );
+        assert_eq!(UserSentiment::Neutral.empathetic_mood(), None);
+    }
+}
@@ -93,6 +93,15 @@ impl AnimationType {
             Self::Swimming => "swimming",
         }
     }
+
+    /// Look up the animation type for an animation name
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|t| t.animation_name() == name)
+    }
 }
 
 // =============================================================================
@@ -680,6 +689,17 @@ mod tests {
         assert_eq!(AnimationType::Swimming.animation_name(), "swimming");
     }
 
+    #[test]
+    fn test_animation_type_from_name() {
+        for animation_type in AnimationType::all() {
+            assert_eq!(
+                AnimationType::from_name(animation_type.animation_name()),
+                Some(*animation_type)
+            );
+        }
+        assert_eq!(AnimationType::from_name("moonwalk"), None);
+    }
+
     // =========================================================================
     // AnimationVariant Tests
     // =========================================================================
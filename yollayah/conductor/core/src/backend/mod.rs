@@ -6,6 +6,7 @@
 //! # Available Backends
 //!
 //! - **Ollama**: Local LLM server (default)
+//! - **Replay**: Streams back fixed chunks, for tests and fuzzing
 //! - More to come: `OpenAI`, Anthropic, etc.
 //!
 //! # Usage
@@ -19,7 +20,15 @@
 //! ```
 
 mod ollama;
+mod replay;
 mod traits;
 
 pub use ollama::OllamaBackend;
-pub use traits::{BackendConfig, LlmBackend, LlmRequest, LlmResponse, ModelInfo, StreamingToken};
+pub use replay::ReplayBackend;
+pub use traits::{
+    BackendConfig, FinishReason, LlmBackend, LlmRequest, LlmResponse, ModelInfo, RetryConfig,
+    StreamingToken, DEFAULT_REQUEST_TIMEOUT,
+};
+
+/// Passed to [`LlmBackend::send_streaming_cancellable`] to stop a response
+pub use tokio_util::sync::CancellationToken;
@@ -16,12 +16,216 @@ use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use futures::StreamExt;
+use thiserror::Error;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 use super::traits::{
-    BackendConfig, LlmBackend, LlmRequest, LlmResponse, ModelInfo, StreamingToken,
+    request_timeout_from_env, stream_batch_ms_from_env, BackendConfig, FinishReason, LlmBackend,
+    LlmRequest, LlmResponse, ModelInfo, RetryConfig, StreamingToken, DEFAULT_REQUEST_TIMEOUT,
 };
 
+/// Longest excerpt of a response body quoted in an error message
+const ERROR_SNIPPET_LEN: usize = 200;
+
+/// Why an Ollama request failed, worded for the user
+#[derive(Debug, Error)]
+enum OllamaError {
+    /// The server couldn't be reached
+    #[error("Couldn't connect to Ollama at {url}: {reason}")]
+    Connection { url: String, reason: String },
+    /// The server didn't respond within the request timeout
+    #[error("Ollama didn't respond within {}s", .0.as_secs_f32())]
+    Timeout(Duration),
+    /// The connection dropped partway through a response
+    #[error("Lost connection to Ollama mid-response: {0}")]
+    Interrupted(String),
+    /// The server answered with a non-success status
+    #[error("Ollama returned HTTP {status}: {message}")]
+    Status { status: u16, message: String },
+    /// The server reported an error in the middle of a stream
+    #[error("Ollama error: {0}")]
+    Server(String),
+    /// A response line wasn't valid JSON
+    #[error("Couldn't parse Ollama response ({reason}): {snippet}")]
+    Decode { reason: String, snippet: String },
+    /// The response wasn't UTF-8 (a misconfigured endpoint, usually)
+    #[error("Ollama sent an invalid response encoding: {0}")]
+    Encoding(std::str::Utf8Error),
+}
+
+impl OllamaError {
+    fn connection(url: &str, error: &reqwest::Error) -> Self {
+        // reqwest's own message is just "error sending request"; the cause is more useful
+        let mut cause: &dyn std::error::Error = error;
+        while let Some(source) = cause.source() {
+            cause = source;
+        }
+        Self::Connection {
+            url: url.to_string(),
+            reason: cause.to_string(),
+        }
+    }
+
+    /// Prefer Ollama's `{"error": "..."}` message over the raw body
+    fn status(status: reqwest::StatusCode, body: &str) -> Self {
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|data| data.get("error")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| snippet(body));
+        Self::Status {
+            status: status.as_u16(),
+            message,
+        }
+    }
+
+    /// Whether trying again might succeed
+    ///
+    /// Ollama refuses connections while it restarts and can answer 5xx while
+    /// a model loads. 4xx responses are the request's fault, and a timeout
+    /// has already made the caller wait long enough.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Connection { .. } => true,
+            Self::Status { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+/// Trimmed excerpt of a response body, cut at `ERROR_SNIPPET_LEN` characters
+fn snippet(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(ERROR_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+/// Split the next non-blank line off the front of a streaming buffer
+///
+/// Lines are only decoded once complete, so a character split across
+/// chunks is reassembled instead of turning into replacement characters.
+fn next_line(buffer: &mut Vec<u8>) -> Option<Result<String, OllamaError>> {
+    loop {
+        let end = buffer.iter().position(|&b| b == b'\n')?;
+        let line: Vec<u8> = buffer.drain(..=end).collect();
+        match String::from_utf8(line) {
+            Ok(line) if line.trim().is_empty() => {}
+            Ok(line) => return Some(Ok(line.trim().to_string())),
+            Err(e) => return Some(Err(OllamaError::Encoding(e.utf8_error()))),
+        }
+    }
+}
+
+/// One parsed line of a streaming response
+#[derive(Debug, PartialEq)]
+struct StreamLine {
+    /// Response text in this line
+    token: Option<String>,
+    /// Whether this is the final line
+    done: bool,
+    /// Why generation stopped (`done_reason`, final line only)
+    finish_reason: Option<FinishReason>,
+}
+
+/// Parse one line of a streaming response
+fn parse_stream_line(line: &str) -> Result<StreamLine, OllamaError> {
+    let data: serde_json::Value = serde_json::from_str(line).map_err(|e| OllamaError::Decode {
+        reason: e.to_string(),
+        snippet: snippet(line),
+    })?;
+
+    if let Some(error) = data.get("error") {
+        let message = error
+            .as_str()
+            .map_or_else(|| error.to_string(), str::to_string);
+        return Err(OllamaError::Server(message));
+    }
+
+    let token = data
+        .get("response")
+        .and_then(|r| r.as_str())
+        .map(str::to_string);
+    let done = data
+        .get("done")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    let finish_reason = data
+        .get("done_reason")
+        .and_then(|r| r.as_str())
+        .map(FinishReason::from_backend);
+    Ok(StreamLine {
+        token,
+        done,
+        finish_reason,
+    })
+}
+
+/// Combines tokens that arrive within a short interval of each other
+///
+/// Fast models produce hundreds of tiny tokens per second; batching them
+/// keeps channel traffic (and downstream redraws) down.
+struct TokenBatcher {
+    /// How long to hold tokens (zero = don't batch)
+    interval: Duration,
+    /// Text held back so far
+    pending: String,
+    /// When the pending text must go out
+    deadline: Option<Instant>,
+}
+
+impl TokenBatcher {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pending: String::new(),
+            deadline: None,
+        }
+    }
+
+    /// Add a token, returning text that's due to be sent
+    fn push(&mut self, token: &str) -> Option<String> {
+        if self.interval.is_zero() {
+            return Some(token.to_string());
+        }
+        self.pending.push_str(token);
+        let deadline = *self
+            .deadline
+            .get_or_insert_with(|| Instant::now() + self.interval);
+        if Instant::now() >= deadline {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// Take whatever is pending
+    fn flush(&mut self) -> Option<String> {
+        self.deadline = None;
+        if self.pending.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.pending))
+        }
+    }
+
+    /// Send whatever is pending; false if the receiver has gone away
+    async fn flush_to(&mut self, tx: &mpsc::Sender<StreamingToken>) -> bool {
+        match self.flush() {
+            Some(text) => tx.send(StreamingToken::Token(text)).await.is_ok(),
+            None => true,
+        }
+    }
+
+    /// How long to wait for the next chunk before pending text is due
+    fn wait(&self, request_timeout: Duration) -> Duration {
+        self.deadline.map_or(request_timeout, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        })
+    }
+}
+
 /// Ollama backend client
 #[derive(Clone)]
 pub struct OllamaBackend {
@@ -29,6 +233,12 @@ pub struct OllamaBackend {
     host: String,
     /// Port number
     port: u16,
+    /// Max wait for connecting, the first response, and each next token
+    request_timeout: Duration,
+    /// Tokens arriving within this interval are sent as one (zero = off)
+    stream_batch: Duration,
+    /// How transient failures are retried
+    retry: RetryConfig,
     /// HTTP client
     http_client: reqwest::Client,
 }
@@ -39,22 +249,130 @@ impl OllamaBackend {
         Self {
             host: host.into(),
             port,
-            http_client: reqwest::Client::builder()
-                .timeout(Duration::from_secs(30))  // Reduced from 120s - fail fast on errors
-                .build()
-                .expect("Failed to create HTTP client"),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            stream_batch: Duration::ZERO,
+            retry: RetryConfig::default(),
+            http_client: Self::build_client(DEFAULT_REQUEST_TIMEOUT),
         }
     }
 
+    /// Set the request timeout
+    ///
+    /// Applies to the initial connection, to waiting for the response to
+    /// start, and to the gap between streamed tokens. A streaming request
+    /// that times out before the response starts returns `Err`; one that
+    /// stalls mid-stream yields `StreamingToken::Error` instead of hanging.
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self.http_client = Self::build_client(timeout);
+        self
+    }
+
+    /// Combine tokens that arrive within `interval` into one streamed token
+    ///
+    /// `Duration::ZERO` (the default) forwards every token as it arrives.
+    #[must_use]
+    pub fn with_stream_batch(mut self, interval: Duration) -> Self {
+        self.stream_batch = interval;
+        self
+    }
+
+    /// Set how connection failures and 5xx responses are retried
+    ///
+    /// Only the initial request is retried; once a response starts
+    /// streaming, a failure is reported rather than replayed.
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Create from `BackendConfig`
     #[must_use]
     pub fn from_config(config: &BackendConfig) -> Option<Self> {
         match config {
-            BackendConfig::Ollama { host, port } => Some(Self::new(host.clone(), *port)),
+            BackendConfig::Ollama {
+                host,
+                port,
+                request_timeout,
+                stream_batch_ms,
+                retry,
+            } => Some(
+                Self::new(host.clone(), *port)
+                    .with_request_timeout(*request_timeout)
+                    .with_stream_batch(Duration::from_millis(*stream_batch_ms))
+                    .with_retry(retry.clone()),
+            ),
             _ => None,
         }
     }
 
+    fn build_client(timeout: Duration) -> reqwest::Client {
+        // No overall timeout: long generations are fine as long as tokens keep coming
+        reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+
+    fn timeout_message(&self) -> String {
+        OllamaError::Timeout(self.request_timeout).to_string()
+    }
+
+    /// POST `body` to `url`, retrying transient failures with backoff
+    ///
+    /// Non-success responses come back as `OllamaError::Status`.
+    async fn post(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        stream: bool,
+    ) -> Result<reqwest::Response, OllamaError> {
+        let mut retry = 0;
+        loop {
+            let error = match self.post_once(url, body, stream).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            if retry >= self.retry.max_retries || !error.is_transient() {
+                return Err(error);
+            }
+            let delay = self.retry.delay_for_retry(retry);
+            retry += 1;
+            tracing::debug!(
+                "Retrying Ollama request ({retry}/{}) in {delay:?}: {error}",
+                self.retry.max_retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn post_once(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        stream: bool,
+    ) -> Result<reqwest::Response, OllamaError> {
+        let mut builder = self.http_client.post(url).json(body);
+        if !stream {
+            // A complete response has to arrive within the timeout
+            builder = builder.timeout(self.request_timeout);
+        }
+        let response = match tokio::time::timeout(self.request_timeout, builder.send()).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) if !e.is_timeout() => return Err(OllamaError::connection(url, &e)),
+            Ok(Err(_)) | Err(_) => return Err(OllamaError::Timeout(self.request_timeout)),
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(OllamaError::status(status, &body));
+        }
+        Ok(response)
+    }
+
     /// Create from environment variables
     #[must_use]
     pub fn from_env() -> Self {
@@ -68,6 +386,8 @@ impl OllamaBackend {
             .unwrap_or(11434);
 
         Self::new(host, port)
+            .with_request_timeout(request_timeout_from_env())
+            .with_stream_batch(Duration::from_millis(stream_batch_ms_from_env()))
     }
 
     /// Get the base URL
@@ -102,6 +422,41 @@ impl OllamaBackend {
         full_prompt.push_str(&request.prompt);
         full_prompt
     }
+    /// Build the `/api/generate` request body
+    fn build_body(&self, request: &LlmRequest, stream: bool) -> serde_json::Value {
+        let mut json_request = serde_json::json!({
+            "model": request.model,
+            "prompt": self.build_prompt(request),
+            "stream": stream,
+        });
+
+        if !request.images.is_empty() {
+            json_request["images"] = serde_json::json!(request.images);
+        }
+
+        // Add optional parameters
+        if request.temperature != 0.7 {
+            json_request["options"] = serde_json::json!({
+                "temperature": request.temperature
+            });
+        }
+
+        if request.max_tokens > 0 {
+            let options = json_request["options"].as_object_mut().map(|o| {
+                o.insert(
+                    "num_predict".to_string(),
+                    serde_json::json!(request.max_tokens),
+                );
+            });
+            if options.is_none() {
+                json_request["options"] = serde_json::json!({
+                    "num_predict": request.max_tokens
+                });
+            }
+        }
+
+        json_request
+    }
 }
 
 impl Default for OllamaBackend {
@@ -116,6 +471,12 @@ impl LlmBackend for OllamaBackend {
         "Ollama"
     }
 
+    fn is_transient_error(&self, error: &anyhow::Error) -> bool {
+        error
+            .downcast_ref::<OllamaError>()
+            .is_some_and(OllamaError::is_transient)
+    }
+
     async fn health_check(&self) -> bool {
         self.http_client
             .get(self.tags_url())
@@ -128,115 +489,113 @@ impl LlmBackend for OllamaBackend {
     async fn send_streaming(
         &self,
         request: &LlmRequest,
+    ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
+        self.send_streaming_cancellable(request, CancellationToken::new())
+            .await
+    }
+
+    async fn send_streaming_cancellable(
+        &self,
+        request: &LlmRequest,
+        cancel: CancellationToken,
     ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
         let (tx, rx) = mpsc::channel(256); // Increased for fast streaming (200+ tok/sec)
 
         let url = self.generate_url();
-        let prompt = self.build_prompt(request);
-
-        let mut json_request = serde_json::json!({
-            "model": request.model,
-            "prompt": prompt,
-            "stream": true,
-        });
-
-        // Add optional parameters
-        if request.temperature != 0.7 {
-            json_request["options"] = serde_json::json!({
-                "temperature": request.temperature
-            });
-        }
-
-        if request.max_tokens > 0 {
-            let options = json_request["options"].as_object_mut().map(|o| {
-                o.insert(
-                    "num_predict".to_string(),
-                    serde_json::json!(request.max_tokens),
-                );
-            });
-            if options.is_none() {
-                json_request["options"] = serde_json::json!({
-                    "num_predict": request.max_tokens
-                });
-            }
-        }
-
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&json_request)
-            .send()
-            .await?;
+        let json_request = self.build_body(request, true);
 
-        // Check for HTTP errors
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Ollama returned {status}: {body}");
-        }
+        let response = tokio::select! {
+            // Closing the channel without a token ends the stream
+            () = cancel.cancelled() => return Ok(rx),
+            response = self.post(&url, &json_request, true) => response,
+        };
+        // Nothing has streamed yet, so callers can still fall back or retry
+        let response = response?;
 
         let mut stream = response.bytes_stream();
+        let request_timeout = self.request_timeout;
+        let timeout_message = self.timeout_message();
+        let mut batcher = TokenBatcher::new(self.stream_batch);
 
         // Spawn task to process stream
         tokio::spawn(async move {
-            let mut buffer = String::new();
+            let mut buffer = Vec::new();
             let mut full_response = String::new();
 
-            while let Some(chunk) = stream.next().await {
+            loop {
+                let wait = batcher.wait(request_timeout);
+                let next = tokio::select! {
+                    // Dropping the response closes the connection, which
+                    // makes Ollama stop generating
+                    () = cancel.cancelled() => return,
+                    next = tokio::time::timeout(wait, stream.next()) => next,
+                };
+                let chunk = match next {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    // Batched tokens are due
+                    Err(_) if batcher.deadline.is_some() => {
+                        if batcher.flush_to(&tx).await {
+                            continue;
+                        }
+                        return;
+                    }
+                    Err(_) => {
+                        let _ = tx.send(StreamingToken::Error(timeout_message)).await;
+                        return;
+                    }
+                };
                 match chunk {
                     Ok(bytes) => {
-                        buffer.push_str(&String::from_utf8_lossy(&bytes));
+                        buffer.extend_from_slice(&bytes);
 
                         // Parse newline-delimited JSON
-                        while let Some(pos) = buffer.find('\n') {
-                            let line = buffer[..pos].trim();
-                            if !line.is_empty() {
-                                if let Ok(data) = serde_json::from_str::<serde_json::Value>(line) {
-                                    // Extract token
-                                    if let Some(token) =
-                                        data.get("response").and_then(|r| r.as_str())
-                                    {
-                                        full_response.push_str(token);
-                                        if tx
-                                            .send(StreamingToken::Token(token.to_string()))
-                                            .await
-                                            .is_err()
-                                        {
-                                            // Receiver dropped, stop streaming
-                                            return;
-                                        }
-                                    }
+                        while let Some(line) = next_line(&mut buffer) {
+                            let parsed = match line.and_then(|line| parse_stream_line(&line)) {
+                                Ok(parsed) => parsed,
+                                Err(e) => {
+                                    let _ = tx.send(StreamingToken::Error(e.to_string())).await;
+                                    return;
+                                }
+                            };
 
-                                    // Check if done
-                                    if data
-                                        .get("done")
-                                        .and_then(serde_json::Value::as_bool)
-                                        .unwrap_or(false)
-                                    {
-                                        let _ = tx
-                                            .send(StreamingToken::Complete {
-                                                message: full_response,
-                                            })
-                                            .await;
+                            if let Some(token) = parsed.token {
+                                full_response.push_str(&token);
+                                if let Some(text) = batcher.push(&token) {
+                                    if tx.send(StreamingToken::Token(text)).await.is_err() {
+                                        // Receiver dropped, stop streaming
                                         return;
                                     }
                                 }
                             }
-                            buffer = buffer[pos + 1..].to_string();
+
+                            if parsed.done {
+                                batcher.flush_to(&tx).await;
+                                let _ = tx
+                                    .send(StreamingToken::Complete {
+                                        message: full_response,
+                                        finish_reason: parsed.finish_reason,
+                                    })
+                                    .await;
+                                return;
+                            }
                         }
                     }
                     Err(e) => {
-                        let _ = tx.send(StreamingToken::Error(e.to_string())).await;
+                        let error = OllamaError::Interrupted(e.to_string());
+                        let _ = tx.send(StreamingToken::Error(error.to_string())).await;
                         return;
                     }
                 }
             }
 
             // Stream ended without done signal
+            batcher.flush_to(&tx).await;
             if !full_response.is_empty() {
                 let _ = tx
                     .send(StreamingToken::Complete {
                         message: full_response,
+                        finish_reason: None,
                     })
                     .await;
             }
@@ -248,48 +607,12 @@ impl LlmBackend for OllamaBackend {
     async fn send(&self, request: &LlmRequest) -> anyhow::Result<LlmResponse> {
         let start = Instant::now();
         let url = self.generate_url();
-        let prompt = self.build_prompt(request);
+        let json_request = self.build_body(request, false);
 
-        let mut json_request = serde_json::json!({
-            "model": request.model,
-            "prompt": prompt,
-            "stream": false,
-        });
-
-        if request.temperature != 0.7 {
-            json_request["options"] = serde_json::json!({
-                "temperature": request.temperature
-            });
-        }
-
-        if request.max_tokens > 0 {
-            let options = json_request["options"].as_object_mut().map(|o| {
-                o.insert(
-                    "num_predict".to_string(),
-                    serde_json::json!(request.max_tokens),
-                );
-            });
-            if options.is_none() {
-                json_request["options"] = serde_json::json!({
-                    "num_predict": request.max_tokens
-                });
-            }
-        }
-
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&json_request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Ollama returned {status}: {body}");
-        }
-
-        let data: serde_json::Value = response.json().await?;
+        let response = self.post(&url, &json_request, false).await?;
+        let bytes = response.bytes().await?;
+        let body = std::str::from_utf8(&bytes).map_err(OllamaError::Encoding)?;
+        let data: serde_json::Value = serde_json::from_str(body)?;
 
         let content = data
             .get("response")
@@ -394,16 +717,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_body_carries_images() {
+        let backend = OllamaBackend::default();
+
+        let request = LlmRequest::new("What's this?", "llava")
+            .with_images(vec!["aGVsbG8=".to_string(), "d29ybGQ=".to_string()]);
+        let body = backend.build_body(&request, true);
+        assert_eq!(body["images"], serde_json::json!(["aGVsbG8=", "d29ybGQ="]));
+
+        // Text-only requests don't send an images array
+        let body = backend.build_body(&LlmRequest::new("Hi", "llava"), true);
+        assert!(body.get("images").is_none());
+    }
+
     #[test]
     fn test_from_config() {
         let config = BackendConfig::Ollama {
             host: "example.com".to_string(),
             port: 8080,
+            request_timeout: Duration::from_secs(5),
+            stream_batch_ms: 20,
+            retry: RetryConfig::none(),
         };
 
         let backend = OllamaBackend::from_config(&config).unwrap();
         assert_eq!(backend.host, "example.com");
         assert_eq!(backend.port, 8080);
+        assert_eq!(backend.request_timeout, Duration::from_secs(5));
+        assert_eq!(backend.stream_batch, Duration::from_millis(20));
+        assert_eq!(backend.retry, RetryConfig::none());
 
         // Wrong config type returns None
         let config = BackendConfig::OpenAI {
@@ -412,4 +755,483 @@ mod tests {
         };
         assert!(OllamaBackend::from_config(&config).is_none());
     }
+
+    /// Accept one connection, optionally write `response`, then stall
+    async fn stalling_server(response: Option<&str>) -> u16 {
+        stalling_server_bytes(response.map(|r| r.as_bytes().to_vec())).await
+    }
+
+    /// Like `stalling_server`, for responses that aren't valid UTF-8
+    async fn stalling_server_bytes(response: Option<Vec<u8>>) -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            if let Some(response) = response {
+                socket.write_all(&response).await.unwrap();
+            }
+            // Hold the connection open without sending anything else
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            drop(socket);
+        });
+        port
+    }
+
+    async fn next_token(rx: &mut mpsc::Receiver<StreamingToken>) -> StreamingToken {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("Backend hung instead of timing out")
+            .expect("Stream closed without a token")
+    }
+
+    #[tokio::test]
+    async fn test_streaming_times_out_when_server_never_responds() {
+        let port = stalling_server(None).await;
+        let backend =
+            OllamaBackend::new("127.0.0.1", port).with_request_timeout(Duration::from_millis(200));
+
+        let error = tokio::time::timeout(
+            Duration::from_secs(5),
+            backend.send_streaming(&LlmRequest::new("Hello", "test")),
+        )
+        .await
+        .expect("Backend hung instead of timing out")
+        .unwrap_err();
+
+        assert!(error.to_string().contains("didn't respond"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_times_out_between_tokens() {
+        let port = stalling_server(Some(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/x-ndjson\r\n\
+             Transfer-Encoding: chunked\r\n\r\n\
+             12\r\n{\"response\":\"Hi\"}\n\r\n",
+        ))
+        .await;
+        let backend =
+            OllamaBackend::new("127.0.0.1", port).with_request_timeout(Duration::from_millis(200));
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+
+        match next_token(&mut rx).await {
+            StreamingToken::Token(t) => assert_eq!(t, "Hi"),
+            other => panic!("Expected first token, got {other:?}"),
+        }
+        assert!(matches!(
+            next_token(&mut rx).await,
+            StreamingToken::Error(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reports_http_status_and_ollama_message() {
+        let port = stalling_server(Some(
+            "HTTP/1.1 500 Internal Server Error\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 42\r\n\r\n\
+             {\"error\":\"model 'missing' failed to load\"}",
+        ))
+        .await;
+        // The server only answers once, so don't retry the 500
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(RetryConfig::none());
+
+        let error = backend
+            .send_streaming(&LlmRequest::new("Hello", "missing"))
+            .await
+            .unwrap_err();
+
+        let e = error.to_string();
+        assert!(e.contains("HTTP 500"), "{e}");
+        assert!(e.contains("model 'missing' failed to load"), "{e}");
+        assert!(!e.contains("{\"error\""), "{e}");
+        assert!(error.downcast_ref::<OllamaError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reports_malformed_json() {
+        let port = stalling_server(Some(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/x-ndjson\r\n\
+             Transfer-Encoding: chunked\r\n\r\n\
+             11\r\n{\"response\":oops\n\r\n",
+        ))
+        .await;
+        let backend = OllamaBackend::new("127.0.0.1", port);
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+
+        match next_token(&mut rx).await {
+            StreamingToken::Error(e) => {
+                assert!(e.contains("Couldn't parse"), "{e}");
+                assert!(e.contains("{\"response\":oops"), "{e}");
+            }
+            other => panic!("Expected decode error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reports_connection_failure() {
+        // Grab a free port, then close it so nothing is listening
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(RetryConfig::none());
+
+        let error = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Couldn't connect"), "{error}");
+        assert!(backend.is_transient_error(&error));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_closes_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Send one token, then report when the client hangs up
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (closed_tx, closed_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\n\
+                      Content-Type: application/x-ndjson\r\n\
+                      Transfer-Encoding: chunked\r\n\r\n\
+                      12\r\n{\"response\":\"Hi\"}\n\r\n",
+                )
+                .await
+                .unwrap();
+            while socket.read(&mut buf).await.is_ok_and(|n| n > 0) {}
+            let _ = closed_tx.send(());
+        });
+        let backend = OllamaBackend::new("127.0.0.1", port);
+        let cancel = CancellationToken::new();
+
+        let mut rx = backend
+            .send_streaming_cancellable(&LlmRequest::new("Hello", "test"), cancel.clone())
+            .await
+            .unwrap();
+        assert!(matches!(next_token(&mut rx).await, StreamingToken::Token(t) if t == "Hi"));
+
+        cancel.cancel();
+        let end = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await;
+        assert!(matches!(end, Ok(None)), "stream kept going: {end:?}");
+        tokio::time::timeout(Duration::from_secs(5), closed_rx)
+            .await
+            .expect("connection left open after cancelling")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_stream_line_surfaces_server_errors() {
+        let line = r#"{"error":"out of memory"}"#;
+        match parse_stream_line(line) {
+            Err(e) => assert_eq!(e.to_string(), "Ollama error: out of memory"),
+            Ok(parsed) => panic!("Expected server error, got {parsed:?}"),
+        }
+        assert_eq!(
+            parse_stream_line(r#"{"response":"Hi","done":true}"#).unwrap(),
+            StreamLine {
+                token: Some("Hi".to_string()),
+                done: true,
+                finish_reason: None,
+            }
+        );
+    }
+
+    /// Stream `count` tokens in a single burst and collect what comes out
+    async fn stream_burst(count: usize, batch: Duration) -> (Vec<String>, String) {
+        let mut body: String = (0..count)
+            .map(|i| format!("{{\"response\":\"t{i} \"}}\n"))
+            .collect();
+        body.push_str("{\"response\":\"\",\"done\":true}\n");
+        let (tokens, message, _) = stream_body(&body, batch).await;
+        (tokens, message)
+    }
+
+    /// Serve an NDJSON body and collect the tokens, message and finish reason
+    async fn stream_body(
+        body: &str,
+        batch: Duration,
+    ) -> (Vec<String>, String, Option<FinishReason>) {
+        let response = format!(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/x-ndjson\r\n\
+             Transfer-Encoding: chunked\r\n\r\n\
+             {:x}\r\n{body}\r\n0\r\n\r\n",
+            body.len()
+        );
+        let port = stalling_server(Some(&response)).await;
+        let backend = OllamaBackend::new("127.0.0.1", port).with_stream_batch(batch);
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+        let mut tokens = Vec::new();
+        loop {
+            match next_token(&mut rx).await {
+                StreamingToken::Token(text) => tokens.push(text),
+                StreamingToken::Complete {
+                    message,
+                    finish_reason,
+                } => return (tokens, message, finish_reason),
+                StreamingToken::Error(e) => panic!("Unexpected error: {e}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_batching_combines_tokens() {
+        let (unbatched, _) = stream_burst(50, Duration::ZERO).await;
+        // One per token (plus the empty one on the done line)
+        assert!(unbatched.len() >= 50);
+
+        let (batched, message) = stream_burst(50, Duration::from_millis(50)).await;
+        assert!(batched.len() < unbatched.len(), "{batched:?}");
+        assert_eq!(batched.concat(), unbatched.concat());
+        assert_eq!(message, unbatched.concat());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reports_length_finish_reason() {
+        let body = "{\"response\":\"Once upon\"}\n\
+                    {\"response\":\"\",\"done\":true,\"done_reason\":\"length\"}\n";
+        let (_, message, finish_reason) = stream_body(body, Duration::ZERO).await;
+        assert_eq!(message, "Once upon");
+        assert_eq!(finish_reason, Some(FinishReason::Length));
+
+        let line = parse_stream_line(r#"{"response":"","done":true,"done_reason":"stop"}"#);
+        assert_eq!(line.unwrap().finish_reason, Some(FinishReason::Stop));
+    }
+
+    /// A chunked NDJSON response sending each chunk separately
+    fn chunked_response(chunks: &[&[u8]]) -> Vec<u8> {
+        let mut response = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: application/x-ndjson\r\n\
+            Transfer-Encoding: chunked\r\n\r\n"
+            .to_vec();
+        for chunk in chunks {
+            response.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+            response.extend_from_slice(chunk);
+            response.extend_from_slice(b"\r\n");
+        }
+        response.extend_from_slice(b"0\r\n\r\n");
+        response
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reports_invalid_utf8() {
+        let response =
+            chunked_response(&[b"{\"response\":\"Hi\"}\n", b"{\"response\":\"\xff\xfe\"}\n"]);
+        let port = stalling_server_bytes(Some(response)).await;
+        let backend = OllamaBackend::new("127.0.0.1", port);
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+        assert!(matches!(next_token(&mut rx).await, StreamingToken::Token(t) if t == "Hi"));
+        match next_token(&mut rx).await {
+            StreamingToken::Error(e) => {
+                assert!(e.contains("invalid response encoding"), "{e}");
+                assert!(!e.contains('\u{FFFD}'), "{e}");
+            }
+            other => panic!("Expected an encoding error, got {other:?}"),
+        }
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reassembles_characters_split_across_chunks() {
+        let line = "{\"response\":\"caf\u{e9}\",\"done\":true}\n".as_bytes();
+        let split = line.iter().position(|&b| b == 0xc3).unwrap() + 1;
+        let response = chunked_response(&[&line[..split], &line[split..]]);
+        let port = stalling_server_bytes(Some(response)).await;
+        let backend = OllamaBackend::new("127.0.0.1", port);
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+        let mut tokens = String::new();
+        loop {
+            match next_token(&mut rx).await {
+                StreamingToken::Token(text) => tokens.push_str(&text),
+                StreamingToken::Complete { message, .. } => {
+                    assert_eq!(message, "caf\u{e9}");
+                    break;
+                }
+                StreamingToken::Error(e) => panic!("Unexpected error: {e}"),
+            }
+        }
+        assert_eq!(tokens, "caf\u{e9}");
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_invalid_utf8() {
+        let mut response = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n".to_vec();
+        let body = b"{\"response\":\"\xc3\x28\"}";
+        response.extend_from_slice(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes());
+        response.extend_from_slice(body);
+        let port = stalling_server_bytes(Some(response)).await;
+        let backend = OllamaBackend::new("127.0.0.1", port);
+
+        let err = backend
+            .send(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("invalid response encoding"),
+            "{err}"
+        );
+    }
+
+    /// Answer the first `failures` requests with `failure`, then succeed
+    ///
+    /// Returns the port and a count of requests received.
+    async fn flaky_server(
+        failures: usize,
+        failure: &'static str,
+        success: &'static str,
+    ) -> (u16, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let attempts = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                let response = if attempt < failures { failure } else { success };
+                socket.write_all(response.as_bytes()).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+        (port, attempts)
+    }
+
+    const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\n\
+                               Connection: close\r\n\
+                               Content-Length: 0\r\n\r\n";
+
+    fn fast_retry() -> RetryConfig {
+        RetryConfig {
+            max_retries: 3,
+            initial_delay_ms: 10,
+            max_delay_ms: 50,
+            multiplier: 2.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_transient_failures() {
+        use std::sync::atomic::Ordering;
+
+        let (port, attempts) = flaky_server(
+            2,
+            UNAVAILABLE,
+            "HTTP/1.1 200 OK\r\n\
+             Connection: close\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 29\r\n\r\n\
+             {\"response\":\"Hi\",\"done\":true}",
+        )
+        .await;
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(fast_retry());
+
+        let response = backend
+            .send(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Hi");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_retries_transient_failures() {
+        use std::sync::atomic::Ordering;
+
+        let (port, attempts) = flaky_server(
+            2,
+            UNAVAILABLE,
+            "HTTP/1.1 200 OK\r\n\
+             Connection: close\r\n\
+             Content-Type: application/x-ndjson\r\n\
+             Content-Length: 30\r\n\r\n\
+             {\"response\":\"Hi\",\"done\":true}\n",
+        )
+        .await;
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(fast_retry());
+
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap();
+        assert!(matches!(next_token(&mut rx).await, StreamingToken::Token(t) if t == "Hi"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        use std::sync::atomic::Ordering;
+
+        let (port, attempts) = flaky_server(
+            usize::MAX,
+            "HTTP/1.1 404 Not Found\r\n\
+             Connection: close\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: 34\r\n\r\n\
+             {\"error\":\"model 'nope' not found\"}",
+            "",
+        )
+        .await;
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(fast_retry());
+
+        let err = backend
+            .send(&LlmRequest::new("Hello", "nope"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("HTTP 404"), "{err}");
+        assert!(!backend.is_transient_error(&err));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_report_a_transient_error() {
+        use std::sync::atomic::Ordering;
+
+        let (port, attempts) = flaky_server(usize::MAX, UNAVAILABLE, "").await;
+        let backend = OllamaBackend::new("127.0.0.1", port).with_retry(fast_retry());
+
+        let err = backend
+            .send(&LlmRequest::new("Hello", "test"))
+            .await
+            .unwrap_err();
+        assert!(backend.is_transient_error(&err));
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+    }
 }
@@ -0,0 +1,191 @@
+//! Replay Backend
+//!
+//! A deterministic [`LlmBackend`] that streams back a fixed sequence of
+//! chunks. Feeding it arbitrary bytes drives the Conductor's streaming path
+//! (and its avatar command parsing) with malformed output, for fuzzing and
+//! tests, without a model in the loop.
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+
+use super::traits::{FinishReason, LlmBackend, LlmRequest, LlmResponse, ModelInfo, StreamingToken};
+
+/// Name the replay backend reports as its only model
+const REPLAY_MODEL: &str = "replay";
+
+/// Backend that answers every request with the same recorded chunks
+#[derive(Clone, Debug, Default)]
+pub struct ReplayBackend {
+    chunks: Vec<String>,
+    /// Why the response stopped, as reported with the completion
+    finish_reason: Option<FinishReason>,
+    /// Ends the stream with this error instead of completing
+    error: Option<String>,
+}
+
+impl ReplayBackend {
+    /// Replay these chunks as streaming tokens, in order
+    #[must_use]
+    pub fn new<I, S>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            chunks: chunks.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Report this finish reason with the completion
+    #[must_use]
+    pub fn with_finish_reason(mut self, reason: FinishReason) -> Self {
+        self.finish_reason = Some(reason);
+        self
+    }
+
+    /// End the stream with an error after the chunks instead of completing
+    #[must_use]
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Replay arbitrary bytes, split into chunks the bytes themselves choose
+    ///
+    /// Invalid UTF-8 is decoded lossily, as a real backend would. Each
+    /// chunk is 1-8 characters long, taken from the character it starts
+    /// at, so the same bytes always split the same way and a fuzzer
+    /// controls where commands get cut.
+    #[must_use]
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let text = String::from_utf8_lossy(data);
+        let mut chunks = Vec::new();
+        let mut chars = text.chars().peekable();
+        while let Some(&first) = chars.peek() {
+            let len = (u32::from(first) % 8) as usize + 1;
+            chunks.push(chars.by_ref().take(len).collect());
+        }
+        Self {
+            chunks,
+            ..Self::default()
+        }
+    }
+
+    /// The chunks streamed for each request
+    #[must_use]
+    pub fn chunks(&self) -> &[String] {
+        &self.chunks
+    }
+
+    /// The full response, as sent with the completion
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.chunks.concat()
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ReplayBackend {
+    fn name(&self) -> &'static str {
+        "Replay"
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    async fn send_streaming(
+        &self,
+        _request: &LlmRequest,
+    ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
+        let (tx, rx) = mpsc::channel(self.chunks.len() + 1);
+        for chunk in &self.chunks {
+            let _ = tx.try_send(StreamingToken::Token(chunk.clone()));
+        }
+        let end = match &self.error {
+            Some(error) => StreamingToken::Error(error.clone()),
+            None => StreamingToken::Complete {
+                message: self.message(),
+                finish_reason: self.finish_reason.clone(),
+            },
+        };
+        let _ = tx.try_send(end);
+        Ok(rx)
+    }
+
+    async fn send(&self, _request: &LlmRequest) -> anyhow::Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: self.message(),
+            model: REPLAY_MODEL.to_string(),
+            tokens_used: None,
+            duration_ms: None,
+        })
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<ModelInfo>> {
+        Ok(vec![ModelInfo {
+            name: REPLAY_MODEL.to_string(),
+            description: None,
+            size: None,
+            parameters: None,
+            loaded: true,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes_keeps_all_text() {
+        let data = "[yolla:wave]Hi 🦎 [yolla:react dizzy]there".as_bytes();
+        let backend = ReplayBackend::from_bytes(data);
+        assert!(backend.chunks().len() > 1);
+        assert!(backend.chunks().iter().all(|chunk| !chunk.is_empty()));
+        assert_eq!(backend.message().as_bytes(), data);
+    }
+
+    #[test]
+    fn test_from_bytes_is_deterministic_and_lossy() {
+        let data = [b'[', 0xFF, b'y', b']', 0xC3];
+        let a = ReplayBackend::from_bytes(&data);
+        let b = ReplayBackend::from_bytes(&data);
+        assert_eq!(a.chunks(), b.chunks());
+        assert_eq!(a.message(), "[\u{FFFD}y]\u{FFFD}");
+    }
+
+    #[tokio::test]
+    async fn test_streams_chunks_then_completes() {
+        let backend = ReplayBackend::new(["Hel", "lo"]);
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("hi", REPLAY_MODEL))
+            .await
+            .unwrap();
+
+        assert!(matches!(rx.recv().await, Some(StreamingToken::Token(t)) if t == "Hel"));
+        assert!(matches!(rx.recv().await, Some(StreamingToken::Token(t)) if t == "lo"));
+        assert!(matches!(
+            rx.recv().await,
+            Some(StreamingToken::Complete { message, .. }) if message == "Hello"
+        ));
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_replaces_completion() {
+        let backend = ReplayBackend::new(["Hel"]).with_error("model crashed");
+        let mut rx = backend
+            .send_streaming(&LlmRequest::new("hi", REPLAY_MODEL))
+            .await
+            .unwrap();
+
+        assert!(matches!(rx.recv().await, Some(StreamingToken::Token(t)) if t == "Hel"));
+        assert!(matches!(
+            rx.recv().await,
+            Some(StreamingToken::Error(e)) if e == "model crashed"
+        ));
+        assert!(rx.recv().await.is_none());
+    }
+}
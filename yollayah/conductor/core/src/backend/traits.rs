@@ -13,8 +13,12 @@
 //!
 //! Implementations handle provider-specific details (API formats, auth, etc.)
 
+use std::time::Duration;
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 /// Token stream events from LLM backends
 #[derive(Clone, Debug)]
@@ -25,11 +29,42 @@ pub enum StreamingToken {
     Complete {
         /// The complete message (may differ from concatenated tokens)
         message: String,
+        /// Why generation stopped, if the backend said
+        finish_reason: Option<FinishReason>,
     },
     /// Error occurred during streaming
     Error(String),
 }
 
+/// Why a backend stopped generating a response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FinishReason {
+    /// The model finished on its own
+    Stop,
+    /// The response hit the maximum length and was cut off
+    Length,
+    /// Any other reason the backend reported
+    Other(String),
+}
+
+impl FinishReason {
+    /// Parse a backend's reason string (e.g. Ollama's `done_reason`)
+    #[must_use]
+    pub fn from_backend(reason: &str) -> Self {
+        match reason {
+            "stop" => Self::Stop,
+            "length" => Self::Length,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Whether the response was cut off before the model finished
+    #[must_use]
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, Self::Length)
+    }
+}
+
 /// Configuration for LLM requests
 #[derive(Clone, Debug)]
 pub struct LlmRequest {
@@ -47,6 +82,10 @@ pub struct LlmRequest {
     pub system: Option<String>,
     /// Conversation context (previous messages)
     pub context: Option<String>,
+    /// Base64-encoded images for vision models (empty for text-only)
+    pub images: Vec<String>,
+    /// ID correlating this request with the turn that made it (for logs)
+    pub trace_id: Option<String>,
 }
 
 impl Default for LlmRequest {
@@ -59,6 +98,8 @@ impl Default for LlmRequest {
             temperature: 0.7,
             system: None,
             context: None,
+            images: Vec::new(),
+            trace_id: None,
         }
     }
 }
@@ -99,12 +140,26 @@ impl LlmRequest {
         self
     }
 
+    /// Attach base64-encoded images
+    #[must_use]
+    pub fn with_images(mut self, images: Vec<String>) -> Self {
+        self.images = images;
+        self
+    }
+
     /// Set max tokens
     #[must_use]
     pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
         self.max_tokens = max_tokens;
         self
     }
+
+    /// Set the trace ID
+    #[must_use]
+    pub fn with_trace_id(mut self, trace_id: Option<String>) -> Self {
+        self.trace_id = trace_id;
+        self
+    }
 }
 
 /// Response from non-streaming LLM request
@@ -121,7 +176,7 @@ pub struct LlmResponse {
 }
 
 /// Information about an available model
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModelInfo {
     /// Model identifier
     pub name: String,
@@ -146,6 +201,23 @@ pub trait LlmBackend: Send + Sync {
     /// Check if the backend is healthy and reachable
     async fn health_check(&self) -> bool;
 
+    /// Whether `send_streaming` works
+    ///
+    /// Batch-only backends return false; the Conductor then uses `send`
+    /// and streams the finished response to surfaces itself.
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    /// Whether `error` is a transient failure this backend already retried
+    ///
+    /// The Conductor doesn't retry these again as a batch request, so a
+    /// backend's own retry policy is the only one applied to them.
+    fn is_transient_error(&self, error: &anyhow::Error) -> bool {
+        let _ = error;
+        false
+    }
+
     /// Send a request and get a streaming response
     ///
     /// Returns a channel receiver that will receive tokens as they arrive.
@@ -155,6 +227,21 @@ pub trait LlmBackend: Send + Sync {
         request: &LlmRequest,
     ) -> anyhow::Result<mpsc::Receiver<StreamingToken>>;
 
+    /// Send a streaming request that stops when `cancel` fires
+    ///
+    /// Backends holding a connection should override this to abort the
+    /// request once cancelled, so the server stops generating. The default
+    /// ignores `cancel` and relies on the caller dropping the receiver,
+    /// which stops backends that give up when their channel closes.
+    async fn send_streaming_cancellable(
+        &self,
+        request: &LlmRequest,
+        cancel: CancellationToken,
+    ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
+        let _ = cancel;
+        self.send_streaming(request).await
+    }
+
     /// Send a request and wait for complete response (non-streaming)
     ///
     /// This is useful for quick queries where streaming isn't needed.
@@ -176,6 +263,63 @@ pub trait LlmBackend: Send + Sync {
     }
 }
 
+/// Default time to wait for a backend to connect or produce the next token
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How a backend retries requests that fail for transient reasons
+///
+/// Delays grow exponentially from `initial_delay_ms` up to `max_delay_ms`,
+/// with jitter so clients that failed together don't retry in lockstep.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryConfig {
+    /// Retries after the first attempt (0 = never retry)
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_delay_ms: u64,
+    /// Upper bound on the delay between attempts
+    pub max_delay_ms: u64,
+    /// Factor the delay grows by after each retry
+    pub multiplier: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 250,
+            max_delay_ms: 5_000,
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Never retry
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (0-based)
+    ///
+    /// Somewhere between half and all of the exponential delay, capped at
+    /// `max_delay_ms`.
+    #[must_use]
+    pub fn delay_for_retry(&self, retry: u32) -> Duration {
+        let exponent = i32::try_from(retry).unwrap_or(i32::MAX);
+        let max_delay = Duration::from_millis(self.max_delay_ms);
+        let delay = Duration::from_millis(self.initial_delay_ms).as_secs_f64()
+            * self.multiplier.powi(exponent);
+        let delay = Duration::try_from_secs_f64(delay)
+            .unwrap_or(max_delay)
+            .min(max_delay);
+        delay.mul_f64(0.5 + rand::random::<f64>() * 0.5)
+    }
+}
+
 /// Backend connection configuration
 #[derive(Clone, Debug)]
 pub enum BackendConfig {
@@ -185,6 +329,14 @@ pub enum BackendConfig {
         host: String,
         /// Ollama port number
         port: u16,
+        /// How long to wait for the connection, the first response, and
+        /// each subsequent token before giving up
+        request_timeout: Duration,
+        /// Combine tokens arriving within this many milliseconds into one
+        /// `StreamingToken::Token` (0 = forward each token as it arrives)
+        stream_batch_ms: u64,
+        /// Retry policy for connection failures and 5xx responses
+        retry: RetryConfig,
     },
     /// OpenAI-compatible API
     OpenAI {
@@ -212,6 +364,9 @@ impl Default for BackendConfig {
         Self::Ollama {
             host: "localhost".to_string(),
             port: 11434,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            stream_batch_ms: 0,
+            retry: RetryConfig::default(),
         }
     }
 }
@@ -222,6 +377,9 @@ impl BackendConfig {
         Self::Ollama {
             host: host.into(),
             port,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            stream_batch_ms: 0,
+            retry: RetryConfig::default(),
         }
     }
 
@@ -237,10 +395,54 @@ impl BackendConfig {
             .parse()
             .unwrap_or(11434);
 
-        Self::Ollama { host, port }
+        Self::Ollama {
+            host,
+            port,
+            request_timeout: request_timeout_from_env(),
+            stream_batch_ms: stream_batch_ms_from_env(),
+            retry: RetryConfig::default(),
+        }
+    }
+
+    /// Request timeout for backends that support one
+    #[must_use]
+    pub fn request_timeout(&self) -> Option<Duration> {
+        match self {
+            Self::Ollama {
+                request_timeout, ..
+            } => Some(*request_timeout),
+            _ => None,
+        }
+    }
+
+    /// Token batching interval for backends that support it
+    #[must_use]
+    pub fn stream_batch(&self) -> Option<Duration> {
+        match self {
+            Self::Ollama {
+                stream_batch_ms, ..
+            } => Some(Duration::from_millis(*stream_batch_ms)),
+            _ => None,
+        }
     }
 }
 
+/// Read the request timeout from `YOLLAYAH_OLLAMA_TIMEOUT_SECS`
+pub(crate) fn request_timeout_from_env() -> Duration {
+    std::env::var("YOLLAYAH_OLLAMA_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map_or(DEFAULT_REQUEST_TIMEOUT, Duration::from_secs)
+}
+
+/// Read the token batching interval from `YOLLAYAH_STREAM_BATCH_MS` (default 0)
+pub(crate) fn stream_batch_ms_from_env() -> u64 {
+    std::env::var("YOLLAYAH_STREAM_BATCH_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,11 +467,39 @@ mod tests {
     fn test_backend_config_default() {
         let config = BackendConfig::default();
         match config {
-            BackendConfig::Ollama { host, port } => {
+            BackendConfig::Ollama {
+                host,
+                port,
+                request_timeout,
+                stream_batch_ms,
+                retry,
+            } => {
                 assert_eq!(host, "localhost");
                 assert_eq!(port, 11434);
+                assert_eq!(request_timeout, DEFAULT_REQUEST_TIMEOUT);
+                assert_eq!(stream_batch_ms, 0);
+                assert_eq!(retry, RetryConfig::default());
             }
             _ => panic!("Expected Ollama config"),
         }
     }
+
+    #[test]
+    fn test_retry_delay_grows_with_jitter_and_cap() {
+        let retry = RetryConfig {
+            max_retries: 5,
+            initial_delay_ms: 100,
+            max_delay_ms: 1_000,
+            multiplier: 2.0,
+        };
+
+        for _ in 0..20 {
+            let first = retry.delay_for_retry(0);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let third = retry.delay_for_retry(2);
+            assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+            // 100ms * 2^10 is well past the cap
+            assert!(retry.delay_for_retry(10) <= Duration::from_millis(1_000));
+        }
+    }
 }
@@ -0,0 +1,61 @@
+//! Time Source
+//!
+//! The Conductor reads elapsed time through a [`Clock`] so timing-dependent
+//! behavior (gesture queues, celebration cooldowns, token latency) can be
+//! tested with a [`ManualClock`] instead of real sleeps.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Source of elapsed milliseconds
+pub trait Clock: Send + Sync {
+    /// Milliseconds since some fixed starting point
+    fn now_ms(&self) -> u64;
+}
+
+/// Wall-clock time since the clock was created
+#[derive(Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Start counting from now
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        u64::try_from(self.start.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+}
+
+/// Clock that only moves when told to
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now_ms: AtomicU64,
+}
+
+impl ManualClock {
+    /// Move the clock forward
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
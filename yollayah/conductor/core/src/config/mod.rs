@@ -30,6 +30,7 @@
 //! messages_per_second = 100
 //! burst_size = 50
 //! max_connections_per_uid = 10
+//! max_total_connections = 100
 //! enabled = true
 //!
 //! [routing]
@@ -41,6 +42,11 @@
 //! [security]
 //! max_message_size = 65536
 //! max_input_length = 32768
+//!
+//! [avatar]
+//! cache_max_bytes = 4194304
+//! default_position = "Center"  # or { Percent = { x = 20, y = 80 } }
+//! default_mood = "Calm"
 //! ```
 
 use std::path::PathBuf;
@@ -49,6 +55,8 @@ use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::avatar::security::{validate_cache_size, MAX_CACHE_SIZE_BYTES};
+use crate::avatar::{AvatarMood, AvatarPosition};
 use crate::transport::config::TransportConfig;
 use crate::transport::heartbeat::HeartbeatConfig;
 use crate::transport::rate_limit::RateLimitConfig as TransportRateLimitConfig;
@@ -138,8 +146,11 @@ pub struct TransportToml {
     /// Number of reconnection attempts
     pub reconnect_attempts: Option<u32>,
 
-    /// Delay between reconnection attempts in milliseconds
+    /// Initial delay between reconnection attempts in milliseconds
     pub reconnect_delay_ms: Option<u64>,
+
+    /// Maximum delay between reconnection attempts in milliseconds
+    pub reconnect_max_delay_ms: Option<u64>,
 }
 
 /// Rate limiting section of the TOML configuration
@@ -155,6 +166,9 @@ pub struct RateLimitToml {
     /// Maximum connections allowed per UID
     pub max_connections_per_uid: Option<u32>,
 
+    /// Maximum connections allowed across all UIDs
+    pub max_total_connections: Option<u32>,
+
     /// Whether rate limiting is enabled
     pub enabled: Option<bool>,
 
@@ -202,6 +216,20 @@ pub struct SecurityToml {
     pub session_timeout_secs: Option<u64>,
 }
 
+/// Avatar section of the TOML configuration
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AvatarToml {
+    /// Sprite cache budget in bytes (at most `MAX_CACHE_SIZE_BYTES`)
+    pub cache_max_bytes: Option<usize>,
+
+    /// Where the avatar starts and returns to when reset
+    pub default_position: Option<AvatarPosition>,
+
+    /// How the avatar feels at startup and after a reset
+    pub default_mood: Option<AvatarMood>,
+}
+
 /// Top-level TOML configuration structure
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -217,6 +245,9 @@ pub struct ConductorToml {
 
     /// Security configuration section
     pub security: SecurityToml,
+
+    /// Avatar configuration section
+    pub avatar: AvatarToml,
 }
 
 // =============================================================================
@@ -260,6 +291,15 @@ pub struct ConductorConfigFile {
     /// Session timeout
     pub session_timeout: Duration,
 
+    /// Sprite cache budget in bytes
+    pub avatar_cache_max_bytes: usize,
+
+    /// Avatar starting position
+    pub default_avatar_position: AvatarPosition,
+
+    /// Avatar starting mood
+    pub default_avatar_mood: AvatarMood,
+
     /// Path to the config file that was loaded (if any)
     pub config_file_path: Option<PathBuf>,
 
@@ -280,6 +320,9 @@ impl Default for ConductorConfigFile {
             max_message_size: 65536,
             max_input_length: 32768,
             session_timeout: Duration::from_secs(3600), // 1 hour
+            avatar_cache_max_bytes: MAX_CACHE_SIZE_BYTES,
+            default_avatar_position: AvatarPosition::default(),
+            default_avatar_mood: AvatarMood::default(),
             config_file_path: None,
             source: ConfigSource::Default,
         }
@@ -343,7 +386,8 @@ pub fn load_config() -> Result<ConductorConfigFile, ConfigError> {
 ///
 /// # Errors
 ///
-/// Returns an error if the specified config file cannot be read or parsed.
+/// Returns an error if the specified config file cannot be read or parsed,
+/// or if it sets a value beyond its hard limit.
 pub fn load_config_from_path(path: Option<PathBuf>) -> Result<ConductorConfigFile, ConfigError> {
     // Start with defaults
     let mut config = ConductorConfigFile::default();
@@ -358,7 +402,7 @@ pub fn load_config_from_path(path: Option<PathBuf>) -> Result<ConductorConfigFil
                 })?;
 
             let toml_config: ConductorToml = toml::from_str(&toml_content)?;
-            apply_toml_config(&mut config, &toml_config);
+            apply_toml_config(&mut config, &toml_config)?;
             config.config_file_path = Some(config_path.clone());
             config.source = ConfigSource::File;
 
@@ -381,7 +425,14 @@ pub fn load_config_from_path(path: Option<PathBuf>) -> Result<ConductorConfigFil
 }
 
 /// Apply TOML configuration values to the config struct
-fn apply_toml_config(config: &mut ConductorConfigFile, toml: &ConductorToml) {
+///
+/// # Errors
+///
+/// Returns `ConfigError::ValidationError` if a value exceeds its hard limit.
+fn apply_toml_config(
+    config: &mut ConductorConfigFile,
+    toml: &ConductorToml,
+) -> Result<(), ConfigError> {
     // Transport settings
     if let Some(timeout) = toml.transport.connect_timeout_ms {
         config.transport.connect_timeout_ms = timeout;
@@ -409,6 +460,9 @@ fn apply_toml_config(config: &mut ConductorConfigFile, toml: &ConductorToml) {
     if let Some(delay) = toml.transport.reconnect_delay_ms {
         config.transport.reconnect_delay_ms = delay;
     }
+    if let Some(max_delay) = toml.transport.reconnect_max_delay_ms {
+        config.transport.reconnect_max_delay_ms = max_delay;
+    }
 
     // Rate limit settings
     if let Some(rate) = toml.rate_limit.messages_per_second {
@@ -420,6 +474,9 @@ fn apply_toml_config(config: &mut ConductorConfigFile, toml: &ConductorToml) {
     if let Some(max_conn) = toml.rate_limit.max_connections_per_uid {
         config.rate_limit.max_connections_per_uid = max_conn;
     }
+    if let Some(max_total) = toml.rate_limit.max_total_connections {
+        config.rate_limit.max_total_connections = max_total;
+    }
     if let Some(enabled) = toml.rate_limit.enabled {
         config.rate_limit.enabled = enabled;
     }
@@ -454,6 +511,26 @@ fn apply_toml_config(config: &mut ConductorConfigFile, toml: &ConductorToml) {
     if let Some(timeout) = toml.security.session_timeout_secs {
         config.session_timeout = Duration::from_secs(timeout);
     }
+
+    // Avatar settings
+    if let Some(bytes) = toml.avatar.cache_max_bytes {
+        validate_cache_size(bytes)
+            .map_err(|e| ConfigError::ValidationError(format!("avatar.cache_max_bytes: {e}")))?;
+        config.avatar_cache_max_bytes = bytes;
+    }
+    if let Some(position) = toml.avatar.default_position {
+        if !position.is_in_range() {
+            return Err(ConfigError::ValidationError(
+                "avatar.default_position: Percent coordinates must be 0-100".to_string(),
+            ));
+        }
+        config.default_avatar_position = position;
+    }
+    if let Some(mood) = toml.avatar.default_mood {
+        config.default_avatar_mood = mood;
+    }
+
+    Ok(())
 }
 
 /// Apply environment variable overrides to the config
@@ -510,6 +587,12 @@ fn apply_env_config(config: &mut ConductorConfigFile) {
             config.source = ConfigSource::Env;
         }
     }
+    if let Ok(max_total) = std::env::var("CONDUCTOR_MAX_TOTAL_CONNECTIONS") {
+        if let Ok(n) = max_total.parse::<u32>() {
+            config.rate_limit.max_total_connections = n;
+            config.source = ConfigSource::Env;
+        }
+    }
 
     // Routing settings from environment
     if let Ok(model) = std::env::var("CONDUCTOR_DEFAULT_MODEL") {
@@ -661,6 +744,8 @@ impl ConfigOverrides {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::avatar::block::Block;
+    use crate::avatar::cache::{SpriteCache, SpriteData};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -675,6 +760,7 @@ mod tests {
         std::env::remove_var("CONDUCTOR_RATE_LIMIT_MPS");
         std::env::remove_var("CONDUCTOR_RATE_LIMIT_BURST");
         std::env::remove_var("CONDUCTOR_MAX_CONNECTIONS_PER_UID");
+        std::env::remove_var("CONDUCTOR_MAX_TOTAL_CONNECTIONS");
         std::env::remove_var("CONDUCTOR_DEFAULT_MODEL");
         std::env::remove_var("CONDUCTOR_MAX_CONCURRENT");
         std::env::remove_var("CONDUCTOR_MAX_MESSAGE_SIZE");
@@ -696,6 +782,7 @@ mod tests {
         assert_eq!(config.max_message_size, 65536);
         assert_eq!(config.max_input_length, 32768);
         assert_eq!(config.session_timeout, Duration::from_secs(3600));
+        assert_eq!(config.avatar_cache_max_bytes, MAX_CACHE_SIZE_BYTES);
         assert_eq!(config.source(), ConfigSource::Default);
     }
 
@@ -726,6 +813,7 @@ max_missed_pongs = 5
 messages_per_second = 200
 burst_size = 100
 max_connections_per_uid = 20
+max_total_connections = 40
 
 [routing]
 default_model = "custom-model"
@@ -752,6 +840,7 @@ max_input_length = 65536
         assert_eq!(config.rate_limit.messages_per_second, 200);
         assert_eq!(config.rate_limit.burst_size, 100);
         assert_eq!(config.rate_limit.max_connections_per_uid, 20);
+        assert_eq!(config.rate_limit.max_total_connections, 40);
 
         // Routing
         assert_eq!(config.default_model, Some("custom-model".to_string()));
@@ -868,6 +957,71 @@ connect_timeout_ms = "not a number"
         assert!(matches!(result.unwrap_err(), ConfigError::ParseError(_)));
     }
 
+    // =========================================================================
+    // Avatar Cache Tests
+    // =========================================================================
+
+    /// Fill a cache with `count` 10x10 sprites, returning the keys still cached
+    fn cached_after_inserting(cache: &mut SpriteCache, count: usize) -> Vec<String> {
+        for i in 0..count {
+            let sprite = SpriteData::new(vec![Block::empty(); 100], 10, 10).unwrap();
+            cache.insert(format!("s1:{i}"), sprite, false).unwrap();
+        }
+        (0..count)
+            .map(|i| format!("s1:{i}"))
+            .filter(|key| cache.peek(key).is_some())
+            .collect()
+    }
+
+    #[test]
+    fn test_avatar_cache_size_evicts_at_configured_boundary() {
+        let sprite_size = SpriteData::new(vec![Block::empty(); 100], 10, 10)
+            .unwrap()
+            .size_bytes();
+        let toml_content = format!("[avatar]\ncache_max_bytes = {}\n", sprite_size * 3);
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let config = load_config_from_path(Some(file.path().to_path_buf())).unwrap();
+        assert_eq!(config.avatar_cache_max_bytes, sprite_size * 3);
+
+        // The fourth sprite pushes out the first at the configured size...
+        let mut cache = SpriteCache::new(config.avatar_cache_max_bytes);
+        assert_eq!(cached_after_inserting(&mut cache, 3).len(), 3);
+        let mut cache = SpriteCache::new(config.avatar_cache_max_bytes);
+        assert_eq!(
+            cached_after_inserting(&mut cache, 4),
+            ["s1:1", "s1:2", "s1:3"]
+        );
+
+        // ...but fits under the default
+        let mut cache = SpriteCache::new(ConductorConfigFile::default().avatar_cache_max_bytes);
+        assert_eq!(cached_after_inserting(&mut cache, 4).len(), 4);
+    }
+
+    #[test]
+    fn test_avatar_cache_size_over_ceiling_rejected() {
+        let toml_content = format!("[avatar]\ncache_max_bytes = {}\n", MAX_CACHE_SIZE_BYTES + 1);
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let result = load_config_from_path(Some(file.path().to_path_buf()));
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_avatar_default_position_out_of_range_rejected() {
+        let toml_content = "[avatar]\ndefault_position = { Percent = { x = 50, y = 101 } }\n";
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let result = load_config_from_path(Some(file.path().to_path_buf()));
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
     // =========================================================================
     // Priority Ordering Tests
     // =========================================================================
@@ -1032,6 +1186,10 @@ connect_timeout_ms = 5000
                 ..Default::default()
             },
             security: SecurityToml::default(),
+            avatar: AvatarToml {
+                cache_max_bytes: Some(4096),
+                ..Default::default()
+            },
         };
 
         let toml_string = toml::to_string(&original).unwrap();
@@ -1046,6 +1204,7 @@ connect_timeout_ms = 5000
         assert_eq!(parsed.rate_limit.messages_per_second, Some(150));
         assert_eq!(parsed.rate_limit.burst_size, Some(75));
         assert_eq!(parsed.routing.default_model, Some("test-model".to_string()));
+        assert_eq!(parsed.avatar.cache_max_bytes, Some(4096));
     }
 
     // =========================================================================
@@ -141,6 +141,8 @@ pub struct Conversation {
     pub token_count: u32,
     /// Response metadata from last completion
     pub last_metadata: Option<ResponseMetadata>,
+    /// Assistant responses completed while this conversation wasn't focused
+    pub unread: u32,
 }
 
 impl Conversation {
@@ -159,6 +161,7 @@ impl Conversation {
             stream_buffer: String::new(),
             token_count: 0,
             last_metadata: None,
+            unread: 0,
         }
     }
 
@@ -177,6 +180,7 @@ impl Conversation {
             stream_buffer: String::new(),
             token_count: 0,
             last_metadata: None,
+            unread: 0,
         }
     }
 
@@ -195,6 +199,7 @@ impl Conversation {
             stream_buffer: String::new(),
             token_count: 0,
             last_metadata: None,
+            unread: 0,
         }
     }
 
@@ -272,6 +277,21 @@ impl Conversation {
     }
 }
 
+/// Entry in a conversation list shown by surfaces
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConversationListEntry {
+    /// Conversation identifier
+    pub conversation_id: ConversationId,
+    /// Display title
+    pub title: String,
+    /// Current state
+    pub state: ConversationState,
+    /// Unread assistant responses
+    pub unread: u32,
+    /// Whether this is the focused conversation
+    pub focused: bool,
+}
+
 // ============================================================================
 // Conversation Manager
 // ============================================================================
@@ -346,11 +366,57 @@ impl ConversationManager {
     /// Focus on a specific conversation
     pub fn focus(&mut self, id: ConversationId) {
         if self.conversations.contains_key(&id) {
-            self.focused = Some(id);
+            self.set_focused(id);
             self.pop_to_top(id);
         }
     }
 
+    /// Move focus without touching the z-order, clearing unread responses
+    fn set_focused(&mut self, id: ConversationId) {
+        self.focused = Some(id);
+        self.mark_read(id);
+    }
+
+    /// Complete a streamed response in a conversation
+    ///
+    /// Counts as unread unless the conversation is focused.
+    pub fn complete_streaming(
+        &mut self,
+        id: ConversationId,
+        final_content: String,
+        metadata: ResponseMetadata,
+    ) {
+        let focused = self.focused == Some(id);
+        if let Some(conversation) = self.conversations.get_mut(&id) {
+            conversation.complete_streaming(final_content, metadata);
+            if !focused {
+                conversation.unread += 1;
+            }
+        }
+    }
+
+    /// Clear a conversation's unread responses
+    pub fn mark_read(&mut self, id: ConversationId) {
+        if let Some(conversation) = self.conversations.get_mut(&id) {
+            conversation.unread = 0;
+        }
+    }
+
+    /// Conversation list entries in z-order (bottom to top)
+    #[must_use]
+    pub fn list(&self) -> Vec<ConversationListEntry> {
+        self.in_z_order()
+            .into_iter()
+            .map(|c| ConversationListEntry {
+                conversation_id: c.id,
+                title: c.title(),
+                state: c.state.clone(),
+                unread: c.unread,
+                focused: self.focused == Some(c.id),
+            })
+            .collect()
+    }
+
     /// User explicitly focused (sticky)
     pub fn user_focus(&mut self, id: ConversationId) {
         self.focus(id);
@@ -540,7 +606,7 @@ impl ConversationManager {
         if let Some(current) = self.focused {
             if let Some(pos) = self.z_order.iter().position(|&x| x == current) {
                 let next_pos = (pos + 1) % self.z_order.len();
-                self.focused = Some(self.z_order[next_pos]);
+                self.set_focused(self.z_order[next_pos]);
             }
         }
     }
@@ -558,7 +624,7 @@ impl ConversationManager {
                 } else {
                     pos - 1
                 };
-                self.focused = Some(self.z_order[prev_pos]);
+                self.set_focused(self.z_order[prev_pos]);
             }
         }
     }
@@ -741,4 +807,56 @@ mod tests {
         assert_eq!(manager.total_count(), 1);
         assert_eq!(manager.focused_id(), Some(id2));
     }
+
+    #[test]
+    fn test_unread_counts_responses_in_unfocused_conversations() {
+        let mut manager = ConversationManager::with_main();
+        let agent = manager.create(Some("Architect".to_string())).unwrap();
+        assert_eq!(manager.focused_id(), Some(ConversationId::main()));
+
+        manager
+            .get_mut(agent)
+            .unwrap()
+            .start_streaming(MessageId::new());
+        manager.complete_streaming(agent, "Done".to_string(), ResponseMetadata::default());
+        manager.complete_streaming(
+            ConversationId::main(),
+            "Hi".to_string(),
+            ResponseMetadata::default(),
+        );
+
+        let unread = |manager: &ConversationManager, id| {
+            manager
+                .list()
+                .into_iter()
+                .find(|entry| entry.conversation_id == id)
+                .map(|entry| entry.unread)
+        };
+        assert_eq!(unread(&manager, agent), Some(1));
+        assert_eq!(unread(&manager, ConversationId::main()), Some(0));
+
+        manager.user_focus(agent);
+        assert_eq!(unread(&manager, agent), Some(0));
+        assert!(manager
+            .list()
+            .iter()
+            .any(|e| e.focused && e.conversation_id == agent));
+    }
+
+    #[test]
+    fn test_mark_read_and_cycling_focus_reset_unread() {
+        let mut manager = ConversationManager::with_main();
+        let agent = manager.create(None).unwrap();
+
+        manager.complete_streaming(agent, String::new(), ResponseMetadata::default());
+        manager.complete_streaming(agent, String::new(), ResponseMetadata::default());
+        assert_eq!(manager.get(agent).unwrap().unread, 2);
+        manager.mark_read(agent);
+        assert_eq!(manager.get(agent).unwrap().unread, 0);
+
+        manager.complete_streaming(agent, String::new(), ResponseMetadata::default());
+        manager.focus_next();
+        assert_eq!(manager.focused_id(), Some(agent));
+        assert_eq!(manager.get(agent).unwrap().unread, 0);
+    }
 }
@@ -11,9 +11,12 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::animation::AnimationPriority;
+use crate::avatar::OneShotAnimation;
 use crate::conversation::ConversationId;
-use crate::messages::{EventId, MessageId};
-use crate::tasks::TaskId;
+use crate::messages::{EventId, MessageId, NotifyLevel, SessionId};
+use crate::session::MergeStrategy;
+use crate::tasks::{TaskFilter, TaskId, TaskSort};
 
 /// Events from UI Surface to Conductor
 ///
@@ -49,6 +52,11 @@ pub enum SurfaceEvent {
         capabilities: SurfaceCapabilities,
         /// Optional authentication token (for remote surfaces)
         auth_token: Option<String>,
+        /// Stable client identifier that survives reconnects (optional)
+        ///
+        /// Lets the Conductor recognize a surface coming back after a drop.
+        #[serde(default)]
+        client_id: Option<String>,
     },
 
     /// Heartbeat response (reply to Ping)
@@ -86,6 +94,19 @@ pub enum SurfaceEvent {
         content: String,
     },
 
+    /// User submitted a message with images attached
+    ///
+    /// Only for surfaces whose negotiated capabilities include `images`.
+    /// The images go to the model with the message, for vision models.
+    UserMessageWithImages {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// The message content
+        content: String,
+        /// Base64-encoded images
+        images: Vec<String>,
+    },
+
     /// User executed a command (e.g., /help, /quit)
     UserCommand {
         /// Event ID for acknowledgment
@@ -96,6 +117,125 @@ pub enum SurfaceEvent {
         args: Vec<String>,
     },
 
+    /// User changed the system prompt (persona) for subsequent turns
+    SetSystemPrompt {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// New system prompt (empty clears it)
+        prompt: String,
+    },
+
+    /// User changed the system prompt (persona) of one conversation
+    SetConversationSystemPrompt {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Conversation (session) to change
+        session_id: SessionId,
+        /// New system prompt (empty falls back to the global one)
+        prompt: String,
+    },
+
+    /// User picked a model for one conversation
+    SwitchModel {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Conversation (session) to change
+        session_id: SessionId,
+        /// Model to use (empty falls back to the global one)
+        model: String,
+    },
+
+    /// User merged a branched conversation back into the current one
+    MergeConversations {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Branched session to merge from
+        source: SessionId,
+        /// Which messages to bring over
+        strategy: MergeStrategy,
+    },
+
+    /// Surface lost track of the conversation (e.g. a gap in token
+    /// sequence numbers) and wants a fresh state snapshot
+    RequestSnapshot {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
+    /// Surface's display got corrupted and wants the last assistant
+    /// response sent again in full
+    ResendLastResponse {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
+    /// Surface wants an aggregated health report
+    RequestHealth {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
+    /// Surface wants details about the current model
+    RequestModelInfo {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
+    /// User wants the latest turn answered again by a different model
+    ///
+    /// The response `message_id` belongs to (or the error a failed one
+    /// left) is dropped and the user's message is re-run on `model`.
+    RetryWithModel {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// A message of the turn to retry (its user message or response)
+        message_id: MessageId,
+        /// Model to answer with this time
+        model: String,
+    },
+
+    /// User sent a message meant to replace the response in progress
+    ///
+    /// The streaming response is stopped (what arrived so far is kept,
+    /// marked cancelled) and `content` starts a new turn.
+    InterruptAndSend {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// The new message
+        content: String,
+    },
+
+    /// User changed one of their earlier messages
+    ///
+    /// Everything after it is dropped and the edited message is answered
+    /// again.
+    EditMessage {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// The user message being edited
+        message_id: MessageId,
+        /// Its new text
+        content: String,
+    },
+
+    /// User wants their last question and its answer taken back
+    ///
+    /// Undoing again removes the exchange before that, up to the
+    /// Conductor's undo depth.
+    Undo {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
+    /// User wants a stalled response looked at ("are you there?")
+    ///
+    /// A response that's been silent too long is cancelled and the turn
+    /// tried again, once per turn.
+    Nudge {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
     /// User is typing (for real-time feedback)
     UserTyping {
         /// Whether user is currently typing
@@ -110,6 +250,15 @@ pub enum SurfaceEvent {
         amount: u32,
     },
 
+    /// Conversation view moved to or away from the latest output
+    ///
+    /// While the user is scrolled away, a finished response gets a gentle
+    /// attention bounce from the avatar.
+    ScrollPositionChanged {
+        /// Whether the view is following the latest output
+        at_bottom: bool,
+    },
+
     // ============================================
     // Interaction Events
     // ============================================
@@ -119,6 +268,15 @@ pub enum SurfaceEvent {
         event_id: EventId,
     },
 
+    /// User wants the avatar back to its defaults
+    ///
+    /// Restores position, mood, size and visibility, and drops any
+    /// gestures and reactions, after a busy run of animations.
+    ResetAvatar {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+    },
+
     /// User clicked/tapped a task
     TaskClicked {
         /// Event ID for acknowledgment
@@ -135,6 +293,49 @@ pub enum SurfaceEvent {
         message_id: MessageId,
     },
 
+    /// User changed how the task panel is sorted/filtered
+    SetTaskView {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Task ordering
+        sort: TaskSort,
+        /// Which tasks to show
+        filter: TaskFilter,
+    },
+
+    /// User wants fewer avatar animations (e.g. focus mode)
+    ///
+    /// Avatar directives below `priority` are suppressed. Critical
+    /// animations (errors) always play.
+    SetAnimationCeiling {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Lowest priority that still plays
+        priority: AnimationPriority,
+    },
+
+    /// User wants fewer notifications on this surface
+    ///
+    /// Notifications below `min_level` aren't sent to the surface that
+    /// asked; other surfaces keep their own setting.
+    SetVerbosity {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Least severe level still shown
+        min_level: NotifyLevel,
+    },
+
+    /// Surface window gained or lost focus (desktop surfaces)
+    ///
+    /// While unfocused, only high-priority and critical avatar animations
+    /// play; the rest resume when focus returns.
+    WindowFocusChanged {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Whether the window now has focus
+        focused: bool,
+    },
+
     // ============================================
     // Acknowledgment Events
     // ============================================
@@ -150,6 +351,12 @@ pub enum SurfaceEvent {
         frame: u64,
     },
 
+    /// Surface finished playing a gesture or reaction
+    AnimationComplete {
+        /// The animation that finished
+        animation: OneShotAnimation,
+    },
+
     // ============================================
     // Capability Response
     // ============================================
@@ -226,6 +433,14 @@ pub enum SurfaceEvent {
         /// Event ID for acknowledgment
         event_id: EventId,
     },
+
+    /// User has seen a conversation's responses without focusing it
+    MarkRead {
+        /// Event ID for acknowledgment
+        event_id: EventId,
+        /// Conversation to mark as read
+        conversation_id: ConversationId,
+    },
 }
 
 impl SurfaceEvent {
@@ -246,10 +461,29 @@ impl SurfaceEvent {
             | Self::Disconnected { event_id, .. }
             | Self::Resized { event_id, .. }
             | Self::UserMessage { event_id, .. }
+            | Self::UserMessageWithImages { event_id, .. }
             | Self::UserCommand { event_id, .. }
+            | Self::SetSystemPrompt { event_id, .. }
+            | Self::SetConversationSystemPrompt { event_id, .. }
+            | Self::SwitchModel { event_id, .. }
+            | Self::MergeConversations { event_id, .. }
+            | Self::RequestSnapshot { event_id }
+            | Self::ResendLastResponse { event_id }
+            | Self::RequestHealth { event_id }
+            | Self::RequestModelInfo { event_id }
+            | Self::RetryWithModel { event_id, .. }
+            | Self::EditMessage { event_id, .. }
+            | Self::Undo { event_id }
+            | Self::Nudge { event_id }
+            | Self::InterruptAndSend { event_id, .. }
             | Self::AvatarClicked { event_id }
+            | Self::ResetAvatar { event_id }
             | Self::TaskClicked { event_id, .. }
             | Self::MessageClicked { event_id, .. }
+            | Self::SetTaskView { event_id, .. }
+            | Self::SetAnimationCeiling { event_id, .. }
+            | Self::SetVerbosity { event_id, .. }
+            | Self::WindowFocusChanged { event_id, .. }
             | Self::CapabilitiesReport { event_id, .. }
             | Self::QuitRequested { event_id }
             | Self::SurfaceError { event_id, .. }
@@ -258,12 +492,15 @@ impl SurfaceEvent {
             | Self::RequestSummary { event_id }
             | Self::ExitSummary { event_id }
             | Self::FocusNextConversation { event_id }
-            | Self::FocusPrevConversation { event_id } => Some(event_id),
+            | Self::FocusPrevConversation { event_id }
+            | Self::MarkRead { event_id, .. } => Some(event_id),
             Self::Pong { .. }
             | Self::UserTyping { .. }
             | Self::UserScrolled { .. }
+            | Self::ScrollPositionChanged { .. }
             | Self::MessageReceived { .. }
-            | Self::RenderComplete { .. } => None,
+            | Self::RenderComplete { .. }
+            | Self::AnimationComplete { .. } => None,
         }
     }
 }
@@ -329,6 +566,13 @@ pub struct SurfaceCapabilities {
     pub max_width: u32,
     /// Maximum text height (0 = unlimited)
     pub max_height: u32,
+    /// Wants CRC32 checksums on transport frames (negotiated in the handshake)
+    #[serde(default)]
+    pub frame_checksums: bool,
+    /// Wants the avatar as concrete sprite frames instead of semantic
+    /// mood directives (for surfaces that can't draw sprites)
+    #[serde(default)]
+    pub sprite_push: bool,
 }
 
 impl SurfaceCapabilities {
@@ -349,6 +593,8 @@ impl SurfaceCapabilities {
             clipboard: false, // Depends on terminal
             max_width: 0,
             max_height: 0,
+            frame_checksums: false, // Local transports don't need integrity checks
+            sprite_push: false,
         }
     }
 
@@ -369,6 +615,8 @@ impl SurfaceCapabilities {
             clipboard: true,
             max_width: 0,
             max_height: 0,
+            frame_checksums: true,
+            sprite_push: false,
         }
     }
 
@@ -389,6 +637,66 @@ impl SurfaceCapabilities {
             clipboard: false,
             max_width: 80,
             max_height: 24,
+            frame_checksums: false,
+            sprite_push: false,
+        }
+    }
+
+    /// Everything the Conductor can currently deliver to a surface
+    ///
+    /// Images are accepted with user messages, for vision models. Audio
+    /// has no Conductor messages yet, so it's never offered. Size limits
+    /// are left to the surface (0 = unlimited).
+    #[must_use]
+    pub fn conductor_supported() -> Self {
+        Self {
+            color: true,
+            avatar: true,
+            avatar_animations: true,
+            tasks: true,
+            streaming: true,
+            images: true,
+            audio: false,
+            rich_text: true,
+            pointer_input: true,
+            keyboard_input: true,
+            clipboard: true,
+            max_width: 0,
+            max_height: 0,
+            frame_checksums: true,
+            sprite_push: true,
+        }
+    }
+
+    /// Intersect these capabilities with what the other side supports
+    ///
+    /// A feature is kept only if both sides have it. Size limits take the
+    /// tighter of the two, where 0 means unlimited.
+    #[must_use]
+    pub fn negotiate(&self, supported: &Self) -> Self {
+        fn tighter(a: u32, b: u32) -> u32 {
+            match (a, b) {
+                (0, limit) | (limit, 0) => limit,
+                (a, b) => a.min(b),
+            }
+        }
+
+        Self {
+            color: self.color && supported.color,
+            avatar: self.avatar && supported.avatar,
+            avatar_animations: self.avatar_animations && supported.avatar_animations,
+            tasks: self.tasks && supported.tasks,
+            streaming: self.streaming && supported.streaming,
+            images: self.images && supported.images,
+            audio: self.audio && supported.audio,
+            rich_text: self.rich_text && supported.rich_text,
+            pointer_input: self.pointer_input && supported.pointer_input,
+            keyboard_input: self.keyboard_input && supported.keyboard_input,
+            clipboard: self.clipboard && supported.clipboard,
+            max_width: tighter(self.max_width, supported.max_width),
+            max_height: tighter(self.max_height, supported.max_height),
+            frame_checksums: self.frame_checksums && supported.frame_checksums,
+            sprite_push: self.sprite_push && supported.sprite_push,
         }
     }
 }
@@ -424,6 +732,32 @@ mod tests {
         assert!(caps.avatar);
         assert!(caps.streaming);
         assert!(!caps.images);
+        assert!(!caps.frame_checksums);
+    }
+
+    #[test]
+    fn test_negotiate_drops_unsupported_features() {
+        let mut supported = SurfaceCapabilities::conductor_supported();
+        supported.max_width = 120;
+
+        let negotiated = SurfaceCapabilities::web().negotiate(&supported);
+        assert!(negotiated.avatar);
+        assert!(negotiated.rich_text);
+        assert!(!negotiated.audio);
+        assert!(negotiated.images);
+        assert!(!negotiated.sprite_push);
+        assert_eq!(negotiated.max_width, 120);
+
+        let canvas = SurfaceCapabilities {
+            sprite_push: true,
+            ..SurfaceCapabilities::web()
+        };
+        assert!(canvas.negotiate(&supported).sprite_push);
+
+        let negotiated = SurfaceCapabilities::headless().negotiate(&supported);
+        assert!(!negotiated.avatar);
+        assert_eq!(negotiated.max_width, 80);
+        assert_eq!(negotiated.max_height, 24);
     }
 
     #[test]
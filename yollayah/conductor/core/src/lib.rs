@@ -92,9 +92,13 @@
 //! - [`animation`]: Surface-agnostic animation abstractions (timing, easing, layers)
 //! - [`avatar`]: Avatar state, moods, gestures, and command parsing
 //! - [`backend`]: LLM backend abstraction (Ollama, etc.)
+//! - [`clock`]: Injectable time source for timing-dependent behavior
 //! - [`events`]: Events from UI surfaces to Conductor
+//! - [`locale`]: Languages Yollayah can greet in
 //! - [`messages`]: Messages from Conductor to UI surfaces
+//! - [`redaction`]: Masking sensitive text in sessions saved to disk
 //! - [`session`]: Conversation session management
+//! - [`session_store`]: Pluggable session persistence (file-based by default)
 //! - [`tasks`]: Background task management
 //! - [`conductor`]: Main Conductor struct
 //! - [`transport`]: IPC transport layer (Unix sockets, WebSocket)
@@ -116,14 +120,18 @@ pub mod accessibility;
 pub mod animation;
 pub mod avatar;
 pub mod backend;
+pub mod clock;
 pub mod conductor;
 pub mod config;
 pub mod conversation;
 pub mod events;
+pub mod locale;
 pub mod messages;
+pub mod redaction;
 pub mod routing;
 pub mod security;
 pub mod session;
+pub mod session_store;
 pub mod streaming;
 pub mod surface_registry;
 pub mod tasks;
@@ -131,27 +139,32 @@ pub mod transport;
 
 // Re-exports for convenience
 pub use avatar::{
-    AvatarCommand, AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction, AvatarSize,
-    AvatarState, CommandParser, PeekDirection, TaskCommand,
+    AvatarActivity, AvatarCommand, AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction,
+    AvatarSize, AvatarState, CommandParser, CommandSpec, MoodAnimations, MotionInterpolator,
+    OneShotAnimation, PeekDirection, TaskCommand,
 };
 // Block-based rendering primitives (P1.1 Avatar Animation System)
 pub use avatar::block::{AnchorPoint, Block, Color, RelativeSize, SizeHint};
 pub use backend::{
-    BackendConfig, LlmBackend, LlmRequest, LlmResponse, OllamaBackend, StreamingToken,
+    BackendConfig, FinishReason, LlmBackend, LlmRequest, LlmResponse, OllamaBackend, StreamingToken,
 };
-pub use conductor::{Conductor, ConductorConfig};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use conductor::{Conductor, ConductorConfig, PollOutcome};
 pub use events::{ScrollDirection, SurfaceCapabilities, SurfaceEvent, SurfaceType};
+pub use locale::GreetingLocale;
 pub use messages::{
-    AvatarStateSnapshot, ConductorMessage, ConductorState, ContentType, EventId, LayoutDirective,
-    MessageId, MessageRole, NotifyLevel, PanelId, ResponseMetadata, SessionId, SessionSnapshot,
-    SnapshotMessage,
+    AvatarStateSnapshot, ConductorError, ConductorMessage, ConductorState, ContentType, EventId,
+    ExportFormat, HealthReport, LayoutDirective, MessageId, MessageRole, NotifyLevel, PanelId,
+    ResponseMetadata, SessionId, SessionSnapshot, SnapshotMessage, TokenLatency,
 };
+pub use redaction::{RedactionRule, Redactor};
 pub use security::{
-    CommandRejectionReason, CommandValidator, ConductorLimits, InputValidator, SecurityConfig,
-    ValidationResult,
+    CommandRejectionReason, CommandValidator, ConductorLimits, EmptyMessageBehavior,
+    InputValidator, SecurityConfig, ValidationResult,
 };
-pub use session::{ConversationMessage, Session, SessionMetadata, SessionState};
-pub use tasks::{Task, TaskCreationError, TaskId, TaskManager, TaskStatus};
+pub use session::{ConversationMessage, MergeStrategy, Session, SessionMetadata, SessionState};
+pub use session_store::{FileSessionStore, SessionStore, SessionStoreError};
+pub use tasks::{Task, TaskCreationError, TaskFilter, TaskId, TaskManager, TaskSort, TaskStatus};
 
 // Accessibility exports
 pub use accessibility::{Accessible, Urgency};
@@ -163,19 +176,21 @@ pub use animation::{
 };
 
 // Conversation exports
-pub use conversation::{Conversation, ConversationId, ConversationManager, ConversationState};
+pub use conversation::{
+    Conversation, ConversationId, ConversationListEntry, ConversationManager, ConversationState,
+};
 
 // Routing exports
 pub use routing::policy::RoutingRequest;
 pub use routing::{
-    MetricsConfig, ModelProfile, QueryRouter, RetryConfig, RouterConfig, RouterError,
-    RouterResponse, TaskClass,
+    MetricsConfig, ModelParameters, ModelProfile, QueryRouter, RetryConfig, RouterConfig,
+    RouterError, RouterResponse, TaskClass,
 };
 
 // Streaming exports
 pub use streaming::{
-    BufferOverflowPolicy, ConversationStream, StreamEvent, StreamEventKind, StreamManager,
-    StreamManagerConfig, StreamStats,
+    BufferOverflowPolicy, ConversationStream, ReasoningSplitter, StreamEvent, StreamEventKind,
+    StreamManager, StreamManagerConfig, StreamStats, TokenPacer,
 };
 
 // Surface registry exports
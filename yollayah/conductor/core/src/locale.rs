@@ -0,0 +1,108 @@
+//! Greeting Locales
+//!
+//! Languages Yollayah can greet in. A [`GreetingLocale`] says what to ask
+//! the model for and what to say instead when the model can't be reached.
+//! Without a configured locale, Yollayah keeps her default English with a
+//! dash of Spanish.
+
+/// A language Yollayah can greet in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GreetingLocale {
+    /// Language subtag, e.g. "es" (also matches "es-MX" and `es_MX`)
+    pub tag: &'static str,
+    /// Language name, as told to the model
+    pub language: &'static str,
+    /// Static greeting for when the model can't be reached
+    pub fallback_greeting: &'static str,
+}
+
+/// Every supported greeting locale
+pub const GREETING_LOCALES: &[GreetingLocale] = &[
+    GreetingLocale {
+        tag: "en",
+        language: "English",
+        fallback_greeting: "Hi there! Ready to chat!",
+    },
+    GreetingLocale {
+        tag: "es",
+        language: "Spanish",
+        fallback_greeting: "¡Hola! ¿Listos para platicar?",
+    },
+    GreetingLocale {
+        tag: "fr",
+        language: "French",
+        fallback_greeting: "Salut ! Prêt à discuter ?",
+    },
+    GreetingLocale {
+        tag: "de",
+        language: "German",
+        fallback_greeting: "Hallo! Bereit zum Plaudern?",
+    },
+    GreetingLocale {
+        tag: "pt",
+        language: "Portuguese",
+        fallback_greeting: "Olá! Vamos conversar?",
+    },
+    GreetingLocale {
+        tag: "it",
+        language: "Italian",
+        fallback_greeting: "Ciao! Pronti a chiacchierare?",
+    },
+    GreetingLocale {
+        tag: "ja",
+        language: "Japanese",
+        fallback_greeting: "こんにちは！おしゃべりしましょう！",
+    },
+];
+
+/// Static greeting when no locale is configured
+pub const DEFAULT_FALLBACK_GREETING: &str = "¡Hola! Ready to chat!";
+
+impl GreetingLocale {
+    /// Find the locale for a tag such as "es", "es-MX" or `pt_BR`
+    ///
+    /// Only the language subtag is matched, ignoring case. Returns None
+    /// for languages not in [`GREETING_LOCALES`].
+    #[must_use]
+    pub fn lookup(tag: &str) -> Option<&'static Self> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        GREETING_LOCALES
+            .iter()
+            .find(|locale| locale.tag.eq_ignore_ascii_case(language))
+    }
+
+    /// Instruction added to the greeting prompt
+    #[must_use]
+    pub fn prompt_instruction(&self) -> String {
+        format!("Greet me in {}.", self.language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_matches_language_subtag() {
+        assert_eq!(GreetingLocale::lookup("es").unwrap().language, "Spanish");
+        assert_eq!(GreetingLocale::lookup("es-MX").unwrap().language, "Spanish");
+        assert_eq!(
+            GreetingLocale::lookup("PT_br").unwrap().language,
+            "Portuguese"
+        );
+        assert_eq!(GreetingLocale::lookup("xx"), None);
+        assert_eq!(GreetingLocale::lookup(""), None);
+    }
+
+    #[test]
+    fn test_locale_tags_are_unique() {
+        for (i, locale) in GREETING_LOCALES.iter().enumerate() {
+            assert!(
+                GREETING_LOCALES[..i].iter().all(|l| l.tag != locale.tag),
+                "Duplicate locale {}",
+                locale.tag
+            );
+            assert_eq!(GreetingLocale::lookup(locale.tag), Some(locale));
+        }
+    }
+}
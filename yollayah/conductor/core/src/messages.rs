@@ -16,11 +16,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::animation::AnimationPriority;
 use crate::avatar::{
-    AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction, AvatarSize, AvatarState,
+    AvatarActivity, AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction, AvatarSize,
+    AvatarState, Block,
 };
-use crate::conversation::{ConversationId, ConversationState};
-use crate::tasks::TaskId;
+use crate::backend::ModelInfo;
+use crate::conversation::{ConversationId, ConversationListEntry, ConversationState};
+use crate::events::SurfaceCapabilities;
+use crate::tasks::{TaskFilter, TaskId, TaskSort};
 
 /// Messages from Conductor to UI Surface
 ///
@@ -42,6 +46,20 @@ pub enum ConductorMessage {
         /// Content type hint for rendering
         #[serde(default)]
         content_type: ContentType,
+        /// Re-sent for display recovery (replace the surface's copy, don't append)
+        #[serde(default)]
+        resend: bool,
+    },
+
+    /// A streaming response is starting
+    ///
+    /// Sent before the message's first `Token`, so surfaces can show its
+    /// bubble ahead of any content. The greeting gets one too.
+    StreamStart {
+        /// Message ID the tokens will carry
+        message_id: MessageId,
+        /// Who the message is from
+        role: MessageRole,
     },
 
     /// A streaming token (partial response)
@@ -50,6 +68,23 @@ pub enum ConductorMessage {
         message_id: MessageId,
         /// The token text
         text: String,
+        /// Position in the conversation's token sequence (starts at 1)
+        ///
+        /// Surfaces on lossy transports use this to detect dropped tokens
+        /// and send `RequestSnapshot`. 0 means the token isn't numbered.
+        #[serde(default)]
+        seq: u64,
+    },
+
+    /// Reasoning from a streaming message (`<think>` content)
+    ///
+    /// Only sent when the Conductor streams reasoning separately; the
+    /// message's tokens and final content then leave it out.
+    Reasoning {
+        /// Message ID the reasoning belongs to
+        message_id: MessageId,
+        /// The reasoning text
+        text: String,
     },
 
     /// Stream has completed
@@ -71,6 +106,29 @@ pub enum ConductorMessage {
         error: String,
     },
 
+    /// A response was abandoned mid-stream because a newer message arrived
+    ///
+    /// Surfaces should stop streaming it and keep what they showed, as the
+    /// session keeps the partial text as a cancelled message.
+    StreamCancelled {
+        /// Message ID that was abandoned
+        message_id: MessageId,
+    },
+
+    /// The first message a surface gets on connect
+    ///
+    /// Surfaces can style it apart from the conversation or collapse it.
+    /// A dynamic greeting streams like any response and finishes with this
+    /// in place of `StreamEnd`, under the same ID.
+    Welcome {
+        /// Message ID (the greeting's, so a resync doesn't duplicate it)
+        id: MessageId,
+        /// Text to show (avatar commands already stripped)
+        content: String,
+        /// Generated by the model (a greeting) rather than a fixed message
+        is_dynamic: bool,
+    },
+
     // ============================================
     // Multi-Conversation Messages
     // ============================================
@@ -134,6 +192,12 @@ pub enum ConductorMessage {
         conversation_id: ConversationId,
     },
 
+    /// Current conversations with their unread counts
+    ConversationList {
+        /// Conversations in z-order (bottom to top)
+        conversations: Vec<ConversationListEntry>,
+    },
+
     // ============================================
     // Avatar Directives
     // ============================================
@@ -149,6 +213,23 @@ pub enum ConductorMessage {
         mood: AvatarMood,
     },
 
+    /// Concrete sprite frames for the avatar's current animation
+    ///
+    /// Sent instead of `AvatarMood` to surfaces that asked for it with
+    /// `SurfaceCapabilities::sprite_push` and can't draw sprites themselves.
+    AvatarSprite {
+        /// Animation the frames were resolved from (e.g., "happy")
+        animation: String,
+        /// Playback rate in frames per second
+        fps: f32,
+        /// Frame width in blocks
+        width: u16,
+        /// Frame height in blocks
+        height: u16,
+        /// Blocks of each frame, row-major
+        blocks: Vec<Vec<Block>>,
+    },
+
     /// Set avatar size
     AvatarSize {
         /// The size to use
@@ -171,6 +252,12 @@ pub enum ConductorMessage {
         duration_ms: u32,
     },
 
+    /// Briefly close the avatar's eyes (idle micro-animation)
+    AvatarBlink {
+        /// How long the eyes stay closed in milliseconds
+        duration_ms: u32,
+    },
+
     /// Show/hide the avatar
     AvatarVisibility {
         /// Whether avatar should be visible
@@ -191,6 +278,21 @@ pub enum ConductorMessage {
         y_percent: u8,
     },
 
+    /// Show an activity overlay on the avatar
+    AvatarActivity {
+        /// The activity to overlay (`None` clears it)
+        activity: AvatarActivity,
+    },
+
+    /// Avatar went back to its defaults
+    ///
+    /// Surfaces snap to `state` and drop any gesture or reaction in
+    /// progress. The activity overlay is left as it was.
+    AvatarReset {
+        /// The restored state
+        state: AvatarStateSnapshot,
+    },
+
     // ============================================
     // Task Directives
     // ============================================
@@ -236,6 +338,15 @@ pub enum ConductorMessage {
         task_id: TaskId,
     },
 
+    /// How the task panel was last sorted and filtered, so a surface
+    /// that connects can restore it
+    TaskView {
+        /// Task ordering
+        sort: TaskSort,
+        /// Which tasks are shown
+        filter: TaskFilter,
+    },
+
     // ============================================
     // Layout Directives
     // ============================================
@@ -256,6 +367,10 @@ pub enum ConductorMessage {
         title: Option<String>,
         /// Message content
         message: String,
+        /// Actionable next step for a known failure, shown apart from the
+        /// message (see [`ConductorError::guidance`])
+        #[serde(default)]
+        guidance: Option<String>,
     },
 
     /// Conductor state change
@@ -283,6 +398,28 @@ pub enum ConductorMessage {
         ready: bool,
     },
 
+    /// A model finished warming up; if it's the main model, it now serves
+    /// new turns
+    ModelReady {
+        /// The model that's warm
+        model: String,
+        /// Whether it's the main model (rather than another routing model)
+        primary: bool,
+    },
+
+    /// The main model is still loading; sent periodically until it's warm
+    /// so surfaces can show a loading indicator
+    WarmupProgress {
+        /// How long the main model has been loading
+        elapsed_ms: u64,
+    },
+
+    /// System prompt changed (applies to subsequent turns only)
+    SystemPromptChanged {
+        /// The new system prompt (None when cleared)
+        prompt: Option<String>,
+    },
+
     /// Request surface to quit
     Quit {
         /// Optional goodbye message
@@ -304,6 +441,23 @@ pub enum ConductorMessage {
         rejection_reason: Option<String>,
         /// Protocol version supported by Conductor
         protocol_version: u32,
+        /// Whether CRC32 frame checksums are enabled for this connection
+        #[serde(default)]
+        frame_checksums: bool,
+        /// Set when the refusal is temporary (e.g. the daemon is full):
+        /// retry after at least this long instead of giving up
+        #[serde(default)]
+        retry_after_ms: Option<u64>,
+    },
+
+    /// Capabilities the Conductor will actually honor for this surface
+    ///
+    /// Sent after an accepted handshake. This is the intersection of what
+    /// the surface claimed and what the Conductor supports, so a surface
+    /// claiming e.g. audio learns it won't receive any.
+    NegotiatedCapabilities {
+        /// Effective capability set
+        capabilities: SurfaceCapabilities,
     },
 
     /// Heartbeat request
@@ -315,6 +469,40 @@ pub enum ConductorMessage {
         seq: u64,
     },
 
+    /// A user message was edited and is being answered again
+    ///
+    /// Surfaces should update it in place and drop the messages that
+    /// followed it.
+    MessageEdited {
+        /// The edited message
+        message_id: MessageId,
+        /// Length of the previous text in bytes
+        old_len: usize,
+        /// The text it now has
+        new_content: String,
+        /// IDs of the messages that followed it, oldest first
+        removed: Vec<MessageId>,
+    },
+
+    /// Messages were taken out of the conversation (e.g. by an undo, or
+    /// when a turn is answered again)
+    ///
+    /// Surfaces should drop them from their display.
+    HistoryTruncated {
+        /// IDs of the removed messages, oldest first
+        removed: Vec<MessageId>,
+    },
+
+    /// Another surface hit a fatal error and was disconnected
+    ///
+    /// Sent to the surfaces that remain, so they can tell the user.
+    SurfaceFailed {
+        /// Connection ID of the failed surface
+        connection_id: String,
+        /// What the surface reported
+        error: String,
+    },
+
     /// State snapshot for newly connected or late-joining surfaces
     ///
     /// Sent after successful handshake to synchronize the surface with
@@ -328,6 +516,84 @@ pub enum ConductorMessage {
         /// Session information
         session_info: SessionSnapshot,
     },
+
+    /// Answer to a surface's `RequestHealth`
+    Health {
+        /// Aggregated Conductor health
+        report: HealthReport,
+    },
+
+    /// Answer to a surface's `RequestModelInfo`
+    ModelInfo {
+        /// The configured model name
+        model: String,
+        /// What the backend reports for it (None if the backend doesn't
+        /// list the model, e.g. it was never pulled)
+        info: Option<ModelInfo>,
+    },
+
+    /// The conversation, exported at a surface's `/history` request
+    ExportReady {
+        /// Format of `content`
+        format: ExportFormat,
+        /// The rendered conversation
+        content: String,
+    },
+}
+
+impl ConductorMessage {
+    /// Animation priority of an avatar directive
+    ///
+    /// Ambient changes (mood, wandering, blinks) are `Low`, gestures,
+    /// reactions and movement are `Normal`, and the error reaction is
+    /// `Critical`. Returns `None` for messages that don't animate the avatar.
+    #[must_use]
+    pub fn animation_priority(&self) -> Option<AnimationPriority> {
+        match self {
+            Self::AvatarMood { .. }
+            | Self::AvatarSprite { .. }
+            | Self::AvatarWander { .. }
+            | Self::AvatarBlink { .. } => Some(AnimationPriority::Low),
+            Self::AvatarReact {
+                reaction: AvatarReaction::Oops,
+                ..
+            } => Some(AnimationPriority::Critical),
+            Self::AvatarGesture { .. }
+            | Self::AvatarReact { .. }
+            | Self::AvatarMoveTo { .. }
+            | Self::AvatarPointAt { .. } => Some(AnimationPriority::Normal),
+            _ => None,
+        }
+    }
+
+    /// Size of this message once serialized for the wire (JSON)
+    #[must_use]
+    pub fn wire_size(&self) -> usize {
+        serde_json::to_vec(self).map_or(usize::MAX, |bytes| bytes.len())
+    }
+
+    /// Trim a `StateSnapshot` so it serializes within `max_size` bytes
+    ///
+    /// Drops the oldest history entries first and returns how many were
+    /// dropped. Other message types are left untouched.
+    pub fn trim_snapshot_to_fit(&mut self, max_size: usize) -> usize {
+        let mut dropped = 0;
+        while self.wire_size() > max_size {
+            let Self::StateSnapshot {
+                conversation_history,
+                ..
+            } = self
+            else {
+                break;
+            };
+            if conversation_history.is_empty() {
+                break;
+            }
+            conversation_history.remove(0);
+            dropped += 1;
+        }
+        dropped
+    }
 }
 
 /// Message identifier
@@ -394,6 +660,41 @@ pub enum MessageRole {
     System,
 }
 
+/// Format of an exported conversation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// A Markdown header per speaker
+    #[default]
+    Markdown,
+    /// Plain `Speaker: text` paragraphs
+    Text,
+    /// A JSON array of `OpenAI`-style `{role, content}` messages
+    OpenAi,
+}
+
+impl ExportFormat {
+    /// Parse a `/history` argument (`md`, `txt` or `json`)
+    #[must_use]
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "md" => Some(Self::Markdown),
+            "txt" => Some(Self::Text),
+            "json" => Some(Self::OpenAi),
+            _ => None,
+        }
+    }
+
+    /// File extension for an export saved in this format
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Text => "txt",
+            Self::OpenAi => "json",
+        }
+    }
+}
+
 /// Content type hints for message rendering
 ///
 /// Tells UI surfaces how to render the message content appropriately.
@@ -472,6 +773,61 @@ pub enum NotifyLevel {
     Success,
 }
 
+impl NotifyLevel {
+    /// How much the level matters: Info, then Success, Warning, Error
+    #[must_use]
+    pub fn severity(self) -> u8 {
+        match self {
+            Self::Info => 0,
+            Self::Success => 1,
+            Self::Warning => 2,
+            Self::Error => 3,
+        }
+    }
+
+    /// Whether a notification at this level passes a `min` filter
+    #[must_use]
+    pub fn is_at_least(self, min: NotifyLevel) -> bool {
+        self.severity() >= min.severity()
+    }
+}
+
+/// Common failures with known recovery steps
+///
+/// Error notifications for these carry the code's guidance, so users get a
+/// next step instead of just a terse error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConductorError {
+    /// The LLM backend couldn't be reached
+    BackendUnreachable,
+    /// The requested model isn't installed
+    ModelNotFound,
+    /// The user sent messages or commands too quickly
+    RateLimited,
+    /// The backend was reached but the response failed
+    ResponseFailed,
+}
+
+impl ConductorError {
+    /// What the user can do about it
+    #[must_use]
+    pub fn guidance(self) -> &'static str {
+        match self {
+            Self::BackendUnreachable => {
+                "Start the model server (for Ollama, run `ollama serve`), then try again."
+            }
+            Self::ModelNotFound => {
+                "Run `/model` to list installed models and pick one, e.g. `/model llama3.2`, \
+                 or install it with `ollama pull <model>`."
+            }
+            Self::RateLimited => "Wait a few seconds, then send again.",
+            Self::ResponseFailed => {
+                "Send your message again, or try another model with `/model <name>`."
+            }
+        }
+    }
+}
+
 /// Response metadata for surface display
 ///
 /// Contains metrics and context about a completed response that surfaces
@@ -498,6 +854,45 @@ pub struct ResponseMetadata {
     /// Optional context hint for surface commentary
     /// e.g., "`large_file`", "`slow_network`", "`complex_reasoning`"
     pub context_hint: Option<String>,
+    /// Time between consecutive tokens (None with fewer than two tokens)
+    pub inter_token_latency: Option<TokenLatency>,
+    /// Whether the user cut the response short (the content is partial)
+    #[serde(default)]
+    pub cancelled: bool,
+    /// ID of the turn that produced the response, for bug reports
+    #[serde(default)]
+    pub trace_id: Option<String>,
+}
+
+/// Inter-token latency of a streamed response
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TokenLatency {
+    /// Shortest gap between two tokens
+    pub min_ms: u64,
+    /// Longest gap between two tokens
+    pub max_ms: u64,
+    /// Average gap between tokens
+    pub mean_ms: f64,
+    /// Number of gaps measured (one fewer than the tokens)
+    pub samples: u32,
+}
+
+impl TokenLatency {
+    /// Stats over the gaps between tokens (None if there are none)
+    #[must_use]
+    pub fn from_gaps(gaps: &[u64]) -> Option<Self> {
+        let min_ms = *gaps.iter().min()?;
+        let max_ms = *gaps.iter().max()?;
+        let samples = u32::try_from(gaps.len()).unwrap_or(u32::MAX);
+        #[allow(clippy::cast_precision_loss)]
+        let mean_ms = gaps.iter().sum::<u64>() as f64 / gaps.len() as f64;
+        Some(Self {
+            min_ms,
+            max_ms,
+            mean_ms,
+            samples,
+        })
+    }
 }
 
 impl ResponseMetadata {
@@ -563,6 +958,21 @@ impl ConductorState {
             Self::ShuttingDown => "Shutting down...",
         }
     }
+
+    /// Activity overlay the avatar shows in this state
+    ///
+    /// Thinking dots while waiting for the model (including warm-up) and
+    /// talking while a response streams in.
+    #[must_use]
+    pub fn avatar_activity(&self) -> AvatarActivity {
+        match self {
+            Self::Initializing | Self::Thinking => AvatarActivity::Thinking,
+            Self::Responding => AvatarActivity::Talking,
+            Self::Ready | Self::Listening | Self::Error | Self::ShuttingDown => {
+                AvatarActivity::None
+            }
+        }
+    }
 }
 
 // ============================================
@@ -605,7 +1015,7 @@ impl SnapshotMessage {
 }
 
 /// Avatar state snapshot for initial sync
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AvatarStateSnapshot {
     /// Current position
     pub position: AvatarPosition,
@@ -690,6 +1100,38 @@ impl SessionSnapshot {
     }
 }
 
+// ============================================
+// Health
+// ============================================
+
+/// Overall Conductor health, from `Conductor::health`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// Whether the LLM backend answered its health check
+    pub backend_reachable: bool,
+    /// Whether the query router is healthy (None when routing is disabled)
+    pub router_healthy: Option<bool>,
+    /// Number of connected surfaces
+    pub surface_count: usize,
+    /// Current conductor state
+    pub state: ConductorState,
+    /// Whether model warmup has finished
+    pub warmup_complete: bool,
+}
+
+impl HealthReport {
+    /// Whether everything that should be working is
+    ///
+    /// Surface count and warmup don't count against health: no surfaces
+    /// is normal for a daemon, and turns are served during warmup.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.backend_reachable
+            && self.router_healthy != Some(false)
+            && self.state != ConductorState::Error
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -711,9 +1153,133 @@ mod tests {
         assert!(!id2.0.is_empty());
     }
 
+    fn snapshot_with_history(count: usize, content_len: usize) -> ConductorMessage {
+        ConductorMessage::StateSnapshot {
+            conversation_history: (0..count)
+                .map(|i| {
+                    SnapshotMessage::new(
+                        MessageId(format!("msg_{i}")),
+                        MessageRole::User,
+                        "x".repeat(content_len),
+                    )
+                })
+                .collect(),
+            avatar_state: AvatarStateSnapshot::default(),
+            session_info: SessionSnapshot::new(
+                SessionId::new(),
+                "test".to_string(),
+                true,
+                ConductorState::Ready,
+                0,
+                0,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_trim_snapshot_to_fit_drops_oldest() {
+        let mut snapshot = snapshot_with_history(20, 1000);
+        let max_size = 8 * 1024;
+        assert!(snapshot.wire_size() > max_size);
+
+        let dropped = snapshot.trim_snapshot_to_fit(max_size);
+
+        assert!(dropped > 0);
+        assert!(snapshot.wire_size() <= max_size);
+        let ConductorMessage::StateSnapshot {
+            conversation_history,
+            ..
+        } = snapshot
+        else {
+            panic!("Expected StateSnapshot");
+        };
+        assert_eq!(conversation_history.len(), 20 - dropped);
+        // The most recent message is kept
+        assert_eq!(conversation_history.last().unwrap().id.0, "msg_19");
+    }
+
+    #[test]
+    fn test_trim_snapshot_within_limit_untouched() {
+        let mut snapshot = snapshot_with_history(3, 10);
+        assert_eq!(snapshot.trim_snapshot_to_fit(100 * 1024), 0);
+    }
+
     #[test]
     fn test_conductor_state_description() {
         assert_eq!(ConductorState::Ready.description(), "Ready");
         assert_eq!(ConductorState::Thinking.description(), "Thinking...");
     }
+
+    #[test]
+    fn test_health_report_is_healthy() {
+        let report = HealthReport {
+            backend_reachable: true,
+            router_healthy: None,
+            surface_count: 0,
+            state: ConductorState::Ready,
+            warmup_complete: false,
+        };
+        assert!(report.is_healthy());
+
+        assert!(!HealthReport {
+            backend_reachable: false,
+            ..report.clone()
+        }
+        .is_healthy());
+        assert!(!HealthReport {
+            router_healthy: Some(false),
+            ..report.clone()
+        }
+        .is_healthy());
+        assert!(!HealthReport {
+            state: ConductorState::Error,
+            ..report
+        }
+        .is_healthy());
+    }
+
+    #[test]
+    fn test_error_codes_have_guidance() {
+        assert_eq!(
+            ConductorError::BackendUnreachable.guidance(),
+            "Start the model server (for Ollama, run `ollama serve`), then try again."
+        );
+        assert_eq!(
+            ConductorError::ModelNotFound.guidance(),
+            "Run `/model` to list installed models and pick one, e.g. `/model llama3.2`, \
+             or install it with `ollama pull <model>`."
+        );
+        assert_eq!(
+            ConductorError::RateLimited.guidance(),
+            "Wait a few seconds, then send again."
+        );
+        assert_eq!(
+            ConductorError::ResponseFailed.guidance(),
+            "Send your message again, or try another model with `/model <name>`."
+        );
+    }
+
+    #[test]
+    fn test_notify_guidance_is_optional_on_the_wire() {
+        let json = r#"{"Notify":{"level":"Error","title":null,"message":"Oops"}}"#;
+        let msg: ConductorMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            msg,
+            ConductorMessage::Notify { guidance: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_token_latency_from_gaps() {
+        assert_eq!(TokenLatency::from_gaps(&[]), None);
+        assert_eq!(
+            TokenLatency::from_gaps(&[30, 10, 25, 15]),
+            Some(TokenLatency {
+                min_ms: 10,
+                max_ms: 30,
+                mean_ms: 20.0,
+                samples: 4,
+            })
+        );
+    }
 }
@@ -0,0 +1,201 @@
+//! Session Redaction
+//!
+//! Masks sensitive text (email addresses, API keys) in sessions before
+//! they're written to disk. Only the stored copy is masked: the session in
+//! memory, and what surfaces show, keep the original text. Masking can't be
+//! undone, so a session loaded back from disk keeps its masks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::session::Session;
+
+/// Replaces a masked email address
+pub const EMAIL_MASK: &str = "[redacted email]";
+
+/// Replaces a masked API key
+pub const API_KEY_MASK: &str = "[redacted key]";
+
+/// Prefixes of well-known API key formats
+const API_KEY_PREFIXES: &[&str] = &[
+    "sk-",
+    "sk_",
+    "pk_",
+    "rk_",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "github_pat_",
+    "glpat-",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+    "AIza",
+    "hf_",
+];
+
+/// Shortest token that's treated as a key, prefix included
+const MIN_API_KEY_LEN: usize = 20;
+
+/// Punctuation that may wrap a word without being part of it
+const TRIMMED_PUNCTUATION: &[char] = &[
+    '<', '>', '(', ')', '[', ']', '{', '}', '"', '\'', '`', ',', ';', ':', '.', '!', '?',
+];
+
+/// Kind of text to mask
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionRule {
+    /// Email addresses, e.g. `someone@example.com`
+    Emails,
+    /// API keys with a well-known prefix, e.g. `sk-...` or `ghp_...`
+    ApiKeys,
+}
+
+impl RedactionRule {
+    /// Parse a rule name (`emails` or `api_keys`)
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "emails" | "email" => Some(Self::Emails),
+            "api_keys" | "api_key" | "keys" => Some(Self::ApiKeys),
+            _ => None,
+        }
+    }
+
+    /// Mask for a word this rule matches, if it does
+    fn mask_for(self, word: &str) -> Option<&'static str> {
+        match self {
+            Self::Emails if is_email(word) => Some(EMAIL_MASK),
+            Self::ApiKeys if is_api_key(word) => Some(API_KEY_MASK),
+            _ => None,
+        }
+    }
+}
+
+/// Masks text matching its rules
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Redactor {
+    rules: Vec<RedactionRule>,
+}
+
+impl Redactor {
+    /// Mask whatever any of `rules` matches
+    #[must_use]
+    pub fn new(rules: Vec<RedactionRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Whether there's nothing to mask
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// `text` with every match masked
+    ///
+    /// Text is matched a word (whitespace-separated run) at a time,
+    /// ignoring punctuation around it, so whitespace and punctuation are
+    /// kept as they were.
+    #[must_use]
+    pub fn redact(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+
+        let mut out = String::with_capacity(text.len());
+        for chunk in text.split_inclusive(char::is_whitespace) {
+            let word = chunk.trim_end_matches(char::is_whitespace);
+            let trimmed = word.trim_start_matches(TRIMMED_PUNCTUATION);
+            let start = word.len() - trimmed.len();
+            let core = trimmed.trim_end_matches(TRIMMED_PUNCTUATION);
+            match self.rules.iter().find_map(|rule| rule.mask_for(core)) {
+                Some(mask) => {
+                    out.push_str(&word[..start]);
+                    out.push_str(mask);
+                    out.push_str(&chunk[start + core.len()..]);
+                }
+                _ => out.push_str(chunk),
+            }
+        }
+        out
+    }
+
+    /// A copy of `session` with its messages and system prompt masked
+    #[must_use]
+    pub fn redact_session(&self, session: &Session) -> Session {
+        let mut redacted = session.clone();
+        for msg in &mut redacted.messages {
+            msg.content = self.redact(&msg.content);
+        }
+        if let Some(prompt) = redacted.system_prompt.as_mut() {
+            *prompt = self.redact(prompt);
+        }
+        redacted
+    }
+}
+
+/// `local@domain.tld`, loosely
+fn is_email(word: &str) -> bool {
+    let Some((local, domain)) = word.split_once('@') else {
+        return false;
+    };
+    let valid_local = !local.is_empty()
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || "._%+-".contains(c));
+    let valid_domain = domain
+        .split('.')
+        .all(|label| !label.is_empty() && label.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        && domain.contains('.');
+    valid_local && valid_domain
+}
+
+/// A long token starting with a known key prefix
+fn is_api_key(word: &str) -> bool {
+    word.len() >= MIN_API_KEY_LEN
+        && API_KEY_PREFIXES
+            .iter()
+            .any(|prefix| word.starts_with(prefix))
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_rules() -> Redactor {
+        Redactor::new(vec![RedactionRule::Emails, RedactionRule::ApiKeys])
+    }
+
+    #[test]
+    fn test_masks_emails_and_keys_keeping_surroundings() {
+        let text = "Mail me (ana.lopez+ai@example.co.uk), key:\nsk-abc123def456ghi789jkl.";
+        assert_eq!(
+            all_rules().redact(text),
+            "Mail me ([redacted email]), key:\n[redacted key]."
+        );
+    }
+
+    #[test]
+    fn test_leaves_lookalikes_alone() {
+        let text = "Use @mentions, a@b, sk-short and the task-runner flag.";
+        assert_eq!(all_rules().redact(text), text);
+        assert_eq!(Redactor::default().redact("a@example.com"), "a@example.com");
+    }
+
+    #[test]
+    fn test_only_configured_rules_apply() {
+        let emails = Redactor::new(vec![RedactionRule::Emails]);
+        assert_eq!(
+            emails.redact("a@example.com ghp_0123456789abcdefghijkl"),
+            "[redacted email] ghp_0123456789abcdefghijkl"
+        );
+        assert_eq!(
+            RedactionRule::parse(" API_KEYS "),
+            Some(RedactionRule::ApiKeys)
+        );
+        assert_eq!(RedactionRule::parse("phones"), None);
+    }
+}
@@ -173,6 +173,7 @@ impl BackendAdapter for OllamaAdapter {
             let _ = tx
                 .send(StreamingToken::Complete {
                     message: format!("Hello from {model}"),
+                    finish_reason: None,
                 })
                 .await;
         });
@@ -275,6 +276,7 @@ impl BackendAdapter for OpenAIAdapter {
             let _ = tx
                 .send(StreamingToken::Complete {
                     message: format!("Response from {model}"),
+                    finish_reason: None,
                 })
                 .await;
         });
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::backend::LlmRequest;
+
 // ============================================================================
 // Task Classification
 // ============================================================================
@@ -477,6 +479,43 @@ impl RetryConfig {
     }
 }
 
+// ============================================================================
+// Per-Model Parameters
+// ============================================================================
+
+/// Default request parameters for a single model
+///
+/// Unset fields leave the global request defaults in place.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelParameters {
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+
+    /// Maximum tokens in the response
+    pub max_tokens: Option<u32>,
+
+    /// System prompt to use when the request doesn't set one
+    pub system: Option<String>,
+}
+
+impl ModelParameters {
+    /// Apply these defaults to a request, overriding the global defaults
+    #[must_use]
+    pub fn apply(&self, mut request: LlmRequest) -> LlmRequest {
+        if let Some(temperature) = self.temperature {
+            request.temperature = temperature;
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            request.max_tokens = max_tokens;
+        }
+        if request.system.is_none() {
+            request.system.clone_from(&self.system);
+        }
+        request
+    }
+}
+
 // ============================================================================
 // Full Router Configuration
 // ============================================================================
@@ -496,6 +535,9 @@ pub struct RouterConfig {
     /// Fallback chains: model -> ordered list of fallbacks
     pub fallback_chains: HashMap<String, Vec<String>>,
 
+    /// Per-model default request parameters: model -> parameters
+    pub model_parameters: HashMap<String, ModelParameters>,
+
     /// Global rate limits
     pub global_rate_limits: RateLimitConfig,
 
@@ -525,6 +567,7 @@ impl Default for RouterConfig {
             models: Vec::new(),
             default_models: HashMap::new(),
             fallback_chains: HashMap::new(),
+            model_parameters: HashMap::new(),
             global_rate_limits: RateLimitConfig::default(),
             preload_models: Vec::new(),
             enable_queue: true,
@@ -630,4 +673,22 @@ mod tests {
         assert_eq!(profile.affinity_for(TaskClass::QuickResponse), 0.2);
         assert_eq!(profile.affinity_for(TaskClass::Creative), 0.5);
     }
+
+    #[test]
+    fn test_model_parameters_override_defaults() {
+        let params = ModelParameters {
+            temperature: Some(0.2),
+            system: Some("Be brief.".to_string()),
+            ..Default::default()
+        };
+
+        let request = params.apply(LlmRequest::new("hi", "model"));
+        assert_eq!(request.temperature, 0.2);
+        assert_eq!(request.max_tokens, 0);
+        assert_eq!(request.system.as_deref(), Some("Be brief."));
+
+        // An explicit system prompt wins over the model default
+        let request = params.apply(LlmRequest::new("hi", "model").with_system("Be verbose."));
+        assert_eq!(request.system.as_deref(), Some("Be verbose."));
+    }
 }
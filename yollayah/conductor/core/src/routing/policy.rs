@@ -70,6 +70,9 @@ pub struct RoutingRequest {
 
     /// Priority override
     pub priority: Option<u8>,
+
+    /// ID correlating this request with the turn that made it (for logs)
+    pub trace_id: Option<String>,
 }
 
 impl Default for RoutingRequest {
@@ -88,6 +91,7 @@ impl Default for RoutingRequest {
             timeout: None,
             conversation_id: None,
             priority: None,
+            trace_id: None,
         }
     }
 }
@@ -127,6 +131,13 @@ impl RoutingRequest {
         self
     }
 
+    /// Set the trace ID (carried into the backend request)
+    #[must_use]
+    pub fn with_trace_id(mut self, trace_id: Option<String>) -> Self {
+        self.trace_id = trace_id;
+        self
+    }
+
     /// Classify the request if not already classified
     #[must_use]
     pub fn classify(&self) -> TaskClass {
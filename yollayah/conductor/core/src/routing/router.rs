@@ -18,6 +18,7 @@ use std::time::{Duration, Instant};
 
 use tokio::sync::{mpsc, RwLock, Semaphore};
 
+use super::backends::{create_adapter, BackendAdapter, BackendAdapterError};
 use super::config::{BackendConfig, RetryConfig, RouterConfig};
 use super::connection_pool::{ConnectionPool, PoolError, PoolManager};
 use super::fallback::{FallbackChainManager, FallbackContext};
@@ -102,6 +103,8 @@ struct BackendHandle {
     config: BackendConfig,
     pool: Arc<ConnectionPool>,
     healthy: RwLock<bool>,
+    /// Talks to the backend's API
+    adapter: Box<dyn BackendAdapter>,
 }
 
 /// Priority queue for requests
@@ -272,12 +275,23 @@ impl QueryRouter {
 
     /// Register a backend
     async fn register_backend(&self, config: BackendConfig) -> Result<(), RouterError> {
+        let adapter = create_adapter(&config.backend_type);
+        self.register_backend_with(config, adapter).await
+    }
+
+    /// Register a backend that requests go to through `adapter`
+    async fn register_backend_with(
+        &self,
+        config: BackendConfig,
+        adapter: Box<dyn BackendAdapter>,
+    ) -> Result<(), RouterError> {
         let pool = self.pools.create_pool(&config).await;
 
         let handle = Arc::new(BackendHandle {
             config: config.clone(),
             pool,
             healthy: RwLock::new(true),
+            adapter,
         });
 
         let mut backends = self.backends.write().await;
@@ -297,6 +311,20 @@ impl QueryRouter {
         Ok(())
     }
 
+    /// Load `model_id` on the backend its profile names
+    ///
+    /// Returns None if no registered backend serves the model.
+    pub async fn warm_model(&self, model_id: &str) -> Option<Result<(), RouterError>> {
+        let profile = self.config.models.iter().find(|p| p.model_id == model_id)?;
+        let backends = self.backends.read().await;
+        let backend = Arc::clone(backends.get(&profile.backend_id)?);
+        drop(backends);
+        // An empty prompt loads the model without generating anything
+        let request = LlmRequest::new("", model_id).with_stream(false);
+        let result = backend.adapter.send(&request).await;
+        Some(result.map(|_| ()).map_err(RouterError::from))
+    }
+
     /// Spawn health check background task
     fn spawn_health_checker(&self) {
         let interval = Duration::from_millis(self.config.health_check_interval_ms);
@@ -378,6 +406,13 @@ impl QueryRouter {
             }
         }
 
+        tracing::debug!(
+            request_id = %request_id,
+            trace_id = request.trace_id.as_deref().unwrap_or_default(),
+            model = %decision.model_id,
+            "Routed request"
+        );
+
         let routing_time = start.elapsed();
         self.metrics
             .routing_decision_time
@@ -523,7 +558,6 @@ impl QueryRouter {
     }
 
     /// Execute a single request (no retry logic)
-    #[allow(unused_variables)]
     async fn execute_request(
         &self,
         request: &RoutingRequest,
@@ -541,64 +575,55 @@ impl QueryRouter {
             return Err(RouterError::BackendUnhealthy(backend_id.to_string()));
         }
 
-        // Acquire connection from pool
+        // Hold a pooled connection for the duration of the call
         let timeout = request.effective_timeout();
-        let conn = backend.pool.acquire(timeout).await.map_err(|e| match e {
+        let _conn = backend.pool.acquire(timeout).await.map_err(|e| match e {
             PoolError::Timeout => RouterError::Timeout,
             PoolError::PoolClosed => RouterError::ShuttingDown,
             e => RouterError::ConnectionError(e.to_string()),
         })?;
 
-        // Get HTTP client
-        let client = conn.http_client().ok_or_else(|| {
-            RouterError::BackendError(500, "No HTTP client available".to_string())
-        })?;
-
-        // Build LLM request
-        let llm_request =
-            LlmRequest::new(&request.prompt, model_id).with_stream(request.requires_streaming);
-
-        // For now, we'll return a placeholder - in a full implementation,
-        // this would call the actual backend
-        let model_id_owned = model_id.to_string();
+        // The model's parameters and the turn's trace ID go with it
+        let llm_request = self.build_llm_request(request, model_id);
 
         if request.requires_streaming {
-            let (tx, rx) = mpsc::channel(100);
-            let model_for_task = model_id_owned.clone();
-
-            // In a real implementation, spawn a task to stream from the backend
-            tokio::spawn(async move {
-                // Simulate streaming
-                let _ = tx
-                    .send(StreamingToken::Token("Response from ".to_string()))
-                    .await;
-                let _ = tx.send(StreamingToken::Token(model_for_task.clone())).await;
-                let _ = tx
-                    .send(StreamingToken::Complete {
-                        message: format!("Response from {model_for_task}"),
-                    })
-                    .await;
-            });
-
+            let receiver = backend
+                .adapter
+                .send_streaming(&llm_request)
+                .await
+                .map_err(RouterError::from)?;
             Ok(RouterResponse::Streaming {
-                receiver: rx,
+                receiver,
                 request_id: request.request_id.clone(),
-                model_id: model_id_owned,
+                model_id: model_id.to_string(),
             })
         } else {
+            let response = backend
+                .adapter
+                .send(&llm_request)
+                .await
+                .map_err(RouterError::from)?;
             Ok(RouterResponse::Complete {
-                response: LlmResponse {
-                    content: format!("Response from {model_id_owned}"),
-                    model: model_id_owned.clone(),
-                    tokens_used: Some(10),
-                    duration_ms: Some(100),
-                },
+                response,
                 request_id: request.request_id.clone(),
-                model_id: model_id_owned,
+                model_id: model_id.to_string(),
             })
         }
     }
 
+    /// Build the backend request for the selected model
+    ///
+    /// The model's configured parameters (if any) override the global defaults.
+    pub fn build_llm_request(&self, request: &RoutingRequest, model_id: &str) -> LlmRequest {
+        let llm_request = LlmRequest::new(&request.prompt, model_id)
+            .with_stream(request.requires_streaming)
+            .with_trace_id(request.trace_id.clone());
+        match self.config.model_parameters.get(model_id) {
+            Some(params) => params.apply(llm_request),
+            None => llm_request,
+        }
+    }
+
     /// Get retry config for a backend
     async fn get_retry_config(&self, backend_id: &str) -> RetryConfig {
         let backends = self.backends.read().await;
@@ -703,6 +728,22 @@ impl std::fmt::Display for RouterError {
 
 impl std::error::Error for RouterError {}
 
+impl From<BackendAdapterError> for RouterError {
+    fn from(error: BackendAdapterError) -> Self {
+        match error {
+            BackendAdapterError::Timeout => Self::Timeout,
+            BackendAdapterError::ConnectionFailed(e) => Self::ConnectionError(e),
+            BackendAdapterError::RateLimited { .. } => Self::BackendError(429, error.to_string()),
+            BackendAdapterError::AuthenticationFailed => Self::BackendError(401, error.to_string()),
+            BackendAdapterError::ModelNotFound(_) => Self::BackendError(404, error.to_string()),
+            BackendAdapterError::ResourceExhausted(_) => Self::BackendError(503, error.to_string()),
+            BackendAdapterError::RequestFailed(_) | BackendAdapterError::Internal(_) => {
+                Self::BackendError(500, error.to_string())
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -710,6 +751,10 @@ impl std::error::Error for RouterError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::routing::config::{
+        BackendType, ConnectionConfig, ModelParameters, ModelProfile, RateLimitConfig,
+        ResourceConfig,
+    };
 
     #[tokio::test]
     async fn test_router_creation() {
@@ -766,4 +811,170 @@ mod tests {
         let third = queue.pop().unwrap();
         assert_eq!(third.priority, 30);
     }
+
+    #[tokio::test]
+    async fn test_selected_model_gets_its_parameters() {
+        let mut config = RouterConfig::default();
+        for (model_id, temperature) in [("fast", 0.2), ("deep", 0.9)] {
+            config.models.push(ModelProfile::new(model_id, "local"));
+            config.model_parameters.insert(
+                model_id.to_string(),
+                ModelParameters {
+                    temperature: Some(temperature),
+                    ..Default::default()
+                },
+            );
+        }
+        let router = QueryRouter::new(config.clone());
+        for profile in config.models {
+            router.policy().register_model(profile).await;
+        }
+
+        for (model_id, temperature) in [("fast", 0.2), ("deep", 0.9)] {
+            let request = RoutingRequest::new("hello").with_model(model_id);
+            let decision = router.policy().route(&request).await.unwrap();
+            assert_eq!(decision.model_id, model_id);
+
+            let llm_request = router.build_llm_request(&request, &decision.model_id);
+            assert_eq!(llm_request.model, model_id);
+            assert_eq!(llm_request.temperature, temperature);
+        }
+
+        // Models without parameters keep the global defaults
+        let request = RoutingRequest::new("hello").with_trace_id(Some("t-1".to_string()));
+        let llm_request = router.build_llm_request(&request, "other");
+        assert_eq!(llm_request.temperature, LlmRequest::default().temperature);
+        assert_eq!(llm_request.trace_id.as_deref(), Some("t-1"));
+    }
+
+    /// Adapter that keeps every request it's sent
+    struct RecordingAdapter {
+        requests: Arc<std::sync::Mutex<Vec<LlmRequest>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl BackendAdapter for RecordingAdapter {
+        fn name(&self) -> &'static str {
+            "Recording"
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+
+        async fn send_streaming(
+            &self,
+            request: &LlmRequest,
+        ) -> Result<mpsc::Receiver<StreamingToken>, BackendAdapterError> {
+            self.requests.lock().unwrap().push(request.clone());
+            let (_tx, rx) = mpsc::channel(1);
+            Ok(rx)
+        }
+
+        async fn send(&self, request: &LlmRequest) -> Result<LlmResponse, BackendAdapterError> {
+            self.requests.lock().unwrap().push(request.clone());
+            Err(BackendAdapterError::RateLimited {
+                retry_after_ms: None,
+            })
+        }
+
+        async fn list_models(&self) -> Result<Vec<String>, BackendAdapterError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backend_gets_the_request_the_router_built() {
+        let mut config = RouterConfig::default();
+        config.model_parameters.insert(
+            "fast".to_string(),
+            ModelParameters {
+                temperature: Some(0.2),
+                ..Default::default()
+            },
+        );
+        let router = QueryRouter::new(config);
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let backend = BackendConfig {
+            id: "local".to_string(),
+            backend_type: BackendType::Ollama {
+                host: "localhost".to_string(),
+                port: 11434,
+            },
+            connection: ConnectionConfig::default(),
+            rate_limits: RateLimitConfig::default(),
+            resources: ResourceConfig::default(),
+            retry: RetryConfig::default(),
+            enabled: true,
+            fallback_priority: 0,
+        };
+        let adapter = RecordingAdapter {
+            requests: requests.clone(),
+        };
+        router
+            .register_backend_with(backend, Box::new(adapter))
+            .await
+            .unwrap();
+
+        let request = RoutingRequest::new("hello").with_trace_id(Some("t-1".to_string()));
+        let response = router.execute_request(&request, "fast", "local").await;
+        assert!(matches!(response, Ok(RouterResponse::Streaming { .. })));
+
+        let mut batch = RoutingRequest::new("hello again");
+        batch.requires_streaming = false;
+        let response = router.execute_request(&batch, "fast", "local").await;
+        assert!(matches!(response, Err(RouterError::BackendError(429, _))));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].prompt, "hello");
+        assert_eq!(requests[0].model, "fast");
+        assert_eq!(requests[0].temperature, 0.2);
+        assert_eq!(requests[0].trace_id.as_deref(), Some("t-1"));
+        assert!(requests[0].stream);
+        assert!(!requests[1].stream);
+    }
+
+    #[tokio::test]
+    async fn test_models_warm_on_their_own_backend() {
+        let mut config = RouterConfig::default();
+        config.models.push(ModelProfile::new("coder", "local"));
+        config.models.push(ModelProfile::new("writer", "cloud"));
+        let router = QueryRouter::new(config);
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let backend = BackendConfig {
+            id: "local".to_string(),
+            backend_type: BackendType::Ollama {
+                host: "localhost".to_string(),
+                port: 11434,
+            },
+            connection: ConnectionConfig::default(),
+            rate_limits: RateLimitConfig::default(),
+            resources: ResourceConfig::default(),
+            retry: RetryConfig::default(),
+            enabled: true,
+            fallback_priority: 0,
+        };
+        let adapter = RecordingAdapter {
+            requests: requests.clone(),
+        };
+        router
+            .register_backend_with(backend, Box::new(adapter))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            router.warm_model("coder").await,
+            Some(Err(RouterError::BackendError(429, _)))
+        ));
+        // No registered backend serves these
+        assert!(router.warm_model("writer").await.is_none());
+        assert!(router.warm_model("unknown").await.is_none());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].model, "coder");
+        assert_eq!(requests[0].prompt, "");
+        assert!(!requests[0].stream);
+    }
 }
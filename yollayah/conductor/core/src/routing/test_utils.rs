@@ -447,6 +447,7 @@ impl LlmBackend for MultiModelMockBackend {
             let _ = tx
                 .send(StreamingToken::Complete {
                     message: full_message,
+                    finish_reason: None,
                 })
                 .await;
         });
@@ -48,6 +48,12 @@ pub struct ConductorLimits {
     pub max_commands_per_response: usize,
     /// Maximum task description length (default: 1000)
     pub max_task_description_length: usize,
+    /// Maximum system prompt size in bytes (default: 16KB)
+    pub max_system_prompt_length: usize,
+    /// Maximum images attached to one message (default: 4)
+    pub max_images_per_message: usize,
+    /// Maximum decoded size of a single image in bytes (default: 10MB)
+    pub max_image_bytes: usize,
 }
 
 impl Default for ConductorLimits {
@@ -63,6 +69,9 @@ impl Default for ConductorLimits {
             task_cleanup_age_ms: 60 * 60 * 1000, // 1 hour
             max_commands_per_response: 10,
             max_task_description_length: 1000,
+            max_system_prompt_length: 16 * 1024, // 16KB
+            max_images_per_message: 4,
+            max_image_bytes: 10 * 1024 * 1024, // 10MB
         }
     }
 }
@@ -113,6 +122,18 @@ impl ConductorLimits {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(default.max_task_description_length),
+            max_system_prompt_length: std::env::var("CONDUCTOR_MAX_SYSTEM_PROMPT_LENGTH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_system_prompt_length),
+            max_images_per_message: std::env::var("CONDUCTOR_MAX_IMAGES_PER_MESSAGE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_images_per_message),
+            max_image_bytes: std::env::var("CONDUCTOR_MAX_IMAGE_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default.max_image_bytes),
         }
     }
 }
@@ -145,10 +166,32 @@ impl ValidationResult {
     }
 }
 
+/// What happens to a message with no text (empty or whitespace-only)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyMessageBehavior {
+    /// Reject it with a warning
+    #[default]
+    Reject,
+    /// Answer the latest turn again, as if asked to continue
+    Continue,
+}
+
+impl EmptyMessageBehavior {
+    /// Parse a behavior name (`reject`, or `continue` / `regenerate`)
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "reject" => Some(Self::Reject),
+            "continue" | "regenerate" => Some(Self::Continue),
+            _ => None,
+        }
+    }
+}
+
 /// Input validator for surface events
 ///
 /// Validates user input before processing to prevent:
-/// - Oversized messages
+/// - Empty or oversized messages
 /// - Rate limiting bypass
 /// - Control character injection
 pub struct InputValidator {
@@ -177,6 +220,10 @@ impl InputValidator {
             return result;
         }
 
+        if content.trim().is_empty() {
+            return ValidationResult::Invalid("Message is empty".to_string());
+        }
+
         // Check size
         if content.len() > self.limits.max_message_size {
             return ValidationResult::Invalid(format!(
@@ -256,6 +303,57 @@ impl InputValidator {
         ValidationResult::Valid
     }
 
+    /// Validate a system prompt set at runtime
+    pub fn validate_system_prompt(&self, prompt: &str) -> ValidationResult {
+        if prompt.len() > self.limits.max_system_prompt_length {
+            return ValidationResult::Invalid(format!(
+                "System prompt too large: {} bytes (max: {})",
+                prompt.len(),
+                self.limits.max_system_prompt_length
+            ));
+        }
+
+        if prompt
+            .chars()
+            .any(|c| c.is_control() && c != '\n' && c != '\t' && c != '\r')
+        {
+            return ValidationResult::Invalid(
+                "System prompt contains invalid control characters".to_string(),
+            );
+        }
+
+        ValidationResult::Valid
+    }
+
+    /// Validate base64-encoded images attached to a user message
+    ///
+    /// Checks the image count, that each image is base64, and each image's
+    /// decoded size. Doesn't count toward the rate limit; the message they
+    /// arrive with already has.
+    pub fn validate_images(&self, images: &[String]) -> ValidationResult {
+        if images.len() > self.limits.max_images_per_message {
+            return ValidationResult::Invalid(format!(
+                "Too many images: {} (max: {})",
+                images.len(),
+                self.limits.max_images_per_message
+            ));
+        }
+
+        for (i, image) in images.iter().enumerate() {
+            let Some(size) = decoded_base64_len(image) else {
+                return ValidationResult::Invalid(format!("Image {i} isn't valid base64"));
+            };
+            if size > self.limits.max_image_bytes {
+                return ValidationResult::Invalid(format!(
+                    "Image {i} too large: {size} bytes (max: {})",
+                    self.limits.max_image_bytes
+                ));
+            }
+        }
+
+        ValidationResult::Valid
+    }
+
     /// Check and update rate limit
     fn check_rate_limit(&self) -> ValidationResult {
         let mut window_start = self.window_start.lock().unwrap();
@@ -286,6 +384,21 @@ impl InputValidator {
     }
 }
 
+/// Size of the data a base64 string decodes to, or None if it isn't base64
+fn decoded_base64_len(encoded: &str) -> Option<usize> {
+    let bytes = encoded.as_bytes();
+    let data = bytes
+        .strip_suffix(b"==")
+        .or_else(|| bytes.strip_suffix(b"="))
+        .unwrap_or(bytes);
+    let valid = !data.is_empty()
+        && bytes.len().is_multiple_of(4)
+        && data
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/');
+    valid.then(|| data.len() * 3 / 4)
+}
+
 /// Reason why a command was rejected
 #[derive(Clone, Debug)]
 pub enum CommandRejectionReason {
@@ -677,6 +790,24 @@ mod tests {
         assert!(result.error_message().unwrap().contains("too large"));
     }
 
+    #[test]
+    fn test_input_validator_empty_message() {
+        let validator = InputValidator::new(ConductorLimits::default());
+        for blank in ["", "   ", "\n\t "] {
+            let result = validator.validate_message(blank);
+            assert_eq!(
+                result.error_message(),
+                Some("Message is empty"),
+                "{blank:?}"
+            );
+        }
+        assert_eq!(
+            EmptyMessageBehavior::parse(" Regenerate"),
+            Some(EmptyMessageBehavior::Continue)
+        );
+        assert_eq!(EmptyMessageBehavior::parse("ignore"), None);
+    }
+
     #[test]
     fn test_input_validator_control_characters() {
         let validator = InputValidator::new(ConductorLimits::default());
@@ -692,6 +823,44 @@ mod tests {
         assert!(result.is_valid());
     }
 
+    #[test]
+    fn test_input_validator_system_prompt_too_long() {
+        let mut limits = ConductorLimits::default();
+        limits.max_system_prompt_length = 8;
+        let validator = InputValidator::new(limits);
+        assert!(validator.validate_system_prompt("Be nice").is_valid());
+        let result = validator.validate_system_prompt("Be very, very nice");
+        assert!(result.error_message().unwrap().contains("too large"));
+    }
+
+    #[test]
+    fn test_input_validator_images() {
+        let mut limits = ConductorLimits::default();
+        limits.max_images_per_message = 2;
+        limits.max_image_bytes = 5;
+        let validator = InputValidator::new(limits);
+
+        // "hello" is exactly at the limit
+        assert!(validator
+            .validate_images(&["aGVsbG8=".to_string()])
+            .is_valid());
+        assert!(validator.validate_images(&[]).is_valid());
+
+        let result = validator.validate_images(&["aGVsbG8hIQ==".to_string()]);
+        assert!(result.error_message().unwrap().contains("too large"));
+
+        let result = validator.validate_images(&vec!["aGk=".to_string(); 3]);
+        assert!(result.error_message().unwrap().contains("Too many images"));
+
+        for bad in ["", "aGk", "aGk=!", "<svg>==="] {
+            let result = validator.validate_images(&[bad.to_string()]);
+            assert!(
+                result.error_message().unwrap().contains("base64"),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn test_input_validator_rate_limit() {
         let mut limits = ConductorLimits::default();
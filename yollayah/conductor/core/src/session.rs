@@ -9,9 +9,16 @@
 //! session state so UI surfaces can connect, disconnect, and reconnect
 //! without losing context. Sessions can be persisted and resumed.
 
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
-use crate::messages::{MessageId, MessageRole, SessionId};
+use crate::avatar::CommandParser;
+use crate::messages::{ExportFormat, MessageId, MessageRole, SessionId};
+use crate::redaction::Redactor;
+use crate::session_store::FileSessionStore;
 
 /// A message in the conversation
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -26,6 +33,9 @@ pub struct ConversationMessage {
     pub timestamp: u64,
     /// Whether the message is still being streamed
     pub streaming: bool,
+    /// Whether the response was cut short by the user (content is partial)
+    #[serde(default)]
+    pub cancelled: bool,
 }
 
 impl ConversationMessage {
@@ -38,6 +48,7 @@ impl ConversationMessage {
             content,
             timestamp: now_ms(),
             streaming: false,
+            cancelled: false,
         }
     }
 
@@ -50,6 +61,7 @@ impl ConversationMessage {
             content: String::new(),
             timestamp: now_ms(),
             streaming: true,
+            cancelled: false,
         }
     }
 
@@ -126,6 +138,16 @@ impl SessionMetadata {
     }
 }
 
+/// How messages from another session are merged into this one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Append every message not already present
+    AppendAll,
+    /// Append only assistant responses not already present
+    AppendAssistantOnly,
+}
+
 /// A conversation session
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Session {
@@ -148,6 +170,12 @@ pub struct Session {
     /// Current total content bytes
     #[serde(default)]
     current_content_bytes: usize,
+    /// System prompt for this conversation's turns (overrides the global one)
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Model for this conversation's turns (overrides the global one)
+    #[serde(default)]
+    pub model: Option<String>,
 }
 
 impl Session {
@@ -163,6 +191,8 @@ impl Session {
             max_messages: 0,      // 0 = unlimited (backwards compatible)
             max_content_bytes: 0, // 0 = unlimited (backwards compatible)
             current_content_bytes: 0,
+            system_prompt: None,
+            model: None,
         }
     }
 
@@ -178,6 +208,8 @@ impl Session {
             max_messages,
             max_content_bytes,
             current_content_bytes: 0,
+            system_prompt: None,
+            model: None,
         }
     }
 
@@ -193,6 +225,8 @@ impl Session {
             max_messages: 0,
             max_content_bytes: 0,
             current_content_bytes: 0,
+            system_prompt: None,
+            model: None,
         }
     }
 
@@ -220,6 +254,18 @@ impl Session {
         id
     }
 
+    /// Add a complete assistant message
+    pub fn add_assistant_message(&mut self, content: String) -> MessageId {
+        let content_len = content.len();
+        let msg = ConversationMessage::new(MessageRole::Assistant, content);
+        let id = msg.id.clone();
+        self.messages.push(msg);
+        self.current_content_bytes += content_len;
+        self.metadata.add_message();
+        self.prune_if_needed();
+        id
+    }
+
     /// Start a streaming assistant response
     pub fn start_assistant_response(&mut self) -> MessageId {
         let msg = ConversationMessage::streaming(MessageRole::Assistant);
@@ -242,6 +288,20 @@ impl Session {
         None
     }
 
+    /// Replace the current streaming response's content
+    ///
+    /// Used to settle on the authoritative text when the streamed tokens
+    /// didn't add up to it.
+    pub fn set_streaming_content(&mut self, content: String) {
+        if let Some(ref streaming_id) = self.current_streaming_id {
+            if let Some(msg) = self.messages.iter_mut().find(|m| &m.id == streaming_id) {
+                self.current_content_bytes =
+                    self.current_content_bytes.saturating_sub(msg.content.len()) + content.len();
+                msg.content = content;
+            }
+        }
+    }
+
     /// Complete the current streaming response
     pub fn complete_streaming(&mut self) -> Option<&ConversationMessage> {
         let streaming_id = self.current_streaming_id.take()?;
@@ -259,6 +319,20 @@ impl Session {
         self.messages.iter().find(|m| m.id == streaming_id)
     }
 
+    /// Stop the current streaming response, keeping what has streamed
+    ///
+    /// The message is completed with its partial content and marked
+    /// cancelled. Returns it, or None if nothing was streaming.
+    pub fn interrupt_streaming(&mut self) -> Option<&ConversationMessage> {
+        let streaming_id = self.current_streaming_id.take()?;
+        self.state = SessionState::Active;
+        let msg = self.messages.iter_mut().find(|m| m.id == streaming_id)?;
+        msg.complete();
+        msg.cancelled = true;
+        self.metadata.add_message();
+        self.messages.iter().find(|m| m.id == streaming_id)
+    }
+
     /// Cancel the current streaming response
     pub fn cancel_streaming(&mut self) {
         if let Some(streaming_id) = self.current_streaming_id.take() {
@@ -317,6 +391,105 @@ impl Session {
         context
     }
 
+    /// The conversation as an `OpenAI`-style `[{role, content}]` array
+    ///
+    /// The system prompt comes first, if there is one. Avatar commands are
+    /// stripped from assistant messages, and a response still streaming
+    /// (or left with nothing but commands) is skipped.
+    #[must_use]
+    pub fn to_openai_messages(&self) -> Vec<serde_json::Value> {
+        let system = self
+            .system_prompt
+            .iter()
+            .map(|prompt| openai_message("system", prompt));
+        let conversation = self
+            .messages
+            .iter()
+            .filter(|msg| !msg.streaming)
+            .filter_map(|msg| {
+                let (role, content) = match msg.role {
+                    MessageRole::User => ("user", msg.content.clone()),
+                    MessageRole::Assistant => (
+                        "assistant",
+                        CommandParser::strip_commands(&msg.content)
+                            .trim()
+                            .to_string(),
+                    ),
+                    MessageRole::System => ("system", msg.content.clone()),
+                };
+                (!content.is_empty()).then(|| openai_message(role, &content))
+            });
+        system.chain(conversation).collect()
+    }
+
+    /// The conversation rendered for the user to keep
+    ///
+    /// Avatar commands are stripped from every message, and a response
+    /// still streaming (or left with nothing but commands) is skipped.
+    #[must_use]
+    pub fn export(&self, format: ExportFormat) -> String {
+        if format == ExportFormat::OpenAi {
+            let messages = serde_json::Value::Array(self.to_openai_messages());
+            return serde_json::to_string_pretty(&messages).unwrap_or_default();
+        }
+        self.messages
+            .iter()
+            .filter(|msg| !msg.streaming)
+            .filter_map(|msg| {
+                let speaker = match msg.role {
+                    MessageRole::User => "You",
+                    MessageRole::Assistant => "Yollayah",
+                    MessageRole::System => "System",
+                };
+                let content = CommandParser::strip_commands(&msg.content);
+                let content = content.trim();
+                (!content.is_empty()).then(|| match format {
+                    ExportFormat::Markdown => format!("## {speaker}\n\n{content}"),
+                    ExportFormat::Text | ExportFormat::OpenAi => format!("{speaker}: {content}"),
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Write the session (history, metadata, and state) to `path` as JSON
+    ///
+    /// The file is replaced atomically. Nothing is redacted; use
+    /// [`Session::save_to_path_with`] to mask secrets on the way out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        self.save_to_path_with(path, &Redactor::default())
+    }
+
+    /// Write the session to `path` as JSON, masking text matching `redactor`
+    ///
+    /// Goes through [`FileSessionStore::save_to_path`], as the Conductor's
+    /// own persistence does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn save_to_path_with(&self, path: &Path, redactor: &Redactor) -> io::Result<()> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        FileSessionStore::new(dir)
+            .with_redactor(redactor.clone())
+            .save_to_path(self, path)
+            .map_err(Into::into)
+    }
+
+    /// Read a session written by [`Session::save_to_path`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or doesn't hold a valid
+    /// session.
+    pub fn load_from_path(path: &Path) -> io::Result<Self> {
+        FileSessionStore::load_from_path(path).map_err(Into::into)
+    }
+
     /// Pause the session
     pub fn pause(&mut self) {
         if self.state == SessionState::Active {
@@ -346,6 +519,126 @@ impl Session {
         self.state = SessionState::Active;
     }
 
+    /// Drop the response to the latest user turn so the turn can be re-run
+    ///
+    /// `message_id` may be the user message itself or anything after it
+    /// (the assistant response, or the error a failed one left). Returns
+    /// the user's message and the removed IDs, oldest first, or None if
+    /// `message_id` isn't in the latest turn.
+    pub fn rewind_last_turn(&mut self, message_id: &MessageId) -> Option<(String, Vec<MessageId>)> {
+        let position = self.messages.iter().position(|m| &m.id == message_id)?;
+        let user = self
+            .messages
+            .iter()
+            .rposition(|m| m.role == MessageRole::User)?;
+        if position < user {
+            return None;
+        }
+
+        let removed = self
+            .messages
+            .drain(user + 1..)
+            .map(|msg| {
+                self.current_content_bytes =
+                    self.current_content_bytes.saturating_sub(msg.content.len());
+                msg.id
+            })
+            .collect();
+        self.current_streaming_id = None;
+        self.state = SessionState::Active;
+        Some((self.messages[user].content.clone(), removed))
+    }
+
+    /// Remove `message_id` and every message after it
+    ///
+    /// A response still streaming is cancelled along with them. Returns
+    /// the removed IDs, oldest first (empty if `message_id` isn't here).
+    pub fn truncate_from(&mut self, message_id: &MessageId) -> Vec<MessageId> {
+        let Some(position) = self.messages.iter().position(|m| &m.id == message_id) else {
+            return Vec::new();
+        };
+
+        let removed = self
+            .messages
+            .drain(position..)
+            .map(|msg| {
+                self.current_content_bytes =
+                    self.current_content_bytes.saturating_sub(msg.content.len());
+                msg.id
+            })
+            .collect();
+        self.current_streaming_id = None;
+        self.state = SessionState::Active;
+        removed
+    }
+
+    /// Replace a user message's text, dropping every message after it
+    ///
+    /// Returns the old text's length in bytes and the removed IDs, oldest
+    /// first, or None if `message_id` isn't a user message here.
+    pub fn edit_user_message(
+        &mut self,
+        message_id: &MessageId,
+        content: String,
+    ) -> Option<(usize, Vec<MessageId>)> {
+        let position = self
+            .messages
+            .iter()
+            .position(|m| &m.id == message_id && m.role == MessageRole::User)?;
+        let removed = match self.messages.get(position + 1) {
+            Some(next) => {
+                let next = next.id.clone();
+                self.truncate_from(&next)
+            }
+            None => Vec::new(),
+        };
+
+        let msg = &mut self.messages[position];
+        let old_len = msg.content.len();
+        self.current_content_bytes =
+            self.current_content_bytes.saturating_sub(old_len) + content.len();
+        msg.content = content;
+        Some((old_len, removed))
+    }
+
+    /// Merge messages from another session (e.g. a branch) into this one
+    ///
+    /// Messages whose id is already present are skipped, as are messages
+    /// still streaming in `other`. Merged messages are appended in their
+    /// original order and re-timestamped after the latest message here, so
+    /// the history stays chronological. Returns the number of messages merged.
+    pub fn merge_from(&mut self, other: &Session, strategy: MergeStrategy) -> usize {
+        let mut seen: HashSet<MessageId> = self.messages.iter().map(|m| m.id.clone()).collect();
+        let last_timestamp = self.messages.iter().map(|m| m.timestamp).max().unwrap_or(0);
+        let mut next_timestamp = now_ms().max(last_timestamp + 1);
+        let mut merged = 0;
+
+        for msg in &other.messages {
+            if msg.streaming || seen.contains(&msg.id) {
+                continue;
+            }
+            if strategy == MergeStrategy::AppendAssistantOnly && msg.role != MessageRole::Assistant
+            {
+                continue;
+            }
+
+            let mut copy = msg.clone();
+            copy.timestamp = next_timestamp;
+            next_timestamp += 1;
+
+            seen.insert(copy.id.clone());
+            self.current_content_bytes += copy.content.len();
+            self.messages.push(copy);
+            self.metadata.add_message();
+            merged += 1;
+        }
+
+        if merged > 0 {
+            self.prune_if_needed();
+        }
+        merged
+    }
+
     /// Prune messages if limits are exceeded
     ///
     /// This is called automatically after adding messages.
@@ -457,6 +750,11 @@ fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
+/// One `{role, content}` message
+fn openai_message(role: &str, content: &str) -> serde_json::Value {
+    serde_json::json!({ "role": role, "content": content })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,4 +902,271 @@ mod tests {
 
         assert_eq!(session.content_bytes(), 12);
     }
+
+    /// Two sessions sharing a common prefix, as after branching
+    fn branched_sessions() -> (Session, Session) {
+        let mut main = Session::new("test".to_string());
+        main.add_user_message("Hi".to_string());
+        main.start_assistant_response();
+        main.append_streaming("Hello!");
+        main.complete_streaming();
+
+        let mut branch = main.clone();
+        branch.id = SessionId::new();
+        branch.add_user_message("Tell me a joke".to_string());
+        branch.start_assistant_response();
+        branch.append_streaming("Why did the llama cross the road?");
+        branch.complete_streaming();
+
+        main.add_user_message("What's the weather?".to_string());
+        (main, branch)
+    }
+
+    fn assert_unique_and_ordered(session: &Session) {
+        let ids: HashSet<_> = session.messages.iter().map(|m| m.id.clone()).collect();
+        assert_eq!(ids.len(), session.messages.len(), "duplicate message ids");
+        assert!(session
+            .messages
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    }
+
+    #[test]
+    fn test_merge_append_all() {
+        let (mut main, branch) = branched_sessions();
+
+        let merged = main.merge_from(&branch, MergeStrategy::AppendAll);
+
+        assert_eq!(merged, 2);
+        let contents: Vec<_> = main.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            [
+                "Hi",
+                "Hello!",
+                "What's the weather?",
+                "Tell me a joke",
+                "Why did the llama cross the road?",
+            ]
+        );
+        assert_unique_and_ordered(&main);
+        assert!(main.messages[3].timestamp > main.messages[2].timestamp);
+        assert!(main.messages[4].timestamp > main.messages[3].timestamp);
+        assert_eq!(main.metadata.message_count, 5);
+    }
+
+    #[test]
+    fn test_merge_append_assistant_only() {
+        let (mut main, branch) = branched_sessions();
+
+        let merged = main.merge_from(&branch, MergeStrategy::AppendAssistantOnly);
+
+        assert_eq!(merged, 1);
+        let last = main.messages.last().unwrap();
+        assert_eq!(last.role, MessageRole::Assistant);
+        assert_eq!(last.content, "Why did the llama cross the road?");
+        assert_eq!(main.messages.len(), 4);
+        assert_unique_and_ordered(&main);
+    }
+
+    #[test]
+    fn test_merge_is_idempotent() {
+        let (mut main, branch) = branched_sessions();
+        main.merge_from(&branch, MergeStrategy::AppendAll);
+        let bytes = main.content_bytes();
+
+        assert_eq!(main.merge_from(&branch, MergeStrategy::AppendAll), 0);
+        assert_eq!(main.messages.len(), 5);
+        assert_eq!(main.content_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_set_streaming_content_replaces_drifted_text() {
+        let mut session = Session::new_with_limits("test".to_string(), 1000, 10000);
+
+        session.start_assistant_response();
+        session.append_streaming("[yolla:wa");
+        session.append_streaming("Hi");
+        session.set_streaming_content("Hi".to_string());
+        session.complete_streaming();
+
+        assert_eq!(session.messages.last().unwrap().content, "Hi");
+        assert_eq!(session.content_bytes(), 2);
+    }
+
+    #[test]
+    fn test_rewind_last_turn() {
+        let mut session = Session::new("test".to_string());
+        session.add_user_message("First".to_string());
+        session.start_assistant_response();
+        session.append_streaming("One");
+        let first_answer = session.complete_streaming().unwrap().id.clone();
+        let question = session.add_user_message("Second".to_string());
+        session.start_assistant_response();
+        session.append_streaming("Two");
+        let answer = session.complete_streaming().unwrap().id.clone();
+        let bytes = session.content_bytes();
+
+        // Only the latest turn can be rewound
+        assert_eq!(session.rewind_last_turn(&first_answer), None);
+        assert_eq!(session.rewind_last_turn(&MessageId::new()), None);
+        assert_eq!(session.messages.len(), 4);
+
+        assert_eq!(
+            session.rewind_last_turn(&answer),
+            Some(("Second".to_string(), vec![answer]))
+        );
+        assert_eq!(session.messages.len(), 3);
+        assert_eq!(session.messages.last().unwrap().id, question);
+        assert_eq!(session.content_bytes(), bytes - "Two".len());
+
+        // A failed turn is rewound from its user message too
+        let error = session.add_system_message("Error: backend down".to_string());
+        assert_eq!(
+            session.rewind_last_turn(&question),
+            Some(("Second".to_string(), vec![error]))
+        );
+        assert_eq!(session.messages.len(), 3);
+    }
+
+    #[test]
+    fn test_interrupt_streaming_keeps_partial_content() {
+        let mut session = Session::new("test".to_string());
+        assert!(session.interrupt_streaming().is_none());
+
+        session.add_user_message("Tell me a story".to_string());
+        session.start_assistant_response();
+        session.append_streaming("Once upon");
+        let msg = session.interrupt_streaming().unwrap();
+        assert_eq!(msg.content, "Once upon");
+        assert!(msg.cancelled);
+        assert!(!msg.streaming);
+        assert!(!session.is_streaming());
+        assert_eq!(session.state, SessionState::Active);
+        assert_eq!(session.message_count(), 2);
+    }
+
+    #[test]
+    fn test_truncate_from() {
+        let mut session = Session::new("test".to_string());
+        session.add_user_message("First".to_string());
+        let question = session.add_user_message("Second".to_string());
+        let answer = session.start_assistant_response();
+        session.append_streaming("Tw");
+
+        assert!(session.truncate_from(&MessageId::new()).is_empty());
+        assert_eq!(session.truncate_from(&question), [question, answer]);
+        assert_eq!(session.messages.len(), 1);
+        assert!(!session.is_streaming());
+        assert_eq!(session.state, SessionState::Active);
+        assert_eq!(session.content_bytes(), "First".len());
+    }
+
+    #[test]
+    fn test_edit_user_message() {
+        let mut session = Session::new("test".to_string());
+        let first = session.add_user_message("First".to_string());
+        let answer = session.start_assistant_response();
+        session.append_streaming("One");
+        session.complete_streaming();
+        let second = session.add_user_message("Second".to_string());
+
+        assert_eq!(session.edit_user_message(&answer, "No".to_string()), None);
+        assert_eq!(
+            session.edit_user_message(&first, "Primero".to_string()),
+            Some(("First".len(), vec![answer, second]))
+        );
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.messages[0].content, "Primero");
+        assert_eq!(session.content_bytes(), "Primero".len());
+    }
+
+    #[test]
+    fn test_to_openai_messages() {
+        #[derive(Deserialize)]
+        struct OpenAiMessage {
+            role: String,
+            content: String,
+        }
+
+        let mut session = Session::new("test".to_string());
+        session.system_prompt = Some("Be kind".to_string());
+        session.add_user_message("Hi".to_string());
+        session.start_assistant_response();
+        session.append_streaming("[yolla:wave] Hello! [yolla:mood happy]How are you?");
+        session.complete_streaming();
+        session.add_system_message("Model set to: alternate".to_string());
+        session.start_assistant_response();
+        session.append_streaming("[yolla:dance]");
+        session.complete_streaming();
+        session.add_user_message("Good".to_string());
+        session.start_assistant_response();
+        session.append_streaming("Glad");
+
+        let exported = serde_json::Value::Array(session.to_openai_messages());
+        let messages: Vec<OpenAiMessage> = serde_json::from_value(exported).unwrap();
+        let pairs: Vec<_> = messages
+            .iter()
+            .map(|m| (m.role.as_str(), m.content.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("system", "Be kind"),
+                ("user", "Hi"),
+                ("assistant", "Hello! How are you?"),
+                ("system", "Model set to: alternate"),
+                ("user", "Good"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        let mut session = Session::new("test-model".to_string());
+        session.add_user_message("Hello".to_string());
+        session.start_assistant_response();
+        session.append_streaming("Hi there");
+        session.complete_streaming();
+        session.end();
+        session.save_to_path(&path).unwrap();
+
+        let loaded = Session::load_from_path(&path).unwrap();
+        assert_eq!(loaded.id, session.id);
+        assert_eq!(loaded.state, SessionState::Ended);
+        assert_eq!(loaded.metadata.model, "test-model");
+        assert_eq!(
+            loaded.metadata.message_count,
+            session.metadata.message_count
+        );
+        let contents: Vec<_> = loaded.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["Hello", "Hi there"]);
+
+        // A corrupt file is an error, not a panic
+        std::fs::write(&path, "{not json").unwrap();
+        let err = Session::load_from_path(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_save_to_path_with_redacts() {
+        use crate::redaction::{RedactionRule, API_KEY_MASK};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let mut session = Session::new("test-model".to_string());
+        session.add_user_message("My key is sk-abcdefghijklmnopqrstuvwx".to_string());
+
+        let redactor = Redactor::new(vec![RedactionRule::ApiKeys]);
+        session.save_to_path_with(&path, &redactor).unwrap();
+
+        let loaded = Session::load_from_path(&path).unwrap();
+        assert_eq!(
+            loaded.messages[0].content,
+            format!("My key is {API_KEY_MASK}")
+        );
+    }
 }
@@ -0,0 +1,434 @@
+//! Session Persistence
+//!
+//! Sessions are saved through a [`SessionStore`] so the Conductor doesn't
+//! care where they end up. [`FileSessionStore`] writes one JSON file per
+//! session and is what the Conductor uses unless another store (a database,
+//! say) is injected with `Conductor::with_session_store`.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::messages::SessionId;
+use crate::redaction::Redactor;
+use crate::session::Session;
+
+/// Errors from a session store
+#[derive(Debug, Error)]
+pub enum SessionStoreError {
+    /// Reading or writing the underlying storage failed
+    #[error("Session storage I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A session couldn't be encoded or decoded
+    #[error("Failed to (de)serialize session: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// The session ID can't be used as a storage key
+    #[error("Invalid session ID: {0}")]
+    InvalidId(String),
+}
+
+impl From<SessionStoreError> for std::io::Error {
+    fn from(error: SessionStoreError) -> Self {
+        match error {
+            SessionStoreError::Io(e) => e,
+            SessionStoreError::Serialization(e) => e.into(),
+            SessionStoreError::InvalidId(_) => {
+                Self::new(std::io::ErrorKind::InvalidInput, error.to_string())
+            }
+        }
+    }
+}
+
+/// Where sessions are persisted
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Save a session, replacing any earlier copy with the same ID
+    async fn save(&self, session: &Session) -> Result<(), SessionStoreError>;
+
+    /// Load a session (None if it was never saved or has been deleted)
+    async fn load(&self, id: &SessionId) -> Result<Option<Session>, SessionStoreError>;
+
+    /// IDs of all saved sessions, sorted
+    async fn list(&self) -> Result<Vec<SessionId>, SessionStoreError>;
+
+    /// Delete a session, returning whether it existed
+    async fn delete(&self, id: &SessionId) -> Result<bool, SessionStoreError>;
+
+    /// ID of the most recently saved session (None if there are none)
+    async fn latest(&self) -> Result<Option<SessionId>, SessionStoreError>;
+}
+
+/// Stores each session as `<dir>/<session id>.json`
+///
+/// With a [`Redactor`], files hold a masked copy of each session; loading
+/// one gives back the masked text.
+#[derive(Clone, Debug)]
+pub struct FileSessionStore {
+    dir: PathBuf,
+    redactor: Redactor,
+}
+
+impl FileSessionStore {
+    /// Store sessions in the given directory (created on first save)
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            redactor: Redactor::default(),
+        }
+    }
+
+    /// Mask what `redactor` matches in the files written
+    #[must_use]
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// Default session directory (`~/.local/share/ai-way/sessions` on Linux)
+    #[must_use]
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|p| p.join("ai-way").join("sessions"))
+    }
+
+    /// Directory sessions are stored in
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File a session is stored in
+    fn path_for(&self, id: &SessionId) -> Result<PathBuf, SessionStoreError> {
+        let valid = !id.0.is_empty()
+            && id
+                .0
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(SessionStoreError::InvalidId(id.0.clone()));
+        }
+        Ok(self.dir.join(format!("{}.json", id.0)))
+    }
+
+    /// Write `session` to `path`, masked by this store's redactor
+    ///
+    /// The file is written then renamed into place, so a crash never
+    /// leaves a half-written session behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session can't be encoded or written.
+    pub fn save_to_path(&self, session: &Session, path: &Path) -> Result<(), SessionStoreError> {
+        let json = if self.redactor.is_empty() {
+            serde_json::to_vec_pretty(session)?
+        } else {
+            serde_json::to_vec_pretty(&self.redactor.redact_session(session))?
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a session file written by [`FileSessionStore::save_to_path`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or doesn't hold a valid
+    /// session.
+    pub fn load_from_path(path: &Path) -> Result<Session, SessionStoreError> {
+        let json = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn save(&self, session: &Session) -> Result<(), SessionStoreError> {
+        let path = self.path_for(&session.id)?;
+        let store = self.clone();
+        let session = session.clone();
+        tokio::task::spawn_blocking(move || store.save_to_path(&session, &path))
+            .await
+            .map_err(std::io::Error::other)?
+    }
+
+    async fn load(&self, id: &SessionId) -> Result<Option<Session>, SessionStoreError> {
+        let path = self.path_for(id)?;
+        let loaded = tokio::task::spawn_blocking(move || Self::load_from_path(&path))
+            .await
+            .map_err(std::io::Error::other)?;
+        match loaded {
+            Ok(session) => Ok(Some(session)),
+            Err(SessionStoreError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<SessionId>, SessionStoreError> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut ids = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    ids.push(SessionId(stem.to_string()));
+                }
+            }
+        }
+        ids.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(ids)
+    }
+
+    async fn delete(&self, id: &SessionId) -> Result<bool, SessionStoreError> {
+        let path = self.path_for(id)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Goes by file modification time, so only the newest file is read
+    async fn latest(&self) -> Result<Option<SessionId>, SessionStoreError> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut latest = None;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let modified = entry.metadata().await?.modified()?;
+            if latest.as_ref().is_none_or(|(newest, _)| modified > *newest) {
+                latest = Some((modified, SessionId(stem.to_string())));
+            }
+        }
+        Ok(latest.map(|(_, id)| id))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Keeps sessions in memory, as JSON to catch serialization problems
+    #[derive(Default)]
+    pub(crate) struct MemorySessionStore {
+        sessions: Mutex<HashMap<SessionId, String>>,
+    }
+
+    #[async_trait]
+    impl SessionStore for MemorySessionStore {
+        async fn save(&self, session: &Session) -> Result<(), SessionStoreError> {
+            let json = serde_json::to_string(session)?;
+            self.sessions
+                .lock()
+                .unwrap()
+                .insert(session.id.clone(), json);
+            Ok(())
+        }
+
+        async fn load(&self, id: &SessionId) -> Result<Option<Session>, SessionStoreError> {
+            let sessions = self.sessions.lock().unwrap();
+            match sessions.get(id) {
+                Some(json) => Ok(Some(serde_json::from_str(json)?)),
+                None => Ok(None),
+            }
+        }
+
+        async fn list(&self) -> Result<Vec<SessionId>, SessionStoreError> {
+            let mut ids: Vec<_> = self.sessions.lock().unwrap().keys().cloned().collect();
+            ids.sort_by(|a, b| a.0.cmp(&b.0));
+            Ok(ids)
+        }
+
+        async fn delete(&self, id: &SessionId) -> Result<bool, SessionStoreError> {
+            Ok(self.sessions.lock().unwrap().remove(id).is_some())
+        }
+
+        async fn latest(&self) -> Result<Option<SessionId>, SessionStoreError> {
+            let sessions = self.sessions.lock().unwrap();
+            let mut latest: Option<Session> = None;
+            for json in sessions.values() {
+                let session: Session = serde_json::from_str(json)?;
+                if latest
+                    .as_ref()
+                    .is_none_or(|l| session.metadata.last_active_at > l.metadata.last_active_at)
+                {
+                    latest = Some(session);
+                }
+            }
+            Ok(latest.map(|session| session.id))
+        }
+    }
+
+    /// Save/load/list/delete semantics every store should share
+    async fn check_store_semantics(store: &dyn SessionStore) {
+        let mut first = Session::with_id(SessionId("session_a".to_string()), "m".to_string());
+        first.add_user_message("Hello".to_string());
+        let second = Session::with_id(SessionId("session_b".to_string()), "m".to_string());
+
+        assert!(store.list().await.unwrap().is_empty());
+        assert!(store.load(&first.id).await.unwrap().is_none());
+
+        store.save(&second).await.unwrap();
+        store.save(&first).await.unwrap();
+        assert_eq!(
+            store.list().await.unwrap(),
+            vec![first.id.clone(), second.id.clone()]
+        );
+
+        let loaded = store.load(&first.id).await.unwrap().unwrap();
+        assert_eq!(loaded.message_count(), 1);
+        assert_eq!(loaded.all_messages()[0].content, "Hello");
+
+        // Saving again replaces the stored copy
+        first.add_user_message("Again".to_string());
+        store.save(&first).await.unwrap();
+        let loaded = store.load(&first.id).await.unwrap().unwrap();
+        assert_eq!(loaded.message_count(), 2);
+        assert_eq!(store.list().await.unwrap().len(), 2);
+
+        assert!(store.delete(&first.id).await.unwrap());
+        assert!(!store.delete(&first.id).await.unwrap());
+        assert!(store.load(&first.id).await.unwrap().is_none());
+        assert_eq!(store.list().await.unwrap(), vec![second.id.clone()]);
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_semantics() {
+        check_store_semantics(&MemorySessionStore::default()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store_semantics() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::new(dir.path().join("sessions"));
+        check_store_semantics(&store).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store_redacts_at_rest() {
+        use crate::redaction::{RedactionRule, EMAIL_MASK};
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::new(dir.path())
+            .with_redactor(Redactor::new(vec![RedactionRule::Emails]));
+        let mut session = Session::with_id(SessionId("private".to_string()), "m".to_string());
+        session.add_user_message("Write to ana@example.com".to_string());
+
+        store.save(&session).await.unwrap();
+
+        let on_disk = std::fs::read_to_string(dir.path().join("private.json")).unwrap();
+        assert!(!on_disk.contains("ana@example.com"));
+        assert!(on_disk.contains(EMAIL_MASK));
+        // The session in memory keeps the original
+        assert_eq!(
+            session.all_messages()[0].content,
+            "Write to ana@example.com"
+        );
+        // Loading gives back the masked text
+        let loaded = store.load(&session.id).await.unwrap().unwrap();
+        assert_eq!(
+            loaded.all_messages()[0].content,
+            format!("Write to {EMAIL_MASK}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_store_latest_goes_by_modification_time() {
+        use std::time::{Duration, SystemTime};
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::new(dir.path());
+        assert_eq!(store.latest().await.unwrap(), None);
+
+        let older = Session::with_id(SessionId("older".to_string()), "m".to_string());
+        let newer = Session::with_id(SessionId("newer".to_string()), "m".to_string());
+        store.save(&newer).await.unwrap();
+        store.save(&older).await.unwrap();
+        // A leftover temp file isn't a session
+        std::fs::write(dir.path().join("stray.json.tmp"), "").unwrap();
+
+        let set_modified = |name: &str, secs_ago: u64| {
+            let file = std::fs::File::options()
+                .write(true)
+                .open(dir.path().join(name))
+                .unwrap();
+            file.set_modified(SystemTime::now() - Duration::from_secs(secs_ago))
+                .unwrap();
+        };
+        set_modified("older.json", 60);
+        set_modified("newer.json", 30);
+        set_modified("stray.json.tmp", 0);
+
+        assert_eq!(
+            store.latest().await.unwrap(),
+            Some(SessionId("newer".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_save_to_path_redacts_and_leaves_no_temp_file() {
+        use crate::redaction::{RedactionRule, API_KEY_MASK};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("session.json");
+        let store = FileSessionStore::new(dir.path())
+            .with_redactor(Redactor::new(vec![RedactionRule::ApiKeys]));
+        let mut session = Session::new("m".to_string());
+        session.add_user_message("My key is sk-abcdefghijklmnopqrstuvwx".to_string());
+
+        store.save_to_path(&session, &path).unwrap();
+
+        let loaded = FileSessionStore::load_from_path(&path).unwrap();
+        assert_eq!(loaded.id, session.id);
+        assert_eq!(
+            loaded.all_messages()[0].content,
+            format!("My key is {API_KEY_MASK}")
+        );
+        let files: Vec<_> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["session.json"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_rejects_path_like_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileSessionStore::new(dir.path());
+        let id = SessionId("../escape".to_string());
+
+        assert!(matches!(
+            store.load(&id).await,
+            Err(SessionStoreError::InvalidId(_))
+        ));
+        assert!(matches!(
+            store.delete(&id).await,
+            Err(SessionStoreError::InvalidId(_))
+        ));
+    }
+}
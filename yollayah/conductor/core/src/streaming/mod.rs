@@ -56,8 +56,12 @@
 //! }
 //! ```
 
+mod pacing;
+mod reasoning;
 mod stream_manager;
 
+pub use pacing::{TokenPacer, DEFAULT_MAX_PACING_MS};
+pub use reasoning::{ReasoningPart, ReasoningSplitter, THINK_CLOSE, THINK_OPEN};
 pub use stream_manager::{
     BufferOverflowPolicy, ConversationStream, StreamEvent, StreamEventKind, StreamManager,
     StreamManagerConfig, StreamStats,
@@ -0,0 +1,228 @@
+//! Token Pacing
+//!
+//! Surfaces render at different speeds. Each reports `RenderComplete` after
+//! drawing a frame; [`TokenPacer`] learns how often that happens and holds a
+//! surface's tokens until it's due for another frame, then releases them
+//! together. A fast terminal gets tokens almost as they arrive, a slow one
+//! gets them in batches it can keep up with, and a surface that never
+//! reports renders isn't paced at all.
+
+use std::collections::HashMap;
+
+use crate::messages::ConductorMessage;
+use crate::surface_registry::ConnectionId;
+
+/// Longest a surface's tokens are held by default, in milliseconds
+pub const DEFAULT_MAX_PACING_MS: u64 = 250;
+
+/// What the pacer knows about one surface
+#[derive(Clone, Debug, Default)]
+struct SurfacePace {
+    /// When the surface last finished rendering (clock ms)
+    last_render_ms: Option<u64>,
+    /// Smoothed time between renders in ms (0 = not known yet)
+    interval_ms: u64,
+    /// When held tokens were last released (clock ms)
+    last_release_ms: Option<u64>,
+    /// Tokens waiting for the surface's next frame, oldest first
+    held: Vec<ConductorMessage>,
+}
+
+/// Paces tokens to each surface's observed render cadence
+#[derive(Clone, Debug)]
+pub struct TokenPacer {
+    /// Longest a surface's tokens are held (0 = never hold)
+    max_interval_ms: u64,
+    surfaces: HashMap<ConnectionId, SurfacePace>,
+}
+
+impl TokenPacer {
+    /// Hold tokens for at most `max_interval_ms` (0 = pacing off)
+    #[must_use]
+    pub fn new(max_interval_ms: u64) -> Self {
+        Self {
+            max_interval_ms,
+            surfaces: HashMap::new(),
+        }
+    }
+
+    /// Note that `surface` finished rendering a frame at `now_ms`
+    ///
+    /// Each gap moves the surface's interval a quarter of the way toward
+    /// it, so one slow frame doesn't throttle a fast terminal.
+    pub fn observe_render(&mut self, surface: ConnectionId, now_ms: u64) {
+        let pace = self.surfaces.entry(surface).or_default();
+        if let Some(last) = pace.last_render_ms.replace(now_ms) {
+            let gap = now_ms.saturating_sub(last).min(self.max_interval_ms);
+            pace.interval_ms = if pace.interval_ms == 0 {
+                gap
+            } else {
+                (pace.interval_ms * 3 + gap) / 4
+            };
+        }
+    }
+
+    /// How long `surface`'s tokens are currently held, in ms
+    #[must_use]
+    pub fn interval_ms(&self, surface: &ConnectionId) -> u64 {
+        self.surfaces
+            .get(surface)
+            .map_or(0, |pace| pace.interval_ms.min(self.max_interval_ms))
+    }
+
+    /// Offer `surface` a token at `now_ms`
+    ///
+    /// Returns what to send it now: nothing while its frame isn't due,
+    /// otherwise everything held for it followed by `token`.
+    pub fn offer(
+        &mut self,
+        surface: ConnectionId,
+        token: ConductorMessage,
+        now_ms: u64,
+    ) -> Vec<ConductorMessage> {
+        let interval_ms = self.interval_ms(&surface);
+        if interval_ms == 0 {
+            return vec![token];
+        }
+        let pace = self.surfaces.entry(surface).or_default();
+        pace.held.push(token);
+        if Self::is_due(pace, interval_ms, now_ms) {
+            pace.last_release_ms = Some(now_ms);
+            std::mem::take(&mut pace.held)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Release held tokens for every surface whose frame is due at `now_ms`
+    pub fn release_due(&mut self, now_ms: u64) -> Vec<(ConnectionId, Vec<ConductorMessage>)> {
+        let max = self.max_interval_ms;
+        self.surfaces
+            .iter_mut()
+            .filter(|(_, pace)| {
+                !pace.held.is_empty() && Self::is_due(pace, pace.interval_ms.min(max), now_ms)
+            })
+            .map(|(id, pace)| {
+                pace.last_release_ms = Some(now_ms);
+                (*id, std::mem::take(&mut pace.held))
+            })
+            .collect()
+    }
+
+    /// Release everything held, due or not
+    ///
+    /// Call before sending anything that must come after the tokens (such
+    /// as the end of the stream).
+    pub fn release_all(&mut self) -> Vec<(ConnectionId, Vec<ConductorMessage>)> {
+        self.surfaces
+            .iter_mut()
+            .filter(|(_, pace)| !pace.held.is_empty())
+            .map(|(id, pace)| (*id, std::mem::take(&mut pace.held)))
+            .collect()
+    }
+
+    /// Drop a surface that went away, along with its held tokens
+    pub fn forget(&mut self, surface: &ConnectionId) {
+        self.surfaces.remove(surface);
+    }
+
+    fn is_due(pace: &SurfacePace, interval_ms: u64, now_ms: u64) -> bool {
+        pace.last_release_ms
+            .is_none_or(|last| now_ms.saturating_sub(last) >= interval_ms)
+    }
+}
+
+impl Default for TokenPacer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PACING_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::MessageId;
+
+    fn token(seq: u64) -> ConductorMessage {
+        ConductorMessage::Token {
+            message_id: MessageId("m".to_string()),
+            text: "x".to_string(),
+            seq,
+        }
+    }
+
+    fn seqs(messages: &[ConductorMessage]) -> Vec<u64> {
+        messages
+            .iter()
+            .filter_map(|m| match m {
+                ConductorMessage::Token { seq, .. } => Some(*seq),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Report renders every `every_ms` from 0 through `until_ms`
+    fn renders(pacer: &mut TokenPacer, surface: ConnectionId, every_ms: u64, until_ms: u64) {
+        for now in (0..=until_ms).step_by(every_ms as usize) {
+            pacer.observe_render(surface, now);
+        }
+    }
+
+    #[test]
+    fn test_interval_follows_render_cadence_up_to_ceiling() {
+        let mut pacer = TokenPacer::new(100);
+        let fast = ConnectionId::new();
+        let slow = ConnectionId::new();
+        let silent = ConnectionId::new();
+        renders(&mut pacer, fast, 16, 320);
+        renders(&mut pacer, slow, 500, 2000);
+
+        assert_eq!(pacer.interval_ms(&fast), 16);
+        assert_eq!(pacer.interval_ms(&slow), 100);
+        assert_eq!(pacer.interval_ms(&silent), 0);
+
+        // One slow frame nudges the interval rather than resetting it
+        pacer.observe_render(fast, 320 + 80);
+        assert_eq!(pacer.interval_ms(&fast), 32);
+    }
+
+    #[test]
+    fn test_tokens_held_until_frame_due() {
+        let mut pacer = TokenPacer::new(1000);
+        let surface = ConnectionId::new();
+        renders(&mut pacer, surface, 100, 400);
+
+        assert_eq!(seqs(&pacer.offer(surface, token(1), 1000)), [1]);
+        assert!(pacer.offer(surface, token(2), 1030).is_empty());
+        assert!(pacer.offer(surface, token(3), 1060).is_empty());
+        assert!(pacer.release_due(1090).is_empty());
+
+        let released = pacer.release_due(1100);
+        assert_eq!(released.len(), 1);
+        assert_eq!(seqs(&released[0].1), [2, 3]);
+
+        // Held tokens come out ahead of the one that released them
+        assert!(pacer.offer(surface, token(4), 1150).is_empty());
+        assert_eq!(seqs(&pacer.offer(surface, token(5), 1200)), [4, 5]);
+    }
+
+    #[test]
+    fn test_release_all_and_off() {
+        let mut pacer = TokenPacer::new(1000);
+        let surface = ConnectionId::new();
+        renders(&mut pacer, surface, 100, 400);
+        pacer.offer(surface, token(1), 1000);
+        pacer.offer(surface, token(2), 1010);
+
+        let released = pacer.release_all();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].0, surface);
+        assert_eq!(seqs(&released[0].1), [2]);
+        assert!(pacer.release_all().is_empty());
+
+        let mut off = TokenPacer::new(0);
+        renders(&mut off, surface, 100, 400);
+        assert_eq!(seqs(&off.offer(surface, token(1), 1000)), [1]);
+        assert_eq!(seqs(&off.offer(surface, token(2), 1001)), [2]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Reasoning Split
+//!
+//! Reasoning models wrap their thinking in `<think>...</think>` before the
+//! answer. [`ReasoningSplitter`] separates the two as tokens arrive, so the
+//! thinking can stream to its own pane while the answer streams cleanly. A
+//! tag may be split across tokens; the splitter holds back a trailing
+//! partial tag until the next token shows whether it completes.
+
+/// Opens a block of reasoning
+pub const THINK_OPEN: &str = "<think>";
+
+/// Closes a block of reasoning
+pub const THINK_CLOSE: &str = "</think>";
+
+/// A piece of streamed text, routed by channel
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReasoningPart {
+    /// Text that belongs to the answer
+    Answer(String),
+    /// Text from inside a `<think>` block
+    Reasoning(String),
+}
+
+/// Splits streamed text into answer and reasoning
+#[derive(Clone, Debug, Default)]
+pub struct ReasoningSplitter {
+    /// Inside a `<think>` block
+    thinking: bool,
+    /// Trailing text that may be the start of a tag
+    carry: String,
+}
+
+impl ReasoningSplitter {
+    /// Route the next token's text, in order and leaving out empty parts
+    pub fn push(&mut self, token: &str) -> Vec<ReasoningPart> {
+        let mut text = std::mem::take(&mut self.carry);
+        text.push_str(token);
+
+        let mut parts = Vec::new();
+        let mut rest = text.as_str();
+        loop {
+            let tag = if self.thinking {
+                THINK_CLOSE
+            } else {
+                THINK_OPEN
+            };
+            if let Some(at) = rest.find(tag) {
+                parts.extend(self.part(&rest[..at]));
+                rest = &rest[at + tag.len()..];
+                self.thinking = !self.thinking;
+                continue;
+            }
+            let held = partial_tag_len(rest, tag);
+            parts.extend(self.part(&rest[..rest.len() - held]));
+            self.carry = rest[rest.len() - held..].to_string();
+            return parts;
+        }
+    }
+
+    /// Release anything held back once the stream ends
+    ///
+    /// A partial tag that never completed is ordinary text.
+    pub fn finish(&mut self) -> Option<ReasoningPart> {
+        let carry = std::mem::take(&mut self.carry);
+        let part = self.part(&carry);
+        self.thinking = false;
+        part
+    }
+
+    /// `text` with its reasoning removed (an unclosed block runs to the end)
+    #[must_use]
+    pub fn strip(text: &str) -> String {
+        let mut splitter = Self::default();
+        let mut parts = splitter.push(text);
+        parts.extend(splitter.finish());
+        parts
+            .into_iter()
+            .filter_map(|part| match part {
+                ReasoningPart::Answer(text) => Some(text),
+                ReasoningPart::Reasoning(_) => None,
+            })
+            .collect()
+    }
+
+    /// `text` for the current channel, unless it's empty
+    fn part(&self, text: &str) -> Option<ReasoningPart> {
+        if text.is_empty() {
+            None
+        } else if self.thinking {
+            Some(ReasoningPart::Reasoning(text.to_string()))
+        } else {
+            Some(ReasoningPart::Answer(text.to_string()))
+        }
+    }
+}
+
+/// Length of the longest suffix of `text` that starts `tag`
+fn partial_tag_len(text: &str, tag: &str) -> usize {
+    (1..tag.len())
+        .rev()
+        .find(|&len| text.ends_with(&tag[..len]))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ReasoningPart::{Answer, Reasoning};
+
+    fn answer(text: &str) -> ReasoningPart {
+        Answer(text.to_string())
+    }
+
+    fn reasoning(text: &str) -> ReasoningPart {
+        Reasoning(text.to_string())
+    }
+
+    #[test]
+    fn test_tags_split_across_tokens() {
+        let mut splitter = ReasoningSplitter::default();
+        let parts: Vec<_> = [
+            "<th",
+            "ink>The user",
+            " greets me.</",
+            "thi",
+            "nk>Hello",
+            " there!",
+        ]
+        .into_iter()
+        .flat_map(|token| splitter.push(token))
+        .collect();
+        assert_eq!(
+            parts,
+            [
+                reasoning("The user"),
+                reasoning(" greets me."),
+                answer("Hello"),
+                answer(" there!")
+            ]
+        );
+        assert_eq!(splitter.finish(), None);
+    }
+
+    #[test]
+    fn test_parts_released_as_they_arrive() {
+        let mut splitter = ReasoningSplitter::default();
+        assert_eq!(splitter.push("<think>Let me"), [reasoning("Let me")]);
+        // "</" might close the block, so it waits for the next token
+        assert_eq!(splitter.push(" see </"), [reasoning(" see ")]);
+        assert_eq!(splitter.push("b>"), [reasoning("</b>")]);
+        assert_eq!(
+            splitter.push("</think>Yes<think>ok</think>!<"),
+            [answer("Yes"), reasoning("ok"), answer("!")]
+        );
+        assert_eq!(splitter.finish(), Some(answer("<")));
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(
+            ReasoningSplitter::strip("<think>hmm</think>A<think>more</think>B"),
+            "AB"
+        );
+        assert_eq!(ReasoningSplitter::strip("Plain 1 < 2"), "Plain 1 < 2");
+        assert_eq!(ReasoningSplitter::strip("Answer<think>cut off"), "Answer");
+    }
+}
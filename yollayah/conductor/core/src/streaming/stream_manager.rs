@@ -292,7 +292,7 @@ impl ConversationStream {
                             events.push(overflow_event);
                         }
                     }
-                    StreamingToken::Complete { message } => {
+                    StreamingToken::Complete { message, .. } => {
                         self.completed = true;
                         self.content = message.clone();
 
@@ -703,6 +703,7 @@ mod tests {
             StreamingToken::Token("world!".to_string()),
             StreamingToken::Complete {
                 message: "Hello world!".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -796,6 +797,7 @@ mod tests {
             StreamingToken::Token("Stream 1".to_string()),
             StreamingToken::Complete {
                 message: "Stream 1".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -803,6 +805,7 @@ mod tests {
             StreamingToken::Token("Stream 2".to_string()),
             StreamingToken::Complete {
                 message: "Stream 2".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -888,6 +891,7 @@ mod tests {
             StreamingToken::Token("Test".to_string()),
             StreamingToken::Complete {
                 message: "Test".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -1058,6 +1062,7 @@ mod tests {
             StreamingToken::Token("AAAA".to_string()),
             StreamingToken::Complete {
                 message: "AAAAAAAA".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -1066,6 +1071,7 @@ mod tests {
             StreamingToken::Token("BBBB".to_string()),
             StreamingToken::Complete {
                 message: "BBBBBBB".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -1200,6 +1206,7 @@ mod tests {
             StreamingToken::Token("b".to_string()),
             StreamingToken::Complete {
                 message: "ab".to_string(),
+                finish_reason: None,
             },
         ];
 
@@ -43,6 +43,7 @@ use uuid::Uuid;
 
 use crate::events::{SurfaceCapabilities, SurfaceType};
 use crate::messages::ConductorMessage;
+use crate::transport::rate_limit::RateLimitError;
 
 /// Unique identifier for a client connection
 ///
@@ -241,6 +242,8 @@ impl BroadcastResult {
 pub struct SurfaceRegistry {
     /// Inner map of connection ID to surface handle
     inner: Arc<RwLock<HashMap<ConnectionId, SurfaceHandle>>>,
+    /// Most connections `try_register` accepts, whatever their UIDs
+    max_connections: Option<u32>,
 }
 
 impl Default for SurfaceRegistry {
@@ -255,12 +258,61 @@ impl SurfaceRegistry {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(HashMap::new())),
+            max_connections: None,
         }
     }
 
+    /// Create an empty registry that holds at most `max` connections
+    #[must_use]
+    pub fn with_max_connections(max: u32) -> Self {
+        Self {
+            max_connections: Some(max),
+            ..Self::new()
+        }
+    }
+
+    /// The most connections `try_register` accepts, if limited
+    #[must_use]
+    pub fn max_connections(&self) -> Option<u32> {
+        self.max_connections
+    }
+
+    /// Register a new surface connection, unless the registry is full
+    ///
+    /// The limit counts every connection regardless of UID, so it guards
+    /// the daemon against exhaustion even when per-UID limits allow more.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TooManyTotalConnections` once `max_connections` surfaces
+    /// are registered.
+    pub fn try_register(&self, handle: SurfaceHandle) -> Result<ConnectionId, RateLimitError> {
+        let mut inner = self.inner.write();
+        if let Some(max) = self.max_connections {
+            if inner.len() >= max as usize {
+                let current = u32::try_from(inner.len()).unwrap_or(u32::MAX);
+                tracing::warn!(
+                    connection_id = %handle.id,
+                    current,
+                    max,
+                    "Surface rejected: connection limit reached"
+                );
+                return Err(RateLimitError::TooManyTotalConnections { current, max });
+            }
+        }
+        let id = handle.id;
+        inner.insert(id, handle);
+        tracing::info!(
+            connection_id = %id,
+            "Surface registered"
+        );
+        Ok(id)
+    }
+
     /// Register a new surface connection
     ///
-    /// Returns the assigned `ConnectionId`.
+    /// Returns the assigned `ConnectionId`. Unlike `try_register`, this
+    /// ignores the connection limit.
     pub fn register(&self, handle: SurfaceHandle) -> ConnectionId {
         let id = handle.id;
         let mut inner = self.inner.write();
@@ -533,6 +585,14 @@ impl SurfaceRegistry {
         self.send_to_matching(message, |_, caps| required_caps(caps))
     }
 
+    /// Whether any connected surface has the given capabilities
+    pub fn any_capable(&self, required_caps: impl Fn(&SurfaceCapabilities) -> bool) -> bool {
+        self.inner
+            .read()
+            .values()
+            .any(|handle| required_caps(&handle.capabilities))
+    }
+
     /// Get a summary of all connected surfaces
     #[must_use]
     pub fn summary(&self) -> RegistrySummary {
@@ -596,6 +656,7 @@ impl fmt::Debug for SurfaceRegistry {
         let inner = self.inner.read();
         f.debug_struct("SurfaceRegistry")
             .field("connection_count", &inner.len())
+            .field("max_connections", &self.max_connections)
             .field("connections", &inner.keys().collect::<Vec<_>>())
             .finish()
     }
@@ -719,6 +780,50 @@ mod tests {
         assert!(!registry.contains(&id));
     }
 
+    #[test]
+    fn test_registry_connection_limit() {
+        let registry = SurfaceRegistry::with_max_connections(3);
+        let ids: Vec<_> = (0..3)
+            .map(|_| {
+                let (handle, _rx) = create_test_handle(ConnectionId::new());
+                registry.try_register(handle).unwrap()
+            })
+            .collect();
+        assert_eq!(registry.count(), 3);
+
+        // The limit is global: a fourth connection is refused with a reason
+        let (handle, _rx) = create_test_handle(ConnectionId::new());
+        let rejected = handle.id;
+        let err = registry.try_register(handle).unwrap_err();
+        assert_eq!(
+            err,
+            RateLimitError::TooManyTotalConnections { current: 3, max: 3 }
+        );
+        assert_eq!(
+            err.to_string(),
+            "3 connections are open across all UIDs (max: 3)"
+        );
+        assert!(!registry.contains(&rejected));
+
+        // Freeing a slot lets the next connection in
+        registry.unregister(&ids[0]);
+        let (handle, _rx) = create_test_handle(ConnectionId::new());
+        let id = registry.try_register(handle).unwrap();
+        assert!(registry.contains(&id));
+        assert_eq!(registry.count(), 3);
+    }
+
+    #[test]
+    fn test_registry_unlimited_by_default() {
+        let registry = SurfaceRegistry::new();
+        assert_eq!(registry.max_connections(), None);
+        for _ in 0..200 {
+            let (handle, _rx) = create_test_handle(ConnectionId::new());
+            registry.try_register(handle).unwrap();
+        }
+        assert_eq!(registry.count(), 200);
+    }
+
     #[test]
     fn test_registry_broadcast() {
         let registry = SurfaceRegistry::new();
@@ -133,6 +133,61 @@ impl std::fmt::Display for TaskStatus {
     }
 }
 
+/// Ordering for a surface's task panel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskSort {
+    /// Running first, then pending, then finished
+    #[default]
+    Status,
+    /// Most progress first
+    Progress,
+    /// Oldest task first
+    StartTime,
+}
+
+impl TaskSort {
+    /// The next sort order (for cycling with a keybinding)
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Status => Self::Progress,
+            Self::Progress => Self::StartTime,
+            Self::StartTime => Self::Status,
+        }
+    }
+
+    /// Human-readable label
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Progress => "progress",
+            Self::StartTime => "started",
+        }
+    }
+}
+
+/// Which tasks a surface's task panel shows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskFilter {
+    /// Show every task
+    All,
+    /// Hide tasks that are done, failed, or cancelled
+    #[default]
+    HideCompleted,
+}
+
+impl TaskFilter {
+    /// The other filter (for toggling with a keybinding)
+    #[must_use]
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::All => Self::HideCompleted,
+            Self::HideCompleted => Self::All,
+        }
+    }
+}
+
 /// A background task
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Task {
@@ -3,6 +3,7 @@
 //! Configuration types for selecting and configuring transport mechanisms.
 
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
@@ -74,8 +75,16 @@ pub struct TransportConfig {
     /// How many times to attempt reconnection on disconnect.
     pub reconnect_attempts: u32,
 
-    /// Delay between reconnection attempts in milliseconds
+    /// Initial delay between reconnection attempts in milliseconds
+    ///
+    /// Doubles (with jitter) after each failed attempt.
     pub reconnect_delay_ms: u64,
+
+    /// Maximum delay between reconnection attempts in milliseconds
+    pub reconnect_max_delay_ms: u64,
+
+    /// How long a connection must stay up before the backoff resets (ms)
+    pub reconnect_stable_ms: u64,
 }
 
 impl Default for TransportConfig {
@@ -88,6 +97,8 @@ impl Default for TransportConfig {
             heartbeat_interval_ms: 30_000,
             reconnect_attempts: 3,
             reconnect_delay_ms: 1000,
+            reconnect_max_delay_ms: 30_000,
+            reconnect_stable_ms: 10_000,
         }
     }
 }
@@ -123,6 +134,9 @@ impl TransportConfig {
     /// - `CONDUCTOR_HEARTBEAT`: "0" or "false" to disable
     /// - `CONDUCTOR_HEARTBEAT_INTERVAL`: Heartbeat interval in ms
     /// - `CONDUCTOR_RECONNECT_ATTEMPTS`: Number of reconnection attempts
+    /// - `CONDUCTOR_RECONNECT_DELAY`: Initial reconnection delay in ms
+    /// - `CONDUCTOR_RECONNECT_MAX_DELAY`: Maximum reconnection delay in ms
+    /// - `CONDUCTOR_RECONNECT_STABLE`: Connection uptime in ms that resets the backoff
     pub fn from_env() -> Self {
         let transport = match std::env::var("CONDUCTOR_TRANSPORT")
             .as_deref()
@@ -174,9 +188,27 @@ impl TransportConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1000),
+            reconnect_max_delay_ms: std::env::var("CONDUCTOR_RECONNECT_MAX_DELAY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30_000),
+            reconnect_stable_ms: std::env::var("CONDUCTOR_RECONNECT_STABLE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10_000),
         }
     }
 
+    /// Create a reconnect backoff from these settings
+    #[must_use]
+    pub fn reconnect_backoff(&self) -> ReconnectBackoff {
+        ReconnectBackoff::new(
+            Duration::from_millis(self.reconnect_delay_ms),
+            Duration::from_millis(self.reconnect_max_delay_ms),
+            Duration::from_millis(self.reconnect_stable_ms),
+        )
+    }
+
     /// Check if this is an in-process (embedded) configuration
     #[must_use]
     pub fn is_embedded(&self) -> bool {
@@ -191,6 +223,67 @@ impl TransportConfig {
     }
 }
 
+/// Exponential reconnect backoff with jitter
+///
+/// Each failed attempt doubles the delay up to a cap. Up to 25% jitter is
+/// subtracted so surfaces that dropped together don't reconnect in lockstep.
+/// The backoff only resets once a connection has stayed up long enough,
+/// so a flapping daemon isn't hammered at the base delay.
+#[derive(Clone, Debug)]
+pub struct ReconnectBackoff {
+    base: Duration,
+    max: Duration,
+    stable_after: Duration,
+    failures: u32,
+    connected_at: Option<Instant>,
+}
+
+impl ReconnectBackoff {
+    /// Create a backoff starting at `base`, capped at `max`
+    #[must_use]
+    pub fn new(base: Duration, max: Duration, stable_after: Duration) -> Self {
+        Self {
+            base,
+            max: max.max(base),
+            stable_after,
+            failures: 0,
+            connected_at: None,
+        }
+    }
+
+    /// Delay before the next reconnection attempt
+    ///
+    /// Each call counts as a failed attempt, growing the following delay.
+    pub fn next_delay(&mut self) -> Duration {
+        let factor = 1u32 << self.failures.min(16);
+        let capped = self.base.saturating_mul(factor).min(self.max);
+        self.failures = self.failures.saturating_add(1);
+
+        let jitter = rand::random::<f64>() * 0.25;
+        capped.mul_f64(1.0 - jitter)
+    }
+
+    /// Record a successful connection
+    pub fn on_connected(&mut self, now: Instant) {
+        self.connected_at = Some(now);
+    }
+
+    /// Record a lost connection, resetting the backoff if it was stable
+    pub fn on_disconnected(&mut self, now: Instant) {
+        if let Some(connected_at) = self.connected_at.take() {
+            if now.saturating_duration_since(connected_at) >= self.stable_after {
+                self.failures = 0;
+            }
+        }
+    }
+
+    /// Consecutive failed attempts since the last reset
+    #[must_use]
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
 /// Get the default Unix socket path
 ///
 /// Uses `XDG_RUNTIME_DIR` if available, otherwise /tmp/ai-way-$UID/
@@ -225,6 +318,54 @@ mod tests {
         assert!(config.heartbeat_enabled);
     }
 
+    fn test_backoff() -> ReconnectBackoff {
+        ReconnectBackoff::new(
+            Duration::from_millis(100),
+            Duration::from_millis(1000),
+            Duration::from_secs(10),
+        )
+    }
+
+    #[test]
+    fn test_reconnect_backoff_grows_and_caps() {
+        let mut backoff = test_backoff();
+
+        // Doubling outpaces the 25% jitter, so delays strictly grow until capped
+        let delays: Vec<Duration> = (0..4).map(|_| backoff.next_delay()).collect();
+        assert!(delays[0] <= Duration::from_millis(100));
+        assert!(
+            delays.windows(2).all(|pair| pair[0] < pair[1]),
+            "{delays:?}"
+        );
+
+        for _ in 0..20 {
+            let delay = backoff.next_delay();
+            assert!(delay <= Duration::from_millis(1000));
+            assert!(delay >= Duration::from_millis(750));
+        }
+    }
+
+    #[test]
+    fn test_reconnect_backoff_resets_after_stable_connection() {
+        let mut backoff = test_backoff();
+        for _ in 0..5 {
+            backoff.next_delay();
+        }
+        let start = Instant::now();
+
+        // A connection that drops quickly keeps backing off
+        backoff.on_connected(start);
+        backoff.on_disconnected(start + Duration::from_secs(1));
+        assert_eq!(backoff.failures(), 5);
+        assert!(backoff.next_delay() > Duration::from_millis(100));
+
+        // A stable connection resets to the base delay
+        backoff.on_connected(start);
+        backoff.on_disconnected(start + Duration::from_secs(11));
+        assert_eq!(backoff.failures(), 0);
+        assert!(backoff.next_delay() <= Duration::from_millis(100));
+    }
+
     #[test]
     fn test_transport_config_embedded() {
         let config = TransportConfig::embedded();
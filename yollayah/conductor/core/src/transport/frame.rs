@@ -15,6 +15,11 @@
 //! The Length field contains the size of the JSON payload only (not including the checksum).
 //! The Checksum is the CRC32 hash of the JSON payload.
 //!
+//! Checksums are negotiated during the handshake (see
+//! `SurfaceCapabilities::frame_checksums`). When they are disabled the header
+//! layout is unchanged but the checksum field is written as zero and ignored
+//! on decode.
+//!
 //! # Security
 //!
 //! - Maximum frame size is enforced to prevent memory exhaustion
@@ -54,6 +59,20 @@ fn compute_checksum(payload: &[u8]) -> u32 {
 /// - JSON serialization fails
 /// - Resulting frame exceeds `MAX_FRAME_SIZE`
 pub fn encode<T: Serialize>(msg: &T) -> Result<Vec<u8>, TransportError> {
+    encode_with_checksum(msg, true)
+}
+
+/// Encode a message to a length-prefixed frame, optionally computing the CRC32
+///
+/// When `checksum` is false the checksum field is written as zero.
+///
+/// # Errors
+///
+/// Same as [`encode`].
+pub fn encode_with_checksum<T: Serialize>(
+    msg: &T,
+    checksum: bool,
+) -> Result<Vec<u8>, TransportError> {
     let json =
         serde_json::to_vec(msg).map_err(|e| TransportError::SerializationError(e.to_string()))?;
 
@@ -66,7 +85,7 @@ pub fn encode<T: Serialize>(msg: &T) -> Result<Vec<u8>, TransportError> {
     }
 
     let len = json.len() as u32;
-    let checksum = compute_checksum(&json);
+    let checksum = if checksum { compute_checksum(&json) } else { 0 };
 
     let mut buf = Vec::with_capacity(HEADER_SIZE + json.len());
     buf.extend_from_slice(&len.to_be_bytes());
@@ -76,19 +95,39 @@ pub fn encode<T: Serialize>(msg: &T) -> Result<Vec<u8>, TransportError> {
 }
 
 /// Encoder for streaming frame output
-#[derive(Debug, Default)]
-pub struct FrameEncoder;
+#[derive(Debug)]
+pub struct FrameEncoder {
+    /// Whether to compute CRC32 checksums for outgoing frames
+    checksums: bool,
+}
+
+impl Default for FrameEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl FrameEncoder {
-    /// Create a new encoder
+    /// Create a new encoder (checksums enabled until negotiated otherwise)
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self { checksums: true }
+    }
+
+    /// Enable or disable checksums for subsequent frames
+    pub fn set_checksums(&mut self, enabled: bool) {
+        self.checksums = enabled;
+    }
+
+    /// Whether checksums are written for outgoing frames
+    #[must_use]
+    pub fn checksums(&self) -> bool {
+        self.checksums
     }
 
     /// Encode a message to bytes
     pub fn encode<T: Serialize>(&self, msg: &T) -> Result<Vec<u8>, TransportError> {
-        encode(msg)
+        encode_with_checksum(msg, self.checksums)
     }
 }
 
@@ -100,6 +139,8 @@ pub struct FrameDecoder {
     buffer: Vec<u8>,
     /// Position where we've consumed up to
     read_pos: usize,
+    /// Whether incoming checksums are verified
+    verify_checksums: bool,
 }
 
 impl Default for FrameDecoder {
@@ -115,9 +156,15 @@ impl FrameDecoder {
         Self {
             buffer: Vec::with_capacity(MIN_BUFFER_CAPACITY),
             read_pos: 0,
+            verify_checksums: true,
         }
     }
 
+    /// Enable or disable checksum verification for subsequent frames
+    pub fn set_verify_checksums(&mut self, enabled: bool) {
+        self.verify_checksums = enabled;
+    }
+
     /// Append bytes to the buffer
     pub fn push(&mut self, data: &[u8]) {
         // Compact buffer if we've consumed a lot
@@ -139,7 +186,8 @@ impl FrameDecoder {
     /// Returns:
     /// - `Ok(Some(msg))` if a complete frame was decoded
     /// - `Ok(None)` if more data is needed
-    /// - `Err(TransportError::ChecksumMismatch)` if checksum verification fails
+    /// - `Err(TransportError::ChecksumMismatch)` if checksum verification fails;
+    ///   the corrupted frame is dropped so decoding can resume with the next one
     /// - `Err(...)` if frame is invalid
     pub fn decode<T: DeserializeOwned>(&mut self) -> Result<Option<T>, TransportError> {
         let available = self.available();
@@ -181,12 +229,16 @@ impl FrameDecoder {
         let payload = &self.buffer[payload_start..payload_end];
 
         // Verify checksum
-        let actual_checksum = compute_checksum(payload);
-        if actual_checksum != expected_checksum {
-            return Err(TransportError::ChecksumMismatch {
-                expected: expected_checksum,
-                actual: actual_checksum,
-            });
+        if self.verify_checksums {
+            let actual_checksum = compute_checksum(payload);
+            if actual_checksum != expected_checksum {
+                // Drop the corrupted frame; the length prefix is still trusted
+                self.read_pos = payload_end;
+                return Err(TransportError::ChecksumMismatch {
+                    expected: expected_checksum,
+                    actual: actual_checksum,
+                });
+            }
         }
 
         let msg = serde_json::from_slice(payload)
@@ -423,4 +475,70 @@ mod tests {
         // Different payloads should produce different checksums
         assert_ne!(checksum1, checksum2);
     }
+
+    #[test]
+    fn test_encoder_checksums_roundtrip() {
+        let msg = TestMessage {
+            content: "integrity".to_string(),
+            number: 7,
+        };
+
+        let encoder = FrameEncoder::new();
+        assert!(encoder.checksums());
+        let encoded = encoder.encode(&msg).unwrap();
+
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&encoded);
+        let decoded: TestMessage = decoder.decode().unwrap().unwrap();
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_flipped_byte_detected_and_frame_dropped() {
+        let corrupted_msg = TestMessage {
+            content: "corrupt me".to_string(),
+            number: 1,
+        };
+        let next_msg = TestMessage {
+            content: "still fine".to_string(),
+            number: 2,
+        };
+
+        let mut encoded = encode(&corrupted_msg).unwrap();
+        // Flip a byte inside the payload
+        encoded[HEADER_SIZE + 3] ^= 0x01;
+        encoded.extend(encode(&next_msg).unwrap());
+
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&encoded);
+
+        let result: Result<Option<TestMessage>, _> = decoder.decode();
+        assert!(matches!(
+            result,
+            Err(TransportError::ChecksumMismatch { .. })
+        ));
+
+        // The corrupted frame is dropped and the next one decodes
+        let decoded: TestMessage = decoder.decode().unwrap().unwrap();
+        assert_eq!(decoded, next_msg);
+    }
+
+    #[test]
+    fn test_checksums_disabled() {
+        let msg = TestMessage {
+            content: "no crc".to_string(),
+            number: 3,
+        };
+
+        let mut encoder = FrameEncoder::new();
+        encoder.set_checksums(false);
+        let encoded = encoder.encode(&msg).unwrap();
+        assert_eq!(&encoded[4..8], &[0, 0, 0, 0]);
+
+        let mut decoder = FrameDecoder::new();
+        decoder.set_verify_checksums(false);
+        decoder.push(&encoded);
+        let decoded: TestMessage = decoder.decode().unwrap().unwrap();
+        assert_eq!(decoded, msg);
+    }
 }
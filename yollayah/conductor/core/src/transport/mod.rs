@@ -35,7 +35,7 @@ pub mod websocket;
 
 // Re-exports for convenience
 pub use auth::{get_runtime_dir, get_token_path, remove_token_file, SessionToken, TokenError};
-pub use config::{TransportConfig, TransportType};
+pub use config::{ReconnectBackoff, TransportConfig, TransportType};
 pub use factory::create_surface_transport;
 pub use frame::{FrameDecoder, FrameEncoder};
 pub use heartbeat::{
@@ -51,7 +51,7 @@ pub use traits::{ConductorTransport, ConnectionId, SurfaceTransport, TransportEr
 #[cfg(unix)]
 pub use unix_socket::{UnixSocketClient, UnixSocketServer};
 
-// WebSocket transport types (infrastructure only - implementation pending security review)
+// WebSocket transport types (the server requires the `websocket` feature)
 pub use websocket::{
     // Security
     AuthenticationMethod,
@@ -74,3 +74,5 @@ pub use websocket::{
     WebSocketFrameType,
     WebSocketListener,
 };
+#[cfg(feature = "websocket")]
+pub use websocket::{WebSocketServer, WebSocketServerConnection};
@@ -61,6 +61,9 @@ pub struct RateLimitConfig {
     /// Maximum connections allowed per UID
     pub max_connections_per_uid: u32,
 
+    /// Maximum connections allowed in total, across all UIDs
+    pub max_total_connections: u32,
+
     /// Whether to enable rate limiting (can be disabled for testing)
     pub enabled: bool,
 
@@ -82,6 +85,7 @@ impl Default for RateLimitConfig {
             messages_per_second: 100,
             burst_size: 50,
             max_connections_per_uid: 10,
+            max_total_connections: 100,
             enabled: true,
             min_throttle_delay_ms: 10,
             max_throttle_delay_ms: 1000,
@@ -118,6 +122,13 @@ impl RateLimitConfig {
         self
     }
 
+    /// Set the maximum connections across all UIDs
+    #[must_use]
+    pub fn with_max_total_connections(mut self, max: u32) -> Self {
+        self.max_total_connections = max;
+        self
+    }
+
     /// Enable or disable rate limiting
     #[must_use]
     pub fn with_enabled(mut self, enabled: bool) -> Self {
@@ -155,6 +166,7 @@ impl RateLimitConfig {
             messages_per_second: 50,
             burst_size: 20,
             max_connections_per_uid: 5,
+            max_total_connections: 50,
             enabled: true,
             min_throttle_delay_ms: 50,
             max_throttle_delay_ms: 2000,
@@ -169,6 +181,7 @@ impl RateLimitConfig {
             messages_per_second: 500,
             burst_size: 200,
             max_connections_per_uid: 50,
+            max_total_connections: 500,
             enabled: true,
             min_throttle_delay_ms: 5,
             max_throttle_delay_ms: 500,
@@ -195,6 +208,15 @@ pub enum RateLimitError {
         max: u32,
     },
 
+    /// Too many connections in total, whatever their UIDs
+    #[error("{current} connections are open across all UIDs (max: {max})")]
+    TooManyTotalConnections {
+        /// Current number of connections
+        current: u32,
+        /// Maximum allowed connections
+        max: u32,
+    },
+
     /// Rate limit exceeded (message rate)
     #[error("Rate limit exceeded: {rate:.1} msg/s (limit: {limit} msg/s)")]
     RateLimitExceeded {
@@ -769,6 +791,7 @@ mod tests {
         assert_eq!(config.messages_per_second, 100);
         assert_eq!(config.burst_size, 50);
         assert_eq!(config.max_connections_per_uid, 10);
+        assert_eq!(config.max_total_connections, 100);
         assert!(config.enabled);
     }
 
@@ -778,11 +801,13 @@ mod tests {
             .with_messages_per_second(200)
             .with_burst_size(100)
             .with_max_connections_per_uid(20)
+            .with_max_total_connections(40)
             .with_enabled(false);
 
         assert_eq!(config.messages_per_second, 200);
         assert_eq!(config.burst_size, 100);
         assert_eq!(config.max_connections_per_uid, 20);
+        assert_eq!(config.max_total_connections, 40);
         assert!(!config.enabled);
     }
 
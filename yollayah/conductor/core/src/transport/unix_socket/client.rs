@@ -14,7 +14,7 @@ use tokio::sync::mpsc;
 
 use crate::events::SurfaceEvent;
 use crate::messages::ConductorMessage;
-use crate::transport::frame::{encode, FrameDecoder};
+use crate::transport::frame::{encode_with_checksum, FrameDecoder};
 use crate::transport::traits::{SurfaceTransport, TransportError};
 
 /// Client-side Unix socket transport for Surfaces
@@ -88,8 +88,12 @@ impl SurfaceTransport for UnixSocketClient {
         let connected = Arc::clone(&self.connected);
         connected.store(true, Ordering::SeqCst);
 
+        // Frame checksums stay on until the handshake negotiates otherwise
+        let checksums = Arc::new(AtomicBool::new(true));
+
         // Spawn read task: stream -> msg_tx (ConductorMessages from server)
         let connected_read = Arc::clone(&connected);
+        let checksums_read = Arc::clone(&checksums);
         tokio::spawn(async move {
             let mut decoder = FrameDecoder::new();
             let mut buf = [0u8; 4096];
@@ -108,12 +112,23 @@ impl SurfaceTransport for UnixSocketClient {
                         loop {
                             match decoder.decode::<ConductorMessage>() {
                                 Ok(Some(msg)) => {
+                                    if let ConductorMessage::HandshakeAck {
+                                        frame_checksums, ..
+                                    } = msg
+                                    {
+                                        decoder.set_verify_checksums(frame_checksums);
+                                        checksums_read.store(frame_checksums, Ordering::SeqCst);
+                                    }
                                     if msg_tx.send(msg).await.is_err() {
                                         tracing::debug!("Message receiver dropped");
                                         break;
                                     }
                                 }
                                 Ok(None) => break, // Need more data
+                                Err(e @ TransportError::ChecksumMismatch { .. }) => {
+                                    // Corrupted frame was dropped, keep decoding
+                                    tracing::warn!(error = %e, "Dropped corrupted frame");
+                                }
                                 Err(e) => {
                                     tracing::warn!(error = %e, "Frame decode error");
                                     break;
@@ -136,7 +151,7 @@ impl SurfaceTransport for UnixSocketClient {
         let connected_write = Arc::clone(&connected);
         tokio::spawn(async move {
             while let Some(event) = event_rx.recv().await {
-                match encode(&event) {
+                match encode_with_checksum(&event, checksums.load(Ordering::SeqCst)) {
                     Ok(data) => {
                         if let Err(e) = write_half.write_all(&data).await {
                             tracing::warn!(error = %e, "Write error");
@@ -6,6 +6,7 @@
 use std::collections::HashMap;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -15,7 +16,7 @@ use tokio::sync::{mpsc, RwLock};
 
 use crate::events::SurfaceEvent;
 use crate::messages::ConductorMessage;
-use crate::transport::frame::{encode, FrameDecoder};
+use crate::transport::frame::{encode_with_checksum, FrameDecoder};
 use crate::transport::traits::{ConductorTransport, ConnectionId, TransportError};
 
 /// Server-side Unix socket transport for the Conductor
@@ -192,9 +193,13 @@ impl ConductorTransport for UnixSocketServer {
         // Split the stream for concurrent read/write
         let (mut read_half, mut write_half) = stream.into_split();
 
+        // Frame checksums stay on until the handshake negotiates otherwise
+        let checksums = Arc::new(AtomicBool::new(true));
+
         // Spawn read task: stream -> event_tx (SurfaceEvents from client)
         let conn_id_read = conn_id.clone();
         let connections_read = Arc::clone(&self.connections);
+        let checksums_read = Arc::clone(&checksums);
         tokio::spawn(async move {
             let mut decoder = FrameDecoder::new();
             let mut buf = [0u8; 4096];
@@ -208,6 +213,7 @@ impl ConductorTransport for UnixSocketServer {
                     }
                     Ok(n) => {
                         decoder.push(&buf[..n]);
+                        decoder.set_verify_checksums(checksums_read.load(Ordering::SeqCst));
 
                         // Decode all available frames
                         loop {
@@ -222,6 +228,14 @@ impl ConductorTransport for UnixSocketServer {
                                     }
                                 }
                                 Ok(None) => break, // Need more data
+                                Err(e @ TransportError::ChecksumMismatch { .. }) => {
+                                    // Corrupted frame was dropped, keep decoding
+                                    tracing::warn!(
+                                        conn_id = %conn_id_read,
+                                        error = %e,
+                                        "Dropped corrupted frame"
+                                    );
+                                }
                                 Err(e) => {
                                     tracing::warn!(
                                         conn_id = %conn_id_read,
@@ -249,7 +263,17 @@ impl ConductorTransport for UnixSocketServer {
         let conn_id_write = conn_id.clone();
         tokio::spawn(async move {
             while let Some(msg) = msg_rx.recv().await {
-                match encode(&msg) {
+                let encoded = encode_with_checksum(&msg, checksums.load(Ordering::SeqCst));
+
+                // The ack itself is still framed with the pre-negotiation setting
+                if let ConductorMessage::HandshakeAck {
+                    frame_checksums, ..
+                } = msg
+                {
+                    checksums.store(frame_checksums, Ordering::SeqCst);
+                }
+
+                match encoded {
                     Ok(data) => {
                         if let Err(e) = write_half.write_all(&data).await {
                             tracing::warn!(
@@ -193,8 +193,8 @@ pub struct WebSocketConfig {
 
     /// Enable per-message compression (permessage-deflate)
     ///
-    /// Reduces bandwidth at the cost of CPU. Recommended for
-    /// remote connections with limited bandwidth.
+    /// Not supported yet: the server refuses to start with this set.
+    /// Default: false
     compression: bool,
 
     /// Heartbeat (ping/pong) interval in milliseconds
@@ -229,7 +229,7 @@ impl Default for WebSocketConfig {
             bind_address: "127.0.0.1:8765".to_string(),
             tls: None,
             max_message_size: 10 * 1024 * 1024, // 10 MB
-            compression: true,
+            compression: false,
             heartbeat_interval_ms: 30_000, // 30 seconds
             handshake_timeout_ms: 10_000,  // 10 seconds
             max_pending_connections: 128,
@@ -258,7 +258,7 @@ impl WebSocketConfig {
             bind_address: "127.0.0.1:8765".to_string(),
             tls: None,
             max_message_size: 10 * 1024 * 1024,
-            compression: true,
+            compression: false,
             heartbeat_interval_ms: 30_000,
             handshake_timeout_ms: 30_000,
             max_pending_connections: 32,
@@ -430,6 +430,9 @@ impl WebSocketConfigBuilder {
     }
 
     /// Enable or disable compression
+    ///
+    /// Compression isn't supported yet, so the server won't start with it
+    /// enabled.
     #[must_use]
     pub fn compression(mut self, enabled: bool) -> Self {
         self.config.compression = enabled;
@@ -534,7 +537,7 @@ mod tests {
         assert_eq!(config.bind_address(), "127.0.0.1:8765");
         assert!(config.tls().is_none());
         assert_eq!(config.max_message_size(), 10 * 1024 * 1024);
-        assert!(config.compression());
+        assert!(!config.compression());
         assert_eq!(config.heartbeat_interval(), Duration::from_secs(30));
         assert_eq!(config.handshake_timeout(), Duration::from_secs(10));
         assert_eq!(config.max_pending_connections(), 128);
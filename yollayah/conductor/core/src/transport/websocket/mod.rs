@@ -27,9 +27,10 @@
 //!
 //! # Status
 //!
-//! **NOTE**: This module provides types and configuration only.
-//! Actual WebSocket server implementation is blocked pending security review.
-//! See P5.2 for implementation timeline.
+//! The types and configuration are always available. The server itself
+//! ([`WebSocketServer`]) is built on `tokio-tungstenite` and requires the
+//! `websocket` feature. It does not terminate TLS; run it on localhost
+//! behind a TLS proxy for remote access.
 //!
 //! # Example
 //!
@@ -56,6 +57,8 @@
 mod config;
 mod frame_adapter;
 mod security;
+#[cfg(feature = "websocket")]
+mod server;
 mod traits;
 
 pub use config::{TlsConfig, WebSocketConfig, WebSocketConfigBuilder};
@@ -65,6 +68,8 @@ pub use frame_adapter::{
 pub use security::{
     AuthenticationMethod, OriginPolicy, OriginValidationResult, SecurityConfig, SecurityError,
 };
+#[cfg(feature = "websocket")]
+pub use server::{WebSocketServer, WebSocketServerConnection};
 pub use traits::{
     WebSocketConnection, WebSocketConnectionState, WebSocketError, WebSocketListener,
 };
@@ -80,7 +85,7 @@ mod tests {
         let config = WebSocketConfig::default();
         assert_eq!(config.bind_address(), "127.0.0.1:8765");
         assert!(config.tls().is_none());
-        assert!(config.compression());
+        assert!(!config.compression());
         assert_eq!(config.max_message_size(), 10 * 1024 * 1024);
     }
 
@@ -89,13 +94,13 @@ mod tests {
         let config = WebSocketConfig::builder()
             .bind_address("0.0.0.0:9000")
             .max_message_size(5 * 1024 * 1024)
-            .compression(false)
+            .compression(true)
             .heartbeat_interval(Duration::from_secs(60))
             .build();
 
         assert_eq!(config.bind_address(), "0.0.0.0:9000");
         assert_eq!(config.max_message_size(), 5 * 1024 * 1024);
-        assert!(!config.compression());
+        assert!(config.compression());
         assert_eq!(config.heartbeat_interval(), Duration::from_secs(60));
     }
 
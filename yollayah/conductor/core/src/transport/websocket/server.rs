@@ -0,0 +1,984 @@
+//! WebSocket Server
+//!
+//! `tokio-tungstenite` implementation of [`WebSocketListener`] and
+//! [`WebSocketConnection`]. Requires the `websocket` feature.
+//!
+//! Security checks happen in two places:
+//!
+//! - During the HTTP upgrade: the Origin header is checked against the
+//!   configured [`OriginPolicy`] (403 on denial) and the per-origin
+//!   connection limit is enforced (429 when exceeded).
+//! - On the first frame: when bearer token authentication is required, the
+//!   surface must open with a `Handshake` event carrying a valid
+//!   `auth_token`, otherwise the connection is closed with code 1008.
+//!
+//! TLS is not terminated here. Configurations with TLS are refused at
+//! `start()`; put a TLS-terminating proxy in front of a localhost listener.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, WebSocketConfig as ProtocolConfig};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use super::config::WebSocketConfig;
+use super::frame_adapter::{WebSocketFrame, WebSocketFrameAdapter, WebSocketFrameType};
+use super::security::{AuthenticationMethod, OriginValidationResult, SecurityError};
+use super::traits::{
+    WebSocketConnection, WebSocketConnectionState, WebSocketError, WebSocketListener,
+};
+use crate::events::SurfaceEvent;
+use crate::messages::ConductorMessage;
+use crate::transport::auth::SessionToken;
+use crate::transport::traits::ConnectionId;
+
+/// Buffered frames per direction before senders wait
+const CHANNEL_CAPACITY: usize = 256;
+
+/// How long `close()` waits for the peer to answer the close frame
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Close code for policy violations (failed authentication)
+const CLOSE_POLICY_VIOLATION: u16 = 1008;
+
+/// Close code for going away (idle timeout)
+const CLOSE_GOING_AWAY: u16 = 1001;
+
+/// Connection counts keyed by origin (empty string when no Origin header)
+type OriginCounts = Arc<Mutex<HashMap<String, u32>>>;
+
+/// WebSocket server for remote surfaces
+///
+/// Binds to the configured address and upgrades incoming TCP connections.
+/// Each upgrade runs in its own task, so a slow client can't hold up the
+/// others. Connections rejected during the upgrade are logged and skipped,
+/// so `accept()` only returns surfaces that passed the origin checks.
+pub struct WebSocketServer {
+    /// Server configuration
+    config: WebSocketConfig,
+    /// Token surfaces must present when bearer authentication is required
+    session_token: Option<SessionToken>,
+    /// Open connections per origin
+    origins: OriginCounts,
+    /// Open connections across all origins
+    active: Arc<AtomicUsize>,
+    /// Address the listener is bound to (None until `start()` is called)
+    local_addr: Option<SocketAddr>,
+    /// Task accepting TCP connections and spawning their upgrades
+    acceptor: Option<JoinHandle<()>>,
+    /// Upgraded connections waiting for `accept()`
+    ready: Option<mpsc::Receiver<Result<WebSocketServerConnection, WebSocketError>>>,
+}
+
+impl WebSocketServer {
+    /// Create a new WebSocket server
+    #[must_use]
+    pub fn new(config: WebSocketConfig) -> Self {
+        Self {
+            config,
+            session_token: None,
+            origins: Arc::new(Mutex::new(HashMap::new())),
+            active: Arc::new(AtomicUsize::new(0)),
+            local_addr: None,
+            acceptor: None,
+            ready: None,
+        }
+    }
+
+    /// Set the token validated against `auth_token` in the surface handshake
+    #[must_use]
+    pub fn with_session_token(mut self, token: SessionToken) -> Self {
+        self.session_token = Some(token);
+        self
+    }
+
+    /// Get the server configuration
+    #[must_use]
+    pub fn config(&self) -> &WebSocketConfig {
+        &self.config
+    }
+
+    /// Whether surfaces must authenticate with a handshake token
+    fn requires_token(&self) -> bool {
+        requires_token(&self.config)
+    }
+}
+
+/// Shared state for upgrading accepted TCP connections
+struct Upgrader {
+    config: WebSocketConfig,
+    session_token: Option<SessionToken>,
+    origins: OriginCounts,
+    active: Arc<AtomicUsize>,
+}
+
+impl Upgrader {
+    /// Protocol limits applied to every upgraded connection
+    fn protocol_config(&self) -> ProtocolConfig {
+        let mut config = ProtocolConfig::default();
+        config.max_message_size = Some(self.config.max_message_size());
+        config.max_frame_size = Some(self.config.max_message_size());
+        config
+    }
+
+    /// Run the WebSocket upgrade for an accepted TCP stream
+    // The callback's error type is fixed by tungstenite
+    #[allow(clippy::result_large_err)]
+    async fn upgrade(
+        &self,
+        stream: TcpStream,
+        remote_addr: SocketAddr,
+    ) -> Result<WebSocketServerConnection, WebSocketError> {
+        let mut admitted: Option<(Option<String>, OriginSlot)> = None;
+        let mut denied: Option<SecurityError> = None;
+
+        let callback = |request: &Request, response: Response| {
+            let origin = request
+                .headers()
+                .get("origin")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+
+            match self.admit(origin.as_deref()) {
+                Ok(slot) => {
+                    admitted = Some((origin, slot));
+                    Ok(response)
+                }
+                Err(err) => {
+                    let status = match err {
+                        SecurityError::ConnectionLimitExceeded { .. } => {
+                            StatusCode::TOO_MANY_REQUESTS
+                        }
+                        _ => StatusCode::FORBIDDEN,
+                    };
+                    let mut rejection = ErrorResponse::new(Some(err.to_string()));
+                    *rejection.status_mut() = status;
+                    denied = Some(err);
+                    Err(rejection)
+                }
+            }
+        };
+
+        let upgrade = tokio_tungstenite::accept_hdr_async_with_config(
+            stream,
+            callback,
+            Some(self.protocol_config()),
+        );
+        let ws = match tokio::time::timeout(self.config.handshake_timeout(), upgrade).await {
+            Ok(Ok(ws)) => ws,
+            Ok(Err(e)) => {
+                return Err(match denied {
+                    Some(err) => WebSocketError::SecurityError(err),
+                    None => WebSocketError::HandshakeError(e.to_string()),
+                });
+            }
+            Err(_) => {
+                return Err(WebSocketError::Timeout(
+                    "WebSocket upgrade did not complete".to_string(),
+                ))
+            }
+        };
+
+        let (origin, slot) = admitted.ok_or_else(|| {
+            WebSocketError::HandshakeError("Upgrade completed without admission".to_string())
+        })?;
+
+        let token = if requires_token(&self.config) {
+            self.session_token.clone()
+        } else {
+            None
+        };
+
+        Ok(WebSocketServerConnection::spawn(
+            ws,
+            remote_addr,
+            origin,
+            slot,
+            &self.config,
+            token,
+        ))
+    }
+
+    /// Check the origin and reserve a per-origin connection slot
+    fn admit(&self, origin: Option<&str>) -> Result<OriginSlot, SecurityError> {
+        let security = self.config.security();
+
+        match origin {
+            Some(origin) => {
+                if let OriginValidationResult::Denied { reason } =
+                    security.origin_policy().validate(origin)
+                {
+                    return Err(SecurityError::OriginDenied {
+                        origin: origin.to_string(),
+                        reason,
+                    });
+                }
+            }
+            None if security.require_origin_validation => {
+                return Err(SecurityError::OriginDenied {
+                    origin: String::new(),
+                    reason: "Missing Origin header".to_string(),
+                });
+            }
+            None => {}
+        }
+
+        let key = origin.unwrap_or_default().to_string();
+        let max = security.max_connections_per_origin;
+        let mut origins = self.origins.lock();
+        let current = origins.entry(key.clone()).or_insert(0);
+        if *current >= max {
+            return Err(SecurityError::ConnectionLimitExceeded {
+                current: *current,
+                max,
+            });
+        }
+        *current += 1;
+        self.active.fetch_add(1, Ordering::SeqCst);
+
+        Ok(OriginSlot {
+            origin: key,
+            origins: Arc::clone(&self.origins),
+            active: Arc::clone(&self.active),
+        })
+    }
+}
+
+/// Whether surfaces must authenticate with a handshake token
+fn requires_token(config: &WebSocketConfig) -> bool {
+    let security = config.security();
+    security.require_authentication && !security.authentication_method.is_none()
+}
+
+/// Accept TCP connections, upgrading each in its own task
+///
+/// Upgraded connections and listener errors are queued for `accept()`;
+/// rejected upgrades are logged and dropped.
+async fn accept_loop(
+    listener: TcpListener,
+    upgrader: Arc<Upgrader>,
+    ready: mpsc::Sender<Result<WebSocketServerConnection, WebSocketError>>,
+) {
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                if ready.send(Err(e.into())).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        let upgrader = Arc::clone(&upgrader);
+        let ready = ready.clone();
+        tokio::spawn(async move {
+            match upgrader.upgrade(stream, remote_addr).await {
+                Ok(conn) => {
+                    tracing::info!(
+                        conn_id = %conn.connection_id.0,
+                        %remote_addr,
+                        origin = conn.origin.as_deref().unwrap_or(""),
+                        "WebSocket surface connected"
+                    );
+                    // A stopped server drops the connection, closing it
+                    let _ = ready.send(Ok(conn)).await;
+                }
+                Err(e) => {
+                    tracing::warn!(%remote_addr, error = %e, "WebSocket connection rejected");
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl WebSocketListener for WebSocketServer {
+    type Connection = WebSocketServerConnection;
+
+    async fn start(&mut self) -> Result<(), WebSocketError> {
+        if self.config.is_tls_enabled() {
+            return Err(WebSocketError::TlsError(
+                "TLS termination is not built in; bind to localhost behind a TLS proxy".to_string(),
+            ));
+        }
+
+        if self.config.compression() {
+            return Err(WebSocketError::ProtocolError(
+                "permessage-deflate compression is not supported yet; disable compression"
+                    .to_string(),
+            ));
+        }
+
+        if self.requires_token() {
+            match &self.config.security().authentication_method {
+                AuthenticationMethod::BearerToken if self.session_token.is_none() => {
+                    return Err(WebSocketError::SecurityError(
+                        SecurityError::InvalidCredentials(
+                            "Bearer token authentication requires a session token".to_string(),
+                        ),
+                    ));
+                }
+                AuthenticationMethod::BearerToken | AuthenticationMethod::None => {}
+                method => {
+                    return Err(WebSocketError::SecurityError(
+                        SecurityError::InvalidCredentials(format!(
+                            "Unsupported authentication method: {method:?}"
+                        )),
+                    ));
+                }
+            }
+        }
+
+        let listener = TcpListener::bind(self.config.bind_address()).await?;
+        let addr = listener.local_addr()?;
+        tracing::info!(%addr, "WebSocket server listening");
+
+        let upgrader = Arc::new(Upgrader {
+            config: self.config.clone(),
+            session_token: self.session_token.clone(),
+            origins: Arc::clone(&self.origins),
+            active: Arc::clone(&self.active),
+        });
+        let capacity = (self.config.max_pending_connections() as usize).max(1);
+        let (ready_tx, ready) = mpsc::channel(capacity);
+        self.local_addr = Some(addr);
+        self.acceptor = Some(tokio::spawn(accept_loop(listener, upgrader, ready_tx)));
+        self.ready = Some(ready);
+        Ok(())
+    }
+
+    async fn accept(&mut self) -> Result<Self::Connection, WebSocketError> {
+        let ready = self
+            .ready
+            .as_mut()
+            .ok_or_else(|| WebSocketError::ConnectionFailed("Server not listening".to_string()))?;
+        match ready.recv().await {
+            Some(accepted) => accepted,
+            None => Err(WebSocketError::ConnectionFailed(
+                "Server stopped".to_string(),
+            )),
+        }
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr, WebSocketError> {
+        self.local_addr
+            .ok_or_else(|| WebSocketError::ConnectionFailed("Server not listening".to_string()))
+    }
+
+    fn is_tls_enabled(&self) -> bool {
+        // `start()` refuses TLS configurations, so a running server is plain ws://
+        false
+    }
+
+    fn active_connections(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    async fn stop(&mut self) -> Result<(), WebSocketError> {
+        self.ready = None;
+        self.local_addr = None;
+        if let Some(acceptor) = self.acceptor.take() {
+            acceptor.abort();
+            tracing::info!("WebSocket server stopped");
+        }
+        Ok(())
+    }
+}
+
+impl Drop for WebSocketServer {
+    fn drop(&mut self) {
+        if let Some(acceptor) = self.acceptor.take() {
+            acceptor.abort();
+        }
+    }
+}
+
+/// Reservation of one connection slot for an origin
+///
+/// Released when dropped.
+struct OriginSlot {
+    origin: String,
+    origins: OriginCounts,
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for OriginSlot {
+    fn drop(&mut self) {
+        let mut origins = self.origins.lock();
+        if let Some(count) = origins.get_mut(&self.origin) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                origins.remove(&self.origin);
+            }
+        }
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A surface connected over WebSocket
+///
+/// A reader task decodes incoming frames into [`SurfaceEvent`]s and a writer
+/// task owns the socket's send half, also sending heartbeat pings.
+pub struct WebSocketServerConnection {
+    connection_id: ConnectionId,
+    remote_addr: SocketAddr,
+    origin: Option<String>,
+    state: Arc<Mutex<WebSocketConnectionState>>,
+    adapter: Arc<WebSocketFrameAdapter>,
+    outgoing: mpsc::Sender<Message>,
+    incoming: mpsc::Receiver<SurfaceEvent>,
+    reader: JoinHandle<()>,
+    writer: JoinHandle<()>,
+    slot: Option<OriginSlot>,
+}
+
+impl WebSocketServerConnection {
+    /// Start the reader and writer tasks for an upgraded stream
+    fn spawn(
+        ws: WebSocketStream<TcpStream>,
+        remote_addr: SocketAddr,
+        origin: Option<String>,
+        slot: OriginSlot,
+        config: &WebSocketConfig,
+        token: Option<SessionToken>,
+    ) -> Self {
+        let connection_id = ConnectionId::new();
+        let state = Arc::new(Mutex::new(WebSocketConnectionState::Open));
+        let adapter = Arc::new(
+            WebSocketFrameAdapter::new()
+                .with_max_size(config.max_message_size())
+                .with_compression(config.compression()),
+        );
+        let (sink, stream) = ws.split();
+        let (outgoing, outgoing_rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let (incoming_tx, incoming) = mpsc::channel(CHANNEL_CAPACITY);
+
+        let writer = tokio::spawn(write_loop(
+            sink,
+            outgoing_rx,
+            config.heartbeat_interval(),
+            Arc::clone(&state),
+        ));
+
+        let reader = tokio::spawn(read_loop(ReadContext {
+            conn_id: connection_id.clone(),
+            stream,
+            events: incoming_tx,
+            outgoing: outgoing.clone(),
+            adapter: Arc::clone(&adapter),
+            state: Arc::clone(&state),
+            token,
+            auth_timeout: config.security().connection_timeout(),
+            idle_timeout: config.security().idle_timeout(),
+            debug_frames: config.debug_frames(),
+        }));
+
+        Self {
+            connection_id,
+            remote_addr,
+            origin,
+            state,
+            adapter,
+            outgoing,
+            incoming,
+            reader,
+            writer,
+            slot: Some(slot),
+        }
+    }
+
+    /// Queue a frame for the writer task
+    async fn enqueue(&self, message: Message) -> Result<(), WebSocketError> {
+        if !self.is_open() {
+            return Err(WebSocketError::ConnectionClosed);
+        }
+        self.outgoing
+            .send(message)
+            .await
+            .map_err(|_| WebSocketError::ConnectionClosed)
+    }
+}
+
+#[async_trait]
+impl WebSocketConnection for WebSocketServerConnection {
+    fn connection_id(&self) -> &ConnectionId {
+        &self.connection_id
+    }
+
+    fn remote_addr(&self) -> SocketAddr {
+        self.remote_addr
+    }
+
+    fn state(&self) -> WebSocketConnectionState {
+        *self.state.lock()
+    }
+
+    fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    async fn send(&self, message: ConductorMessage) -> Result<(), WebSocketError> {
+        let frame = self
+            .adapter
+            .to_websocket_frame(&message)
+            .map_err(|e| WebSocketError::SendFailed(e.to_string()))?;
+        self.send_frame(frame).await
+    }
+
+    async fn send_frame(&self, frame: WebSocketFrame) -> Result<(), WebSocketError> {
+        self.enqueue(to_message(frame)?).await
+    }
+
+    async fn recv(&mut self) -> Result<SurfaceEvent, WebSocketError> {
+        self.incoming
+            .recv()
+            .await
+            .ok_or(WebSocketError::ConnectionClosed)
+    }
+
+    fn try_recv(&mut self) -> Result<Option<SurfaceEvent>, WebSocketError> {
+        match self.incoming.try_recv() {
+            Ok(event) => Ok(Some(event)),
+            Err(mpsc::error::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::error::TryRecvError::Disconnected) => Err(WebSocketError::ConnectionClosed),
+        }
+    }
+
+    async fn ping(&self, payload: &[u8]) -> Result<(), WebSocketError> {
+        self.enqueue(Message::Ping(payload.to_vec())).await
+    }
+
+    async fn close(&mut self, code: u16, reason: &str) -> Result<(), WebSocketError> {
+        if self.state().is_terminal() {
+            return Ok(());
+        }
+
+        let sent = self.enqueue(close_message(code, reason)).await;
+        *self.state.lock() = WebSocketConnectionState::Closing;
+
+        // The reader finishes once the peer answers the close frame
+        if tokio::time::timeout(CLOSE_TIMEOUT, &mut self.reader)
+            .await
+            .is_err()
+        {
+            tracing::debug!(conn_id = %self.connection_id.0, "Close handshake timed out");
+        }
+        self.force_close();
+        sent
+    }
+
+    fn force_close(&mut self) {
+        self.reader.abort();
+        self.writer.abort();
+        *self.state.lock() = WebSocketConnectionState::Closed;
+        self.slot = None;
+    }
+}
+
+impl Drop for WebSocketServerConnection {
+    fn drop(&mut self) {
+        self.reader.abort();
+        self.writer.abort();
+    }
+}
+
+/// Convert an adapter frame into a tungstenite message
+fn to_message(frame: WebSocketFrame) -> Result<Message, WebSocketError> {
+    if frame.compressed {
+        return Err(WebSocketError::InvalidFrame(
+            "Compressed frames are not supported".to_string(),
+        ));
+    }
+
+    Ok(match frame.frame_type {
+        WebSocketFrameType::Text => Message::Text(
+            String::from_utf8(frame.payload)
+                .map_err(|e| WebSocketError::InvalidFrame(e.to_string()))?,
+        ),
+        WebSocketFrameType::Binary => Message::Binary(frame.payload),
+        WebSocketFrameType::Ping => Message::Ping(frame.payload),
+        WebSocketFrameType::Pong => Message::Pong(frame.payload),
+        WebSocketFrameType::Close => {
+            let Some((code, reason)) = frame.payload.split_first_chunk::<2>() else {
+                return Ok(Message::Close(None));
+            };
+            close_message(u16::from_be_bytes(*code), &String::from_utf8_lossy(reason))
+        }
+    })
+}
+
+/// Build a close message with a code and reason
+fn close_message(code: u16, reason: &str) -> Message {
+    Message::Close(Some(CloseFrame {
+        code: CloseCode::from(code),
+        reason: reason.to_string().into(),
+    }))
+}
+
+/// Writer task: forwards queued messages and sends heartbeat pings
+async fn write_loop(
+    mut sink: SplitSink<WebSocketStream<TcpStream>, Message>,
+    mut outgoing: mpsc::Receiver<Message>,
+    heartbeat_interval: Duration,
+    state: Arc<Mutex<WebSocketConnectionState>>,
+) {
+    // A zero interval disables heartbeats
+    let mut heartbeat = (!heartbeat_interval.is_zero()).then(|| {
+        tokio::time::interval_at(
+            tokio::time::Instant::now() + heartbeat_interval,
+            heartbeat_interval,
+        )
+    });
+
+    loop {
+        let message = tokio::select! {
+            message = outgoing.recv() => match message {
+                Some(message) => message,
+                None => break,
+            },
+            _ = async {
+                match heartbeat.as_mut() {
+                    Some(interval) => interval.tick().await,
+                    None => std::future::pending().await,
+                }
+            } => Message::Ping(Vec::new()),
+        };
+
+        let closing = matches!(message, Message::Close(_));
+        if let Err(e) = sink.send(message).await {
+            tracing::debug!(error = %e, "WebSocket write failed");
+            *state.lock() = WebSocketConnectionState::Closed;
+            break;
+        }
+        if closing {
+            break;
+        }
+    }
+}
+
+/// Everything the reader task needs
+struct ReadContext {
+    conn_id: ConnectionId,
+    stream: SplitStream<WebSocketStream<TcpStream>>,
+    events: mpsc::Sender<SurfaceEvent>,
+    outgoing: mpsc::Sender<Message>,
+    adapter: Arc<WebSocketFrameAdapter>,
+    state: Arc<Mutex<WebSocketConnectionState>>,
+    /// Expected handshake token (None when authentication is off)
+    token: Option<SessionToken>,
+    auth_timeout: Duration,
+    idle_timeout: Duration,
+    debug_frames: bool,
+}
+
+/// Reader task: decodes frames into surface events
+async fn read_loop(mut ctx: ReadContext) {
+    let mut authenticated = ctx.token.is_none();
+
+    loop {
+        // Until authenticated, the handshake has to arrive within the connection timeout
+        let wait = if authenticated {
+            ctx.idle_timeout
+        } else {
+            ctx.auth_timeout
+        };
+        let next = if wait.is_zero() {
+            ctx.stream.next().await
+        } else if let Ok(next) = tokio::time::timeout(wait, ctx.stream.next()).await {
+            next
+        } else {
+            let (code, reason) = if authenticated {
+                (CLOSE_GOING_AWAY, "Idle timeout")
+            } else {
+                (CLOSE_POLICY_VIOLATION, "Handshake timeout")
+            };
+            tracing::info!(conn_id = %ctx.conn_id.0, reason, "Closing WebSocket connection");
+            let _ = ctx.outgoing.send(close_message(code, reason)).await;
+            break;
+        };
+
+        let frame = match next {
+            Some(Ok(Message::Text(text))) => WebSocketFrame::text(text.into_bytes()),
+            Some(Ok(Message::Binary(data))) => WebSocketFrame::binary(data),
+            Some(Ok(Message::Close(_))) | None => break,
+            // Pings are answered by tungstenite; pongs only prove liveness
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => {
+                tracing::warn!(conn_id = %ctx.conn_id.0, error = %e, "WebSocket read error");
+                break;
+            }
+        };
+
+        if ctx.debug_frames {
+            tracing::debug!(
+                conn_id = %ctx.conn_id.0,
+                frame = %String::from_utf8_lossy(&frame.payload),
+                "WebSocket frame received"
+            );
+        }
+
+        let event: SurfaceEvent = match ctx.adapter.from_websocket_frame(&frame) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::warn!(conn_id = %ctx.conn_id.0, error = %e, "Frame decode error");
+                continue;
+            }
+        };
+
+        if !authenticated {
+            let valid = match (&event, &ctx.token) {
+                (
+                    SurfaceEvent::Handshake {
+                        auth_token: Some(provided),
+                        ..
+                    },
+                    Some(token),
+                ) => token.validate(provided),
+                _ => false,
+            };
+            if !valid {
+                tracing::warn!(conn_id = %ctx.conn_id.0, "WebSocket authentication failed");
+                let _ = ctx
+                    .outgoing
+                    .send(close_message(
+                        CLOSE_POLICY_VIOLATION,
+                        "Authentication failed",
+                    ))
+                    .await;
+                break;
+            }
+            authenticated = true;
+        }
+
+        if ctx.events.send(event).await.is_err() {
+            break;
+        }
+    }
+
+    *ctx.state.lock() = WebSocketConnectionState::Closed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{SurfaceCapabilities, SurfaceType};
+    use crate::messages::{ConductorState, EventId};
+    use crate::transport::websocket::SecurityConfig;
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::http::HeaderValue;
+
+    /// Start a server on an ephemeral localhost port
+    async fn start_server(security: SecurityConfig) -> (WebSocketServer, SocketAddr) {
+        let config = WebSocketConfig::builder()
+            .bind_address("127.0.0.1:0")
+            .security(security)
+            .build();
+        let mut server = WebSocketServer::new(config);
+        server.start().await.unwrap();
+        let addr = server.local_addr().unwrap();
+        (server, addr)
+    }
+
+    /// Build an upgrade request carrying an Origin header
+    fn request_from(addr: SocketAddr, origin: &'static str) -> Request {
+        let mut request = format!("ws://{addr}").into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("origin", HeaderValue::from_static(origin));
+        request
+    }
+
+    fn app_origin_security(max_per_origin: u32) -> SecurityConfig {
+        SecurityConfig {
+            require_authentication: false,
+            authentication_method: AuthenticationMethod::None,
+            allowed_origins: vec!["https://app.example.com".into()],
+            max_connections_per_origin: max_per_origin,
+            ..SecurityConfig::default()
+        }
+    }
+
+    fn rejection_status(err: tokio_tungstenite::tungstenite::Error) -> StatusCode {
+        match err {
+            tokio_tungstenite::tungstenite::Error::Http(response) => response.status(),
+            other => panic!("expected HTTP rejection, got {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_state_message_roundtrip() {
+        let (mut server, addr) = start_server(SecurityConfig::development()).await;
+        let accept = tokio::spawn(async move {
+            let conn = server.accept().await.unwrap();
+            (server, conn)
+        });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+        let (server, mut conn) = accept.await.unwrap();
+        assert!(conn.is_open());
+        assert_eq!(server.active_connections(), 1);
+
+        conn.send(ConductorMessage::State {
+            state: ConductorState::Ready,
+        })
+        .await
+        .unwrap();
+        let Some(Ok(Message::Text(text))) = client.next().await else {
+            panic!("expected a text frame");
+        };
+        let decoded: ConductorMessage = serde_json::from_str(&text).unwrap();
+        assert!(matches!(
+            decoded,
+            ConductorMessage::State {
+                state: ConductorState::Ready
+            }
+        ));
+
+        let event = serde_json::to_string(&SurfaceEvent::Pong { seq: 7 }).unwrap();
+        client.send(Message::Text(event)).await.unwrap();
+        assert!(matches!(
+            conn.recv().await.unwrap(),
+            SurfaceEvent::Pong { seq: 7 }
+        ));
+
+        // The client answers the close frame while reading
+        tokio::spawn(async move { while client.next().await.is_some() {} });
+        conn.close(1000, "done").await.unwrap();
+        assert_eq!(conn.state(), WebSocketConnectionState::Closed);
+        assert_eq!(server.active_connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_rejected_with_403() {
+        let (mut server, addr) = start_server(app_origin_security(10)).await;
+        let accept = tokio::spawn(async move { server.accept().await.map(|_| ()) });
+
+        let err = tokio_tungstenite::connect_async(request_from(addr, "https://evil.example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(rejection_status(err), StatusCode::FORBIDDEN);
+
+        let (_client, _) =
+            tokio_tungstenite::connect_async(request_from(addr, "https://app.example.com"))
+                .await
+                .unwrap();
+        assert!(accept.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_per_origin_limit_enforced() {
+        let (mut server, addr) = start_server(app_origin_security(1)).await;
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok(conn) = server.accept().await {
+                held.push(conn);
+            }
+        });
+
+        let (_first, _) =
+            tokio_tungstenite::connect_async(request_from(addr, "https://app.example.com"))
+                .await
+                .unwrap();
+        let err = tokio_tungstenite::connect_async(request_from(addr, "https://app.example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(rejection_status(err), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_slow_upgrade_does_not_block_accept() {
+        let (mut server, addr) = start_server(SecurityConfig::development()).await;
+
+        // Connects but never sends the upgrade request
+        let _stalled = TcpStream::connect(addr).await.unwrap();
+
+        let accept = tokio::spawn(async move { server.accept().await.map(|_| ()) });
+        let (_client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+        let accepted = tokio::time::timeout(Duration::from_secs(2), accept)
+            .await
+            .expect("accept waited on the stalled upgrade");
+        assert!(accepted.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_compression_refused_at_start() {
+        let config = WebSocketConfig::builder()
+            .bind_address("127.0.0.1:0")
+            .compression(true)
+            .build();
+
+        let mut server = WebSocketServer::new(config);
+        assert!(matches!(
+            server.start().await,
+            Err(WebSocketError::ProtocolError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_required_in_handshake() {
+        let token = SessionToken::generate();
+        let security = SecurityConfig {
+            require_authentication: true,
+            authentication_method: AuthenticationMethod::BearerToken,
+            ..app_origin_security(10)
+        };
+        let config = WebSocketConfig::builder()
+            .bind_address("127.0.0.1:0")
+            .security(security)
+            .build();
+
+        let mut server = WebSocketServer::new(config.clone());
+        assert!(server.start().await.is_err());
+
+        let mut server = WebSocketServer::new(config).with_session_token(token);
+        server.start().await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let accept = tokio::spawn(async move {
+            let conn = server.accept().await.unwrap();
+            (server, conn)
+        });
+
+        let (mut client, _) =
+            tokio_tungstenite::connect_async(request_from(addr, "https://app.example.com"))
+                .await
+                .unwrap();
+        let (_server, _conn) = accept.await.unwrap();
+
+        let handshake = SurfaceEvent::Handshake {
+            event_id: EventId("evt_1".into()),
+            protocol_version: 1,
+            surface_type: SurfaceType::Web,
+            capabilities: SurfaceCapabilities::web(),
+            auth_token: Some("not-the-token".into()),
+            client_id: None,
+        };
+        client
+            .send(Message::Text(serde_json::to_string(&handshake).unwrap()))
+            .await
+            .unwrap();
+
+        let Some(Ok(Message::Close(Some(frame)))) = client.next().await else {
+            panic!("expected a close frame");
+        };
+        assert_eq!(u16::from(frame.code), CLOSE_POLICY_VIOLATION);
+    }
+}
@@ -3,8 +3,8 @@
 //! Trait definitions for WebSocket connection handling. These traits define
 //! the interface for WebSocket server and connection implementations.
 //!
-//! **NOTE**: These are interface definitions only. Actual implementation
-//! is blocked pending security review (see P5.2).
+//! The `tokio-tungstenite` implementation lives in `server.rs` behind the
+//! `websocket` feature.
 
 use std::net::SocketAddr;
 
@@ -117,8 +117,7 @@ impl WebSocketConnectionState {
 /// Represents an established WebSocket connection. Implementations handle
 /// the underlying socket management, frame parsing, and message conversion.
 ///
-/// **NOTE**: This trait defines the interface. Implementation is pending
-/// security review.
+/// Implemented by `WebSocketServerConnection` (`websocket` feature).
 #[async_trait]
 pub trait WebSocketConnection: Send + Sync {
     /// Get the connection ID
@@ -205,8 +204,7 @@ pub trait WebSocketConnection: Send + Sync {
 /// Accepts incoming WebSocket connections. Implementations handle
 /// binding, TLS termination, and the WebSocket upgrade handshake.
 ///
-/// **NOTE**: This trait defines the interface. Implementation is pending
-/// security review.
+/// Implemented by `WebSocketServer` (`websocket` feature).
 #[async_trait]
 pub trait WebSocketListener: Send + Sync {
     /// The connection type produced by this listener
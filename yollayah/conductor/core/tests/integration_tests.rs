@@ -507,6 +507,7 @@ fn clear_config_env_vars() {
     std::env::remove_var("CONDUCTOR_RATE_LIMIT_MPS");
     std::env::remove_var("CONDUCTOR_RATE_LIMIT_BURST");
     std::env::remove_var("CONDUCTOR_MAX_CONNECTIONS_PER_UID");
+    std::env::remove_var("CONDUCTOR_MAX_TOTAL_CONNECTIONS");
     std::env::remove_var("CONDUCTOR_DEFAULT_MODEL");
     std::env::remove_var("CONDUCTOR_MAX_CONCURRENT");
     std::env::remove_var("CONDUCTOR_MAX_MESSAGE_SIZE");
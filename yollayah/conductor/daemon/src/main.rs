@@ -21,27 +21,46 @@
 //!
 //! # Verbose logging
 //! RUST_LOG=debug conductor-daemon
+//!
+//! # JSON logs for ingestion
+//! conductor-daemon --log-format json
 //! ```
 //!
 //! # Signals
 //!
-//! - `SIGTERM` / `SIGINT`: Graceful shutdown
+//! - `SIGTERM` / `SIGINT`: Graceful shutdown (a second one exits at once)
 //! - `SIGHUP`: Reload configuration (hot reload)
+//!
+//! With `--daemonize` the process forks before the async runtime starts, and
+//! the handlers are installed in the daemon itself before its PID file is
+//! written.
+//!
+//! # Watchdog
+//!
+//! If the main loop makes no progress for `--watchdog-stall-secs` (default
+//! 60, 0 = off), a critical error is logged and `--watchdog-action` is
+//! taken: `log` (default), `shutdown` (exit cleanly so a supervisor can
+//! restart the daemon) or `abort`.
 
 mod server;
+mod watchdog;
 
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use tokio::signal::unix::{signal, SignalKind};
 use tracing::{error, info, warn};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
 
-use server::DaemonServer;
+use server::{DaemonServer, ReloadGate};
+use watchdog::{StallAction, DEFAULT_STALL_SECS};
 
 /// Conductor Daemon - Multi-surface orchestration server for ai-way
 #[derive(Parser, Debug)]
@@ -67,6 +86,45 @@ pub struct Args {
     /// Log level (trace, debug, info, warn, error)
     #[arg(short = 'l', long, env = "CONDUCTOR_LOG_LEVEL", default_value = "info")]
     log_level: String,
+
+    /// Log format (text, json, compact)
+    #[arg(
+        long,
+        env = "CONDUCTOR_LOG_FORMAT",
+        value_enum,
+        default_value_t = LogFormat::Text
+    )]
+    log_format: LogFormat,
+
+    /// Seconds the main loop may stall before the watchdog acts (0 = off)
+    #[arg(
+        long,
+        env = "CONDUCTOR_WATCHDOG_STALL_SECS",
+        value_name = "SECS",
+        default_value_t = DEFAULT_STALL_SECS
+    )]
+    watchdog_stall_secs: u64,
+
+    /// What the watchdog does when the main loop stalls (log, shutdown, abort)
+    #[arg(
+        long,
+        env = "CONDUCTOR_WATCHDOG_ACTION",
+        value_enum,
+        default_value_t = StallAction::Log
+    )]
+    watchdog_action: StallAction,
+}
+
+/// How log lines are written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    /// Human-readable multi-field lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log ingestion
+    Json,
+    /// Shorter human-readable lines
+    Compact,
 }
 
 /// Get the default socket path
@@ -151,21 +209,41 @@ fn check_existing_daemon(pid_path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-/// Initialize logging with the specified level
-fn init_logging(level: &str) -> Result<()> {
+/// Build the log subscriber for a level and format, writing to `writer`
+fn log_subscriber<W>(
+    level: &str,
+    format: LogFormat,
+    writer: W,
+) -> Box<dyn tracing::Subscriber + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
     let filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
         tracing_subscriber::EnvFilter::new(format!(
             "conductor_daemon={level},conductor_core={level}"
         ))
     });
 
-    tracing_subscriber::fmt()
+    let builder = tracing_subscriber::fmt()
         .with_env_filter(filter)
+        .with_writer(writer)
         .with_target(true)
         .with_thread_ids(true)
         .with_file(true)
-        .with_line_number(true)
-        .init();
+        .with_line_number(true);
+
+    match format {
+        LogFormat::Text => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(builder.json().finish()),
+        LogFormat::Compact => Box::new(builder.compact().finish()),
+    }
+}
+
+/// Initialize logging with the specified level and format
+fn init_logging(level: &str, format: LogFormat) -> Result<()> {
+    log_subscriber(level, format, std::io::stdout)
+        .try_init()
+        .context("Failed to initialize logging")?;
 
     Ok(())
 }
@@ -204,26 +282,81 @@ fn daemonize() -> Result<()> {
         }
     }
 
+    reset_signals()?;
+
     // Close stdin, stdout, stderr (or redirect to /dev/null)
     // For now we keep them open for logging purposes in early stages
 
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+/// Clear the signal mask and dispositions inherited from the launcher
+///
+/// A shell may start background jobs with SIGINT ignored, and a launcher
+/// may block signals; either would leave the daemon deaf to shutdown
+/// requests until our own handlers are installed.
+fn reset_signals() -> Result<()> {
+    use nix::sys::signal::{self, SigHandler, SigSet, SigmaskHow, Signal};
+
+    signal::sigprocmask(SigmaskHow::SIG_SETMASK, Some(&SigSet::empty()), None)
+        .context("Failed to clear the signal mask")?;
+    for sig in [Signal::SIGTERM, Signal::SIGINT, Signal::SIGHUP] {
+        // SAFETY: restoring the default disposition installs no handler code
+        unsafe { signal::signal(sig, SigHandler::SigDfl) }
+            .with_context(|| format!("Failed to reset {sig}"))?;
+    }
+    Ok(())
+}
+
+/// Install the shutdown (SIGTERM/SIGINT) and reload (SIGHUP) handlers
+///
+/// The handlers are registered before this returns, so a signal sent once
+/// it has is never met by the default disposition. A second shutdown signal
+/// exits immediately, in case graceful shutdown is stuck.
+fn install_signal_handlers(
+    shutdown: Arc<AtomicBool>,
+    reload_config: Arc<ReloadGate>,
+) -> Result<()> {
+    let mut sigterm =
+        signal(SignalKind::terminate()).context("Failed to install SIGTERM handler")?;
+    let mut sigint = signal(SignalKind::interrupt()).context("Failed to install SIGINT handler")?;
+    let mut sighup = signal(SignalKind::hangup()).context("Failed to install SIGHUP handler")?;
+
+    tokio::spawn(async move {
+        loop {
+            let name = tokio::select! {
+                _ = sigterm.recv() => "SIGTERM",
+                _ = sigint.recv() => "SIGINT",
+                _ = sighup.recv() => {
+                    info!("Received SIGHUP, marking config for reload");
+                    reload_config.request();
+                    continue;
+                }
+            };
+            if shutdown.swap(true, Ordering::SeqCst) {
+                warn!("Received {name} during shutdown, exiting immediately");
+                std::process::exit(1);
+            }
+            info!("Received {name}, initiating shutdown");
+        }
+    });
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
     let args = Args::parse();
 
     // Initialize logging first
-    init_logging(&args.log_level)?;
+    init_logging(&args.log_level, args.log_format)?;
 
     info!("Conductor Daemon starting");
     info!("Version: {}", env!("CARGO_PKG_VERSION"));
     info!("PID: {}", std::process::id());
 
     // Resolve paths
-    let socket_path = args.socket_path.unwrap_or_else(default_socket_path);
-    let pid_path = args.pid_file.unwrap_or_else(default_pid_path);
+    let socket_path = args.socket_path.clone().unwrap_or_else(default_socket_path);
+    let pid_path = args.pid_file.clone().unwrap_or_else(default_pid_path);
 
     info!(socket_path = ?socket_path, "Socket path");
     info!(pid_path = ?pid_path, "PID file path");
@@ -235,7 +368,9 @@ async fn main() -> Result<()> {
     // Check for existing daemon
     check_existing_daemon(&pid_path)?;
 
-    // Daemonize if requested
+    // Daemonize if requested. This has to happen before the async runtime
+    // starts: fork only keeps the calling thread, so a runtime started
+    // earlier would lose its workers and signal driver in the daemon.
     if args.daemonize {
         info!("Daemonizing...");
         daemonize()?;
@@ -243,44 +378,25 @@ async fn main() -> Result<()> {
         info!("Daemonized, new PID: {}", std::process::id());
     }
 
-    // Write PID file
-    write_pid_file(&pid_path)?;
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the async runtime")?;
+    runtime.block_on(run(args, socket_path, pid_path))
+}
 
-    // Setup signal handlers
+/// Run the daemon until it's told to shut down
+async fn run(args: Args, socket_path: PathBuf, pid_path: PathBuf) -> Result<()> {
+    // Setup signal handlers before the PID file tells anyone where to send them
     let shutdown = Arc::new(AtomicBool::new(false));
-    let reload_config = Arc::new(AtomicBool::new(false));
-
-    // Spawn signal handler task
-    let shutdown_clone = Arc::clone(&shutdown);
-    let reload_clone = Arc::clone(&reload_config);
-    tokio::spawn(async move {
-        let mut sigterm =
-            signal(SignalKind::terminate()).expect("Failed to install SIGTERM handler");
-        let mut sigint = signal(SignalKind::interrupt()).expect("Failed to install SIGINT handler");
-        let mut sighup = signal(SignalKind::hangup()).expect("Failed to install SIGHUP handler");
+    let reload_config = Arc::new(ReloadGate::new());
+    install_signal_handlers(Arc::clone(&shutdown), Arc::clone(&reload_config))?;
 
-        loop {
-            tokio::select! {
-                _ = sigterm.recv() => {
-                    info!("Received SIGTERM, initiating shutdown");
-                    shutdown_clone.store(true, Ordering::SeqCst);
-                    break;
-                }
-                _ = sigint.recv() => {
-                    info!("Received SIGINT, initiating shutdown");
-                    shutdown_clone.store(true, Ordering::SeqCst);
-                    break;
-                }
-                _ = sighup.recv() => {
-                    info!("Received SIGHUP, marking config for reload");
-                    reload_clone.store(true, Ordering::SeqCst);
-                }
-            }
-        }
-    });
+    // Write PID file
+    write_pid_file(&pid_path)?;
 
     // Create and run daemon server
-    let mut server = DaemonServer::new(socket_path.clone(), args.config)?;
+    let mut server = DaemonServer::new(socket_path.clone(), args.config)?.with_watchdog(
+        Duration::from_secs(args.watchdog_stall_secs),
+        args.watchdog_action,
+    );
 
     // Run the server
     let result = server.run(shutdown, reload_config).await;
@@ -307,3 +423,90 @@ async fn main() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Log writer that collects everything written to it
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// Log one event with `format` and return what was written
+    fn log_once(format: LogFormat) -> String {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = log_subscriber("info", format, move || writer.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            info!(surfaces = 2, "Daemon ready");
+        });
+        logs.text()
+    }
+
+    #[test]
+    fn test_every_log_format_writes() {
+        for format in [LogFormat::Text, LogFormat::Json, LogFormat::Compact] {
+            let output = log_once(format);
+            assert!(output.contains("Daemon ready"), "{format:?}: {output}");
+        }
+    }
+
+    #[test]
+    fn test_json_logs_are_parseable_lines() {
+        let output = log_once(LogFormat::Json);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["level"], "INFO");
+        assert_eq!(entry["target"], "conductor_daemon::tests");
+        assert_eq!(entry["fields"]["message"], "Daemon ready");
+        assert_eq!(entry["fields"]["surfaces"], 2);
+        assert!(entry["filename"].is_string());
+        assert!(entry["line_number"].is_number());
+        assert!(entry["threadId"].is_string());
+    }
+
+    #[test]
+    fn test_log_format_flag() {
+        let args = Args::try_parse_from(["conductor-daemon", "--log-format", "json"]).unwrap();
+        assert_eq!(args.log_format, LogFormat::Json);
+        assert!(Args::try_parse_from(["conductor-daemon", "--log-format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_watchdog_flags() {
+        let args = Args::try_parse_from(["conductor-daemon"]).unwrap();
+        assert_eq!(args.watchdog_stall_secs, DEFAULT_STALL_SECS);
+        assert_eq!(args.watchdog_action, StallAction::Log);
+
+        let args = Args::try_parse_from([
+            "conductor-daemon",
+            "--watchdog-stall-secs",
+            "0",
+            "--watchdog-action",
+            "abort",
+        ])
+        .unwrap();
+        assert_eq!(args.watchdog_stall_secs, 0);
+        assert_eq!(args.watchdog_action, StallAction::Abort);
+    }
+}
@@ -6,6 +6,7 @@
 //! - Tracks active connections via SurfaceRegistry
 //! - Supports graceful shutdown
 //! - Handles config reload signals
+//! - Watches the main loop for stalls
 //!
 //! # Multi-Surface Architecture
 //!
@@ -30,6 +31,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use dashmap::DashMap;
@@ -38,11 +40,17 @@ use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, error, info, warn, Instrument};
 
 use conductor_core::{
-    transport::{FrameDecoder, FrameEncoder},
-    Conductor, ConductorConfig, ConductorMessage, ConnectionId, OllamaBackend, SurfaceCapabilities,
-    SurfaceEvent, SurfaceHandle, SurfaceRegistry, SurfaceType,
+    default_config_path, load_config_from_path,
+    transport::{FrameDecoder, FrameEncoder, TransportError},
+    Conductor, ConductorConfig, ConductorConfigFile, ConductorMessage, ConnectionId, OllamaBackend,
+    SurfaceCapabilities, SurfaceEvent, SurfaceHandle, SurfaceRegistry, SurfaceType,
 };
 
+use crate::watchdog::{Heartbeat, StallAction, Watchdog, DEFAULT_STALL_SECS};
+
+/// How long a client turned away by the connection limit waits to retry
+const FULL_RETRY_AFTER: Duration = Duration::from_secs(2);
+
 /// Connection state tracking (internal to server, separate from SurfaceHandle)
 struct ConnectionState {
     /// When the connection was established
@@ -55,12 +63,16 @@ struct ConnectionState {
 
 /// Configuration for the daemon server
 pub struct ServerConfig {
-    /// Maximum number of concurrent connections
-    pub max_connections: usize,
+    /// Maximum number of concurrent connections, across all UIDs
+    pub max_connections: u32,
     /// Per-connection channel capacity
     pub connection_channel_capacity: usize,
     /// Event channel capacity (from surfaces to conductor)
     pub event_capacity: usize,
+    /// How long the main loop may go without progress (zero = no watchdog)
+    pub watchdog_stall: Duration,
+    /// What the watchdog does when the main loop stalls
+    pub watchdog_action: StallAction,
 }
 
 impl Default for ServerConfig {
@@ -69,6 +81,18 @@ impl Default for ServerConfig {
             max_connections: 100,
             connection_channel_capacity: 256,
             event_capacity: 256,
+            watchdog_stall: Duration::from_secs(DEFAULT_STALL_SECS),
+            watchdog_action: StallAction::Log,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Server settings from the loaded configuration file
+    pub fn from_file(file: &ConductorConfigFile) -> Self {
+        Self {
+            max_connections: file.rate_limit.max_total_connections,
+            ..Self::default()
         }
     }
 }
@@ -81,21 +105,39 @@ pub struct DaemonServer {
     config_path: Option<PathBuf>,
     /// Server configuration
     server_config: ServerConfig,
+    /// Configuration loaded from the config file
+    file_config: ConductorConfigFile,
     /// Active connection state (task handles, peer info)
     connection_states: Arc<DashMap<ConnectionId, ConnectionState>>,
 }
 
 impl DaemonServer {
     /// Create a new daemon server
+    ///
+    /// Server limits and the Conductor's file settings come from the config
+    /// file (the default path if none is given) and its environment
+    /// overrides.
     pub fn new(socket_path: PathBuf, config_path: Option<PathBuf>) -> Result<Self> {
+        let file_config = load_config_from_path(config_path.clone().or_else(default_config_path))
+            .context("Failed to load configuration")?;
         Ok(Self {
             socket_path,
             config_path,
-            server_config: ServerConfig::default(),
+            server_config: ServerConfig::from_file(&file_config),
+            file_config,
             connection_states: Arc::new(DashMap::new()),
         })
     }
 
+    /// Watch the main loop, taking `action` once it's stuck for `stall_after`
+    ///
+    /// A zero `stall_after` turns the watchdog off.
+    pub fn with_watchdog(mut self, stall_after: Duration, action: StallAction) -> Self {
+        self.server_config.watchdog_stall = stall_after;
+        self.server_config.watchdog_action = action;
+        self
+    }
+
     /// Get peer credentials from Unix socket
     #[cfg(unix)]
     fn get_peer_uid(stream: &UnixStream) -> Option<u32> {
@@ -148,7 +190,7 @@ impl DaemonServer {
     pub async fn run(
         &mut self,
         shutdown: Arc<AtomicBool>,
-        reload_config: Arc<AtomicBool>,
+        reload_config: Arc<ReloadGate>,
     ) -> Result<()> {
         // Prepare socket
         self.prepare_socket()?;
@@ -167,15 +209,16 @@ impl DaemonServer {
             std::fs::set_permissions(&self.socket_path, perms)?;
         }
 
-        // Create shared SurfaceRegistry for multi-surface support
-        let registry = SurfaceRegistry::new();
+        // Create shared SurfaceRegistry for multi-surface support; it
+        // enforces the global connection limit
+        let registry = SurfaceRegistry::with_max_connections(self.server_config.max_connections);
 
         // Create event channel for aggregated surface events (with connection ID)
         let (event_tx, mut event_rx) =
             mpsc::channel::<(ConnectionId, SurfaceEvent)>(self.server_config.event_capacity);
 
         // Create Conductor with SurfaceRegistry (multi-surface mode)
-        let conductor_config = ConductorConfig::from_env();
+        let conductor_config = ConductorConfig::from_env_and_file(&self.file_config);
         let backend = OllamaBackend::from_env();
         let conductor = Arc::new(Mutex::new(Conductor::new_with_registry(
             backend,
@@ -203,8 +246,11 @@ impl DaemonServer {
             }
         });
 
-        // Spawn task for streaming token polling
+        // Spawn task for streaming token polling. This is the daemon's main
+        // loop: a Conductor call stuck while holding the lock stops it too.
         let conductor_for_streaming = Arc::clone(&conductor);
+        let heartbeat = Heartbeat::default();
+        let main_loop_heartbeat = heartbeat.clone();
         tokio::spawn(async move {
             loop {
                 {
@@ -213,11 +259,30 @@ impl DaemonServer {
                     // No sleep needed - eliminates 0-1ms latency and idle CPU waste
                     c.poll_streaming().await;
                 }
+                main_loop_heartbeat.beat();
                 // ✅ GOOD: Yield to other tasks between poll batches (no sleep!)
                 tokio::task::yield_now().await;
             }
         });
 
+        // Spawn watchdog for a stalled main loop
+        if !self.server_config.watchdog_stall.is_zero() {
+            info!(
+                stall_secs = self.server_config.watchdog_stall.as_secs(),
+                action = ?self.server_config.watchdog_action,
+                "Main loop watchdog enabled"
+            );
+            tokio::spawn(
+                Watchdog::new(
+                    heartbeat,
+                    self.server_config.watchdog_stall,
+                    self.server_config.watchdog_action,
+                    Arc::clone(&shutdown),
+                )
+                .run(),
+            );
+        }
+
         // Spawn task to periodically cleanup disconnected surfaces
         let registry_for_cleanup = registry.clone();
         tokio::spawn(async move {
@@ -239,8 +304,9 @@ impl DaemonServer {
                 break;
             }
 
-            // Check for config reload
-            if reload_config.swap(false, Ordering::SeqCst) {
+            // Check for config reload (signals that arrive mid-reload are
+            // coalesced into a single follow-up reload)
+            if let Some(_reload) = reload_config.begin() {
                 info!("Config reload requested");
                 self.reload_config().await?;
             }
@@ -262,13 +328,6 @@ impl DaemonServer {
                 }
             };
 
-            // Check connection limit
-            if self.connection_states.len() >= self.server_config.max_connections {
-                warn!("Connection limit reached, rejecting new connection");
-                drop(stream);
-                continue;
-            }
-
             // Get peer credentials
             let peer_uid = Self::get_peer_uid(&stream);
 
@@ -298,7 +357,11 @@ impl DaemonServer {
                 SurfaceType::Headless, // Will be updated when Handshake is received
                 SurfaceCapabilities::headless(), // Will be updated when Handshake is received
             );
-            registry.register(handle);
+            if let Err(e) = registry.try_register(handle) {
+                warn!(reason = %e, "Connection limit reached, rejecting new connection");
+                tokio::spawn(Self::reject_connection(stream, e.to_string()));
+                continue;
+            }
 
             info!(
                 conn_id = %conn_id,
@@ -397,6 +460,10 @@ impl DaemonServer {
                                         // Need more data
                                         break;
                                     }
+                                    Err(e @ TransportError::ChecksumMismatch { .. }) => {
+                                        // Corrupted frame was dropped, keep decoding
+                                        warn!(error = %e, "Dropped corrupted event frame");
+                                    }
                                     Err(e) => {
                                         warn!(error = %e, "Failed to decode event frame");
                                         break;
@@ -418,8 +485,16 @@ impl DaemonServer {
                             let encoder = encoder.clone();
                             let write_half = write_half.clone();
 
-                            let enc = encoder.lock().await;
-                            match enc.encode(&message) {
+                            let mut enc = encoder.lock().await;
+                            let encoded = enc.encode(&message);
+
+                            // Apply negotiated frame checksums after framing the ack itself
+                            if let ConductorMessage::HandshakeAck { frame_checksums, .. } = message {
+                                enc.set_checksums(frame_checksums);
+                                decoder.set_verify_checksums(frame_checksums);
+                            }
+
+                            match encoded {
                                 Ok(frame) => {
                                     use tokio::io::AsyncWriteExt;
                                     drop(enc); // Release lock before async write
@@ -454,6 +529,35 @@ impl DaemonServer {
         );
     }
 
+    /// Tell a client why it was turned away, then hang up
+    ///
+    /// The refusal is sent as a rejected handshake marked temporary, so
+    /// surfaces show the reason and back off instead of giving up.
+    async fn reject_connection(mut stream: UnixStream, reason: String) {
+        use tokio::io::AsyncWriteExt;
+
+        let ack = ConductorMessage::HandshakeAck {
+            accepted: false,
+            connection_id: String::new(),
+            rejection_reason: Some(reason),
+            protocol_version: 1,
+            frame_checksums: false,
+            retry_after_ms: Some(FULL_RETRY_AFTER.as_millis() as u64),
+        };
+        match FrameEncoder::new().encode(&ack) {
+            Ok(frame) => {
+                let write = stream.write_all(&frame);
+                if tokio::time::timeout(Duration::from_secs(1), write)
+                    .await
+                    .is_err()
+                {
+                    debug!("Timed out telling a rejected client why");
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to encode rejection"),
+        }
+    }
+
     /// Reload configuration from file
     async fn reload_config(&mut self) -> Result<()> {
         if let Some(ref config_path) = self.config_path {
@@ -519,6 +623,69 @@ impl DaemonServer {
     }
 }
 
+/// Serializes config reloads
+///
+/// Only one reload runs at a time. Any number of requests made while a
+/// reload is in flight collapse into a single follow-up reload, so a burst
+/// of SIGHUPs never applies config out of order or more often than needed.
+#[derive(Debug, Default)]
+pub struct ReloadGate {
+    /// A reload has been requested and not yet started
+    requested: AtomicBool,
+    /// A reload is currently running
+    in_flight: AtomicBool,
+}
+
+impl ReloadGate {
+    /// Create a gate with no reload pending
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark config for reload
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether a reload is waiting to start
+    #[cfg(test)]
+    pub fn is_pending(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// Start a reload if one was requested and none is already running
+    ///
+    /// The reload counts as in flight until the returned guard is dropped.
+    pub fn begin(&self) -> Option<ReloadGuard<'_>> {
+        if self
+            .in_flight
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return None;
+        }
+
+        if self.requested.swap(false, Ordering::SeqCst) {
+            Some(ReloadGuard { gate: self })
+        } else {
+            self.in_flight.store(false, Ordering::SeqCst);
+            None
+        }
+    }
+}
+
+/// Marks a config reload as in flight until dropped
+#[derive(Debug)]
+pub struct ReloadGuard<'a> {
+    gate: &'a ReloadGate,
+}
+
+impl Drop for ReloadGuard<'_> {
+    fn drop(&mut self) {
+        self.gate.in_flight.store(false, Ordering::SeqCst);
+    }
+}
+
 /// Statistics for a single connection
 #[derive(Debug, Clone)]
 pub struct ConnectionStats {
@@ -578,5 +745,150 @@ mod tests {
         assert_eq!(config.max_connections, 100);
         assert_eq!(config.connection_channel_capacity, 256);
         assert_eq!(config.event_capacity, 256);
+        assert_eq!(config.watchdog_stall, Duration::from_secs(60));
+        assert_eq!(config.watchdog_action, StallAction::Log);
+    }
+
+    #[test]
+    fn test_server_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conductor.toml");
+        std::fs::write(&path, "[rate_limit]\nmax_total_connections = 3\n").unwrap();
+
+        let server = DaemonServer::new(dir.path().join("conductor.sock"), Some(path)).unwrap();
+        assert_eq!(server.server_config.max_connections, 3);
+    }
+
+    #[test]
+    fn test_conductor_config_from_file() {
+        use conductor_core::{AvatarMood, AvatarPosition};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conductor.toml");
+        std::fs::write(
+            &path,
+            "[avatar]\ncache_max_bytes = 1048576\ndefault_position = \"TopLeft\"\ndefault_mood = \"Calm\"\n",
+        )
+        .unwrap();
+
+        let server = DaemonServer::new(dir.path().join("conductor.sock"), Some(path)).unwrap();
+        let config = ConductorConfig::from_env_and_file(&server.file_config);
+        assert_eq!(config.default_avatar_position, AvatarPosition::TopLeft);
+        assert_eq!(config.default_avatar_mood, AvatarMood::Calm);
+        assert_eq!(config.avatar_cache_max_bytes, 1_048_576);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_connection_told_to_retry() {
+        use tokio::io::AsyncReadExt;
+
+        let (server_end, mut client_end) = UnixStream::pair().unwrap();
+        DaemonServer::reject_connection(server_end, "daemon full".to_string()).await;
+
+        let mut frame = Vec::new();
+        client_end.read_to_end(&mut frame).await.unwrap();
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&frame);
+        let ack = decoder.decode::<ConductorMessage>().unwrap();
+        assert!(matches!(
+            ack,
+            Some(ConductorMessage::HandshakeAck {
+                accepted: false,
+                rejection_reason: Some(ref reason),
+                retry_after_ms: Some(_),
+                ..
+            }) if reason == "daemon full"
+        ));
+    }
+
+    #[test]
+    fn test_reload_requests_coalesce() {
+        let gate = ReloadGate::new();
+        assert!(gate.begin().is_none(), "nothing requested yet");
+
+        for _ in 0..5 {
+            gate.request();
+        }
+        assert!(gate.begin().is_some());
+        assert!(gate.begin().is_none(), "burst should apply only once");
+    }
+
+    #[test]
+    fn test_reload_requested_mid_reload_runs_once_after() {
+        let gate = ReloadGate::new();
+        gate.request();
+
+        let guard = gate.begin().expect("reload should start");
+        gate.request();
+        gate.request();
+        assert!(gate.begin().is_none(), "only one reload may be in flight");
+        assert!(gate.is_pending());
+        drop(guard);
+
+        assert!(gate.begin().is_some(), "coalesced follow-up reload");
+        assert!(gate.begin().is_none());
+        assert!(!gate.is_pending());
+    }
+
+    #[test]
+    fn test_rapid_reload_requests_run_one_at_a_time_and_cover_the_last() {
+        use std::sync::atomic::AtomicUsize;
+
+        let gate = Arc::new(ReloadGate::new());
+        let written = Arc::new(AtomicUsize::new(0));
+        let done = Arc::new(AtomicBool::new(false));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+
+        let signallers: Vec<_> = (0..4)
+            .map(|_| {
+                let gate = Arc::clone(&gate);
+                let written = Arc::clone(&written);
+                std::thread::spawn(move || {
+                    for _ in 0..250 {
+                        written.fetch_add(1, Ordering::SeqCst);
+                        gate.request();
+                    }
+                })
+            })
+            .collect();
+
+        let reloaders: Vec<_> = (0..2)
+            .map(|_| {
+                let gate = Arc::clone(&gate);
+                let written = Arc::clone(&written);
+                let done = Arc::clone(&done);
+                let in_flight = Arc::clone(&in_flight);
+                std::thread::spawn(move || {
+                    let mut applied = Vec::new();
+                    loop {
+                        let finished = done.load(Ordering::SeqCst);
+                        if let Some(_reload) = gate.begin() {
+                            assert_eq!(in_flight.fetch_add(1, Ordering::SeqCst), 0);
+                            applied.push(written.load(Ordering::SeqCst));
+                            in_flight.fetch_sub(1, Ordering::SeqCst);
+                        } else if finished {
+                            return applied;
+                        }
+                        std::thread::yield_now();
+                    }
+                })
+            })
+            .collect();
+
+        for signaller in signallers {
+            signaller.join().unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+
+        let mut applied: Vec<usize> = reloaders
+            .into_iter()
+            .flat_map(|reloader| reloader.join().unwrap())
+            .collect();
+        applied.sort_unstable();
+
+        assert!(!applied.is_empty());
+        assert!(applied.len() <= 1000, "reloads should be coalesced");
+        assert_eq!(applied.last(), Some(&1000), "last request never reloaded");
+        assert!(!gate.is_pending());
     }
 }
@@ -0,0 +1,217 @@
+//! Main Loop Watchdog
+//!
+//! If a Conductor call gets stuck (say, a backend request that ignores its
+//! timeout) the daemon keeps its PID and socket but stops answering. The
+//! main loop beats a [`Heartbeat`] on every pass; a [`Watchdog`] task looks
+//! at it periodically and, once it hasn't moved for the stall threshold,
+//! logs a critical error and takes the configured [`StallAction`].
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use tracing::{error, info};
+
+/// Default time the main loop may go without a heartbeat, in seconds
+pub const DEFAULT_STALL_SECS: u64 = 60;
+
+/// Counter the main loop bumps to show it's still making progress
+#[derive(Clone, Debug, Default)]
+pub struct Heartbeat(Arc<AtomicU64>);
+
+impl Heartbeat {
+    /// Record one pass of the main loop
+    pub fn beat(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of passes so far
+    pub fn count(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// What to do when the main loop stalls
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum StallAction {
+    /// Only log the stall
+    #[default]
+    Log,
+    /// Shut down gracefully, leaving the restart to a supervisor
+    Shutdown,
+    /// Abort the process immediately
+    Abort,
+}
+
+/// Watches a [`Heartbeat`] and acts when it stops advancing
+pub struct Watchdog {
+    heartbeat: Heartbeat,
+    /// How long the heartbeat may stay still (zero = never watch)
+    stall_after: Duration,
+    action: StallAction,
+    /// Set to request a graceful shutdown
+    shutdown: Arc<AtomicBool>,
+    /// Heartbeat count when last checked
+    last_count: u64,
+    /// When the heartbeat last moved
+    last_beat_at: Instant,
+    /// The current stall has already been reported
+    stalled: bool,
+}
+
+impl Watchdog {
+    /// Watch `heartbeat`, taking `action` once it's still for `stall_after`
+    pub fn new(
+        heartbeat: Heartbeat,
+        stall_after: Duration,
+        action: StallAction,
+        shutdown: Arc<AtomicBool>,
+    ) -> Self {
+        let last_count = heartbeat.count();
+        Self {
+            heartbeat,
+            stall_after,
+            action,
+            shutdown,
+            last_count,
+            last_beat_at: Instant::now(),
+            stalled: false,
+        }
+    }
+
+    /// Check the heartbeat a few times per threshold, forever
+    pub async fn run(mut self) {
+        if self.stall_after.is_zero() {
+            return;
+        }
+
+        let mut interval = tokio::time::interval(self.stall_after / 4);
+        interval.tick().await; // First tick completes immediately
+
+        loop {
+            interval.tick().await;
+            if let Some(stuck_for) = self.check(Instant::now()) {
+                self.fire(stuck_for);
+            }
+        }
+    }
+
+    /// Look at the heartbeat at `now`
+    ///
+    /// Returns how long the loop has been stuck when it has just crossed
+    /// the threshold. A stall is reported once; the watchdog re-arms when
+    /// the heartbeat moves again.
+    fn check(&mut self, now: Instant) -> Option<Duration> {
+        let count = self.heartbeat.count();
+        if count != self.last_count {
+            if self.stalled {
+                info!("Main loop recovered from stall");
+            }
+            self.last_count = count;
+            self.last_beat_at = now;
+            self.stalled = false;
+            return None;
+        }
+
+        let stuck_for = now.saturating_duration_since(self.last_beat_at);
+        if self.stalled || self.stall_after.is_zero() || stuck_for < self.stall_after {
+            return None;
+        }
+        self.stalled = true;
+        Some(stuck_for)
+    }
+
+    /// Report a stall and take the configured action
+    fn fire(&self, stuck_for: Duration) {
+        error!(
+            stuck_secs = stuck_for.as_secs(),
+            action = ?self.action,
+            "Main loop stalled, daemon is unresponsive"
+        );
+        match self.action {
+            StallAction::Log => {}
+            StallAction::Shutdown => self.shutdown.store(true, Ordering::SeqCst),
+            StallAction::Abort => std::process::abort(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog(heartbeat: &Heartbeat, action: StallAction) -> (Watchdog, Arc<AtomicBool>) {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let watchdog = Watchdog::new(
+            heartbeat.clone(),
+            Duration::from_secs(10),
+            action,
+            Arc::clone(&shutdown),
+        );
+        (watchdog, shutdown)
+    }
+
+    #[test]
+    fn test_stall_detected_once_and_rearmed_by_heartbeat() {
+        let heartbeat = Heartbeat::default();
+        let (mut watchdog, _) = watchdog(&heartbeat, StallAction::Log);
+        let start = watchdog.last_beat_at;
+        let at = |secs| start + Duration::from_secs(secs);
+
+        // A beating loop is never reported
+        for secs in [4, 8, 12, 16] {
+            heartbeat.beat();
+            assert_eq!(watchdog.check(at(secs)), None);
+        }
+
+        // Still since 16s
+        assert_eq!(watchdog.check(at(25)), None);
+        assert_eq!(watchdog.check(at(26)), Some(Duration::from_secs(10)));
+        assert_eq!(watchdog.check(at(40)), None, "reported only once");
+
+        heartbeat.beat();
+        assert_eq!(watchdog.check(at(41)), None);
+        assert_eq!(watchdog.check(at(51)), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_actions() {
+        let heartbeat = Heartbeat::default();
+
+        let (log, shutdown) = watchdog(&heartbeat, StallAction::Log);
+        log.fire(Duration::from_secs(10));
+        assert!(!shutdown.load(Ordering::SeqCst));
+
+        let (graceful, shutdown) = watchdog(&heartbeat, StallAction::Shutdown);
+        graceful.fire(Duration::from_secs(10));
+        assert!(shutdown.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_loop_triggers_shutdown() {
+        let heartbeat = Heartbeat::default();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn(
+            Watchdog::new(
+                heartbeat.clone(),
+                Duration::from_millis(40),
+                StallAction::Shutdown,
+                Arc::clone(&shutdown),
+            )
+            .run(),
+        );
+
+        // Beating keeps the watchdog quiet
+        for _ in 0..10 {
+            heartbeat.beat();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!shutdown.load(Ordering::SeqCst));
+
+        // Then the loop gets stuck
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(shutdown.load(Ordering::SeqCst));
+        task.abort();
+    }
+}
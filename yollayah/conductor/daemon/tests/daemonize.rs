@@ -0,0 +1,80 @@
+//! Daemon Mode Integration Tests
+//!
+//! Runs the real `conductor-daemon --daemonize` binary and checks that the
+//! process left behind after forking still shuts down gracefully on SIGTERM.
+
+#![cfg(target_os = "linux")]
+
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Poll `check` until it passes or `timeout` runs out
+fn wait_for(timeout: Duration, mut check: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if check() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    check()
+}
+
+/// Whether a process has exited (gone, or a zombie nobody has reaped)
+fn has_exited(pid: i32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+        // The state follows the parenthesised command name
+        Ok(stat) => {
+            stat.rsplit(')')
+                .next()
+                .and_then(|rest| rest.split_whitespace().next())
+                == Some("Z")
+        }
+        Err(_) => true,
+    }
+}
+
+#[test]
+fn test_daemonized_process_shuts_down_on_sigterm() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("conductor.sock");
+    let pid_file = dir.path().join("conductor.pid");
+
+    // The launcher exits as soon as the daemon has forked away
+    let status = Command::new(env!("CARGO_BIN_EXE_conductor-daemon"))
+        .arg("--daemonize")
+        .arg("--socket-path")
+        .arg(&socket)
+        .arg("--pid-file")
+        .arg(&pid_file)
+        .args(["--watchdog-stall-secs", "0"])
+        .env("XDG_RUNTIME_DIR", dir.path())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // The PID file is written before the socket is bound
+    assert!(
+        wait_for(Duration::from_secs(10), || socket.exists()),
+        "daemon never started listening"
+    );
+    let pid: i32 = std::fs::read_to_string(&pid_file)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+
+    assert_eq!(unsafe { libc::kill(pid, libc::SIGTERM) }, 0);
+    let exited = wait_for(Duration::from_secs(10), || has_exited(pid));
+    if !exited {
+        unsafe { libc::kill(pid, libc::SIGKILL) };
+    }
+    assert!(exited, "daemon {pid} did not exit on SIGTERM");
+
+    // Graceful shutdown cleans up after itself
+    assert!(!pid_file.exists());
+    assert!(!socket.exists());
+}
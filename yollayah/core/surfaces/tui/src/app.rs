@@ -14,35 +14,58 @@
 //! 4. Renders based on DisplayState
 
 use std::io;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crossterm::event::{
     self, Event, EventStream, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind,
 };
 use futures::StreamExt;
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::{Backend, CrosstermBackend};
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::Terminal;
 
-use conductor_core::{ConductorMessage, ConductorState, ScrollDirection};
+use conductor_core::transport::TransportConfig;
+use conductor_core::{
+    AvatarActivity, ConductorMessage, ConductorState, ExportFormat, MessageId, MotionInterpolator,
+    NotifyLevel, ScrollDirection,
+};
 
 use crate::avatar::{Activity, Avatar, AvatarSize as TuiAvatarSize};
-use crate::compositor::{Compositor, LayerId};
+use crate::cli::Args;
+use crate::compositor::{Compositor, FrameDiff, FrameStats, LayerId};
 use crate::conductor_client::ConductorClient;
-use crate::display::{DisplayRole, DisplayState};
+use crate::display::{DisplayNotification, DisplayRole, DisplayState, NotificationPart};
+use crate::events::{apply_enter, insert_at_cursor, InputHistory, SubmitKey};
 use crate::theme::{
-    scroll_fade_color, scroll_fade_factor, ASSISTANT_PREFIX_COLOR, INDICATOR_AGENT_ACTIVE,
-    INDICATOR_AGENT_IDLE, INDICATOR_PROCESSING_ACTIVE, INPUT_TEXT_COLOR, METADATA_COLOR,
-    STATUS_READY_COLOR, STATUS_THINKING_COLOR, STREAMING_CURSOR_COLOR, USER_PREFIX_COLOR,
-    YOLLAYAH_MAGENTA,
+    mood_accent_color, scroll_fade_color, scroll_fade_factor, ERROR_RED, GUIDANCE_COLOR,
+    INDICATOR_AGENT_ACTIVE, INDICATOR_AGENT_IDLE, INDICATOR_PROCESSING_ACTIVE, INPUT_TEXT_COLOR,
+    METADATA_COLOR, STATUS_READY_COLOR, STATUS_THINKING_COLOR, STREAMING_CURSOR_COLOR,
+    USER_PREFIX_COLOR, YOLLAYAH_MAGENTA,
 };
+use crate::widgets::{AutoscrollMode, ConversationScroll};
 
 /// Input box height (lines) for text wrapping
 const INPUT_HEIGHT: u16 = 5;
 
-/// Maximum number of input history entries to keep
-const MAX_INPUT_HISTORY: usize = 50;
+/// Reasoning pane size (header plus lines of reasoning)
+const REASONING_PANE_WIDTH: u16 = 48;
+const REASONING_PANE_HEIGHT: u16 = 8;
+
+/// Spinner shown while the main model loads, a frame per half second
+const WARMUP_SPINNER: [&str; 4] = ["◐", "◓", "◑", "◒"];
+
+/// Frame time while anything is happening (~10 FPS)
+const ACTIVE_FRAME_DURATION: Duration = Duration::from_millis(100);
+
+/// Frame time once the UI has been idle for a while (~4 FPS)
+const IDLE_FRAME_DURATION: Duration = Duration::from_millis(250);
+
+/// Quiet frames before backing off to `IDLE_FRAME_DURATION`
+const IDLE_FRAMES_BEFORE_BACKOFF: u32 = 20;
+
+/// Input that opens the avatar command reference instead of being sent
+const COMMANDS_VIEW: &str = "/commands";
 
 /// Quick goodbye messages (no LLM needed, instant)
 const QUICK_GOODBYES: &[&str] = &[
@@ -87,16 +110,12 @@ pub struct App {
     input_buffer: String,
     /// Cursor position within input buffer (char index)
     cursor_pos: usize,
-    /// Input history (most recent at end)
-    input_history: Vec<String>,
-    /// Current position in history (None = current input, Some(i) = history[i])
-    history_index: Option<usize>,
-    /// Temporary storage for current input when browsing history
-    history_draft: String,
-    /// Scroll offset (lines from bottom, 0 = latest)
-    scroll_offset: usize,
-    /// Total rendered lines (for scroll bounds)
-    total_lines: usize,
+    /// Sent prompts, recalled with Up/Down
+    input_history: InputHistory,
+    /// Conversation scroll position (holds still while the user reads back)
+    scroll: ConversationScroll,
+    /// Whether the Conductor last heard the view was at the bottom
+    reported_at_bottom: bool,
     /// Previous input state for dirty tracking
     prev_input_buffer: String,
     prev_cursor_pos: usize,
@@ -104,18 +123,23 @@ pub struct App {
     prev_conductor_state: ConductorState,
     prev_task_count: usize,
     prev_scroll_offset: usize,
+    prev_unseen_below: bool,
+    prev_warmup_elapsed_ms: Option<u64>,
+    prev_unread_elsewhere: u32,
     /// Previous tasks state for dirty tracking (simple hash)
     prev_tasks_hash: u64,
+    /// Reasoning pane contents last rendered (dirty tracking)
+    prev_reasoning: Option<(Option<MessageId>, usize)>,
     /// Conversation dirty tracking (Sprint 2 optimization)
     conversation_dirty: bool,
     last_render_width: usize,
     cached_conversation_lines: Vec<LineMeta>,
 
     // === Avatar Rendering State ===
-    /// Avatar position (x, y)
+    /// Avatar cell (x, y), the motion position rounded each frame
     avatar_pos: (u16, u16),
-    /// Avatar target position for smooth movement
-    avatar_target: (u16, u16),
+    /// Sub-cell avatar position gliding towards its target
+    avatar_motion: MotionInterpolator,
     /// Time until next wander
     wander_timer: Duration,
     /// Whether avatar animation changed this frame (for dirty tracking)
@@ -126,6 +150,12 @@ pub struct App {
     last_frame: Instant,
     /// Developer mode
     dev_mode: bool,
+    /// Last frame flushed to the terminal, for writing only changed cells
+    frame_diff: FrameDiff,
+    /// Which Enter combination sends the input
+    submit_key: SubmitKey,
+    /// Which events snap the conversation back to the bottom
+    autoscroll: AutoscrollMode,
     /// Terminal size
     size: (u16, u16),
 }
@@ -137,6 +167,7 @@ struct AppLayers {
     input: LayerId,
     status: LayerId,
     avatar: LayerId,
+    reasoning: LayerId,
 }
 
 /// Line metadata for conversation rendering
@@ -152,49 +183,77 @@ struct LineMeta {
 impl App {
     /// Create a new App instance
     pub async fn new() -> anyhow::Result<Self> {
+        Self::with_args(&Args::default()).await
+    }
+
+    /// Create a new App instance configured by command-line flags
+    pub async fn with_args(args: &Args) -> anyhow::Result<Self> {
         let size = crossterm::terminal::size()?;
         let area = Rect::new(0, 0, size.0, size.1);
 
         let mut compositor = Compositor::new(area);
+        let layer_limit = || anyhow::anyhow!("Compositor layer limit reached");
 
         // Create layers with z-ordering
         let input_and_status_height = INPUT_HEIGHT + 1;
-        let conversation = compositor.create_layer(
-            Rect::new(
-                0,
+        let conversation = compositor
+            .create_layer(
+                Rect::new(
+                    0,
+                    0,
+                    area.width,
+                    area.height.saturating_sub(input_and_status_height),
+                ),
                 0,
-                area.width,
-                area.height.saturating_sub(input_and_status_height),
-            ),
-            0,
-        );
-
-        let input = compositor.create_layer(
-            Rect::new(
-                0,
-                area.height.saturating_sub(input_and_status_height),
-                area.width,
-                INPUT_HEIGHT,
-            ),
-            10,
-        );
-
-        let status = compositor.create_layer(
-            Rect::new(0, area.height.saturating_sub(1), area.width, 1),
-            10,
-        );
+            )
+            .ok_or_else(layer_limit)?;
+
+        let input = compositor
+            .create_layer(
+                Rect::new(
+                    0,
+                    area.height.saturating_sub(input_and_status_height),
+                    area.width,
+                    INPUT_HEIGHT,
+                ),
+                10,
+            )
+            .ok_or_else(layer_limit)?;
+
+        let status = compositor
+            .create_layer(
+                Rect::new(0, area.height.saturating_sub(1), area.width, 1),
+                10,
+            )
+            .ok_or_else(layer_limit)?;
 
         // Task panel layer - right side
         let task_panel_width = 32u16;
-        let tasks_layer = compositor.create_layer(
-            Rect::new(
-                area.width.saturating_sub(task_panel_width),
-                0,
-                task_panel_width,
-                area.height.saturating_sub(input_and_status_height),
-            ),
-            25,
-        );
+        let tasks_layer = compositor
+            .create_layer(
+                Rect::new(
+                    area.width.saturating_sub(task_panel_width),
+                    0,
+                    task_panel_width,
+                    area.height.saturating_sub(input_and_status_height),
+                ),
+                25,
+            )
+            .ok_or_else(layer_limit)?;
+
+        // Reasoning pane - top left, over the oldest visible lines
+        let reasoning = compositor
+            .create_layer(
+                Rect::new(
+                    0,
+                    0,
+                    area.width.min(REASONING_PANE_WIDTH),
+                    REASONING_PANE_HEIGHT,
+                ),
+                20,
+            )
+            .ok_or_else(layer_limit)?;
+        compositor.set_visible(reasoning, false);
 
         // Avatar layer
         let avatar_bounds = Rect::new(
@@ -203,7 +262,9 @@ impl App {
             24,
             6,
         );
-        let avatar_layer = compositor.create_layer(avatar_bounds, 50);
+        let avatar_layer = compositor
+            .create_layer(avatar_bounds, 50)
+            .ok_or_else(layer_limit)?;
 
         let layers = AppLayers {
             conversation,
@@ -211,48 +272,66 @@ impl App {
             input,
             status,
             avatar: avatar_layer,
+            reasoning,
         };
 
-        let avatar = Avatar::new();
-
         // Initial avatar position
         let avatar_x = area.width.saturating_sub(26);
         let avatar_y = area.height.saturating_sub(input_and_status_height + 8);
 
         // Create conductor client
-        let conductor = ConductorClient::new();
+        let conductor_config = args.conductor_config();
+        let avatar_motion = MotionInterpolator::new(
+            f32::from(avatar_x),
+            f32::from(avatar_y),
+            conductor_config.avatar_move_speed,
+        );
+        let conductor =
+            ConductorClient::with_conductor_config(TransportConfig::embedded(), conductor_config);
+
+        // Monochrome terminals get the ASCII density avatar
+        let mut avatar = Avatar::new();
+        avatar.set_color(conductor.capabilities().color);
 
         let now = Instant::now();
         Ok(Self {
             running: true,
             goodbye_message: None,
             conductor,
-            display: DisplayState::new(),
+            display: DisplayState {
+                grouping: !args.no_grouping,
+                ..DisplayState::new()
+            },
             compositor,
             avatar,
             layers,
             input_buffer: String::new(),
             cursor_pos: 0,
-            input_history: Vec::new(),
-            history_index: None,
-            history_draft: String::new(),
-            scroll_offset: 0,
-            total_lines: 0,
+            input_history: args.input_history(),
+            scroll: ConversationScroll::new(),
+            reported_at_bottom: true,
             prev_input_buffer: String::new(),
             prev_cursor_pos: 0,
             prev_conductor_state: ConductorState::Initializing,
             prev_task_count: 0,
             prev_scroll_offset: 0,
+            prev_unseen_below: false,
+            prev_warmup_elapsed_ms: None,
+            prev_unread_elsewhere: 0,
             prev_tasks_hash: 0,
+            prev_reasoning: None,
             conversation_dirty: true, // Start dirty to render on first frame
             last_render_width: 0,
             cached_conversation_lines: Vec::new(),
             avatar_pos: (avatar_x, avatar_y),
-            avatar_target: (avatar_x, avatar_y),
+            avatar_motion,
             wander_timer: Duration::from_secs(5),
             avatar_changed: true, // Start dirty to render on first frame
             last_frame: now,
             dev_mode: false,
+            frame_diff: FrameDiff::new(),
+            submit_key: args.submit_key,
+            autoscroll: args.autoscroll_mode,
             size: (size.0, size.1),
         })
     }
@@ -262,8 +341,9 @@ impl App {
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ) -> anyhow::Result<()> {
-        // Target ~10 FPS for terminal-style animations
-        let frame_duration = Duration::from_millis(100);
+        // Target ~10 FPS for terminal-style animations, less when idle
+        let mut idle_frames: u32 = 0;
+        let mut frame_duration = ACTIVE_FRAME_DURATION;
 
         // Create async event stream for non-blocking terminal events
         let mut event_stream = EventStream::new();
@@ -280,7 +360,7 @@ impl App {
         self.render(terminal)?;
 
         while self.running {
-            let frame_start = Instant::now();
+            let mut activity = false;
 
             // Use select to handle events WHILE doing startup
             // This ensures we remain responsive even during slow startup
@@ -290,6 +370,7 @@ impl App {
                 // Check for terminal events - highest priority
                 maybe_event = event_stream.next() => {
                     if let Some(Ok(event)) = maybe_event {
+                        activity = true;
                         match event {
                             // Only handle Press events (not Release or Repeat)
                             Event::Key(key) if key.kind == KeyEventKind::Press => {
@@ -304,7 +385,8 @@ impl App {
 
                 // REACTIVE STREAMING: Process tokens as they arrive
                 // This replaces the polling anti-pattern (poll_streaming())
-                _ = self.conductor.process_streaming_token() => {
+                processed = self.conductor.process_streaming_token() => {
+                    activity |= processed;
                     // Token was processed by conductor, which sent messages to UI
                     // Process those messages immediately
                     self.process_conductor_messages();
@@ -316,8 +398,9 @@ impl App {
                     }
                 }
 
-                // Frame tick - do work and render (10 FPS = 100ms)
-                _ = tokio::time::sleep(Duration::from_millis(100)) => {
+                // Frame tick - do work and render (slower once idle; key
+                // events above still wake the loop straight away)
+                _ = tokio::time::sleep(frame_duration) => {
                     // Handle startup phases incrementally
                     match startup_phase {
                         StartupPhase::NeedStart => {
@@ -351,17 +434,42 @@ impl App {
                                 }
                             }
                         }
-                        StartupPhase::Done => {}
+                        StartupPhase::Done => {
+                            // Timed work the reactive token path doesn't cover
+                            // (queued gestures, blinks, warmup); tokens stay
+                            // with process_streaming_token above
+                            let outcome = self.conductor.tick().await;
+                            activity |= !outcome.is_idle();
+                        }
                     }
                 }
             }
 
             // Process conductor messages (non-streaming control messages)
             // Streaming tokens are now handled reactively in tokio::select! above
-            self.process_conductor_messages();
+            activity |= self.process_conductor_messages();
+
+            // Missed tokens (sequence gap) - ask for the authoritative state
+            if self.display.take_resync_request() {
+                let _ = self.conductor.request_snapshot().await;
+            }
+            if let Some((format, content)) = self.display.take_export() {
+                self.save_export(format, &content);
+            }
 
             // Update animations and display state
             self.update();
+            // Keep the frame rate up while the avatar glides
+            activity |= self.avatar_motion.is_moving();
+            for animation in self.display.take_finished_animations() {
+                let _ = self.conductor.animation_complete(animation).await;
+            }
+            // Lets the avatar point out responses that finish off-screen
+            let at_bottom = self.scroll.is_following();
+            if at_bottom != self.reported_at_bottom {
+                self.reported_at_bottom = at_bottom;
+                let _ = self.conductor.scroll_position_changed(at_bottom).await;
+            }
 
             // Render frame (streaming renders happen immediately in select!)
             self.render(terminal)?;
@@ -371,24 +479,42 @@ impl App {
                 self.running = false;
             }
 
-            // Frame rate limiting
-            let elapsed = frame_start.elapsed();
-            if elapsed < frame_duration {
-                tokio::time::sleep(frame_duration - elapsed).await;
-            }
+            // Frame rate limiting - back off when nothing has happened for a while
+            idle_frames = if activity {
+                0
+            } else {
+                idle_frames.saturating_add(1)
+            };
+            // A static avatar has nothing left to animate, so don't wait out the backoff
+            let settled = !activity && self.avatar.is_static() && !self.display.avatar.wandering;
+            frame_duration = if settled || idle_frames >= IDLE_FRAMES_BEFORE_BACKOFF {
+                IDLE_FRAME_DURATION
+            } else {
+                ACTIVE_FRAME_DURATION
+            };
         }
 
         Ok(())
     }
 
     /// Process all pending messages from the Conductor
-    fn process_conductor_messages(&mut self) {
+    ///
+    /// Returns true if any messages were received.
+    fn process_conductor_messages(&mut self) -> bool {
         let mut any_messages = false;
         for msg in self.conductor.recv_all() {
             // Check for quit message before applying
             if let ConductorMessage::Quit { message } = &msg {
                 self.goodbye_message = message.clone();
             }
+            // The start (or first token) of a response that isn't streaming yet
+            if let ConductorMessage::StreamStart { message_id, .. }
+            | ConductorMessage::Token { message_id, .. } = &msg
+            {
+                if self.display.streaming_id.as_ref() != Some(message_id) {
+                    self.scroll.on_stream_start(self.autoscroll);
+                }
+            }
 
             // Apply message to display state
             self.display.apply_message(msg);
@@ -400,6 +526,41 @@ impl App {
         if any_messages {
             self.conversation_dirty = true;
         }
+        any_messages
+    }
+
+    /// Tell the Conductor the task panel view so it's restored on reconnect
+    async fn save_task_view(&mut self) {
+        let view = self.display.task_view;
+        let _ = self.conductor.set_task_view(view.sort, view.filter).await;
+    }
+
+    /// Save a `/history` export to the working directory and say where
+    fn save_export(&mut self, format: ExportFormat, content: &str) {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = format!("yollayah-conversation-{stamp}.{}", format.extension());
+        let (level, message) = match std::fs::write(&path, content) {
+            Ok(()) => (
+                NotifyLevel::Success,
+                format!("Saved the conversation to {path}"),
+            ),
+            Err(e) => {
+                tracing::warn!(path = %path, error = %e, "Couldn't save conversation export");
+                (
+                    NotifyLevel::Error,
+                    format!("Couldn't save the conversation to {path}: {e}"),
+                )
+            }
+        };
+        self.display.notification = Some(DisplayNotification {
+            level,
+            title: None,
+            message,
+            guidance: None,
+            count: 1,
+        });
     }
 
     /// Handle keyboard input
@@ -419,24 +580,27 @@ impl App {
 
             // Submit message
             KeyCode::Enter => {
-                if !self.input_buffer.is_empty() {
-                    let message = std::mem::take(&mut self.input_buffer);
-                    self.cursor_pos = 0;
-
-                    // Save to history (avoid duplicates of the last entry)
-                    if self.input_history.last() != Some(&message) {
-                        self.input_history.push(message.clone());
-                        // Limit history size
-                        if self.input_history.len() > MAX_INPUT_HISTORY {
-                            self.input_history.remove(0);
-                        }
+                let submitted = apply_enter(
+                    self.submit_key,
+                    &key,
+                    &mut self.input_buffer,
+                    &mut self.cursor_pos,
+                );
+                if let Some(message) = submitted {
+                    self.input_history.push(message.clone());
+                    // Whatever went wrong before is stale once the user moves on
+                    if self.display.notification.take().is_some() {
+                        self.conversation_dirty = true;
                     }
-                    // Reset history navigation state
-                    self.history_index = None;
-                    self.history_draft.clear();
 
-                    let _ = self.conductor.send_message(message).await;
-                    self.scroll_offset = 0;
+                    if message.trim() == COMMANDS_VIEW {
+                        // Shown locally; the Conductor never sees it
+                        self.display.show_command_reference();
+                        self.conversation_dirty = true;
+                    } else {
+                        let _ = self.conductor.send_message(message).await;
+                    }
+                    self.scroll.on_send(self.autoscroll);
                 }
             }
 
@@ -446,6 +610,16 @@ impl App {
                 self.input_buffer.clear();
                 self.cursor_pos = 0;
             }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Cycle task panel sort order
+                self.display.task_view.cycle_sort();
+                self.save_task_view().await;
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Toggle hiding completed tasks
+                self.display.task_view.toggle_filter();
+                self.save_task_view().await;
+            }
             KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 // Delete word backwards
                 if self.cursor_pos > 0 {
@@ -469,15 +643,7 @@ impl App {
 
             // Typing - insert at cursor position
             KeyCode::Char(c) => {
-                // Insert character at cursor position
-                let byte_pos = self
-                    .input_buffer
-                    .char_indices()
-                    .nth(self.cursor_pos)
-                    .map(|(i, _)| i)
-                    .unwrap_or(self.input_buffer.len());
-                self.input_buffer.insert(byte_pos, c);
-                self.cursor_pos += 1;
+                insert_at_cursor(&mut self.input_buffer, &mut self.cursor_pos, c);
                 let _ = self.conductor.user_typing(true).await;
             }
 
@@ -551,45 +717,17 @@ impl App {
                 }
             }
 
-            // Input history navigation
+            // Input history navigation (the draft comes back past the newest)
             KeyCode::Up => {
-                if !self.input_history.is_empty() {
-                    match self.history_index {
-                        None => {
-                            // Save current input as draft and go to most recent history
-                            self.history_draft = self.input_buffer.clone();
-                            let idx = self.input_history.len() - 1;
-                            self.history_index = Some(idx);
-                            self.input_buffer = self.input_history[idx].clone();
-                            self.cursor_pos = self.input_buffer.chars().count();
-                        }
-                        Some(idx) if idx > 0 => {
-                            // Go to older history entry
-                            let new_idx = idx - 1;
-                            self.history_index = Some(new_idx);
-                            self.input_buffer = self.input_history[new_idx].clone();
-                            self.cursor_pos = self.input_buffer.chars().count();
-                        }
-                        _ => {
-                            // Already at oldest entry, do nothing
-                        }
-                    }
+                if let Some(entry) = self.input_history.older(&self.input_buffer) {
+                    self.input_buffer = entry.to_string();
+                    self.cursor_pos = self.input_buffer.chars().count();
                 }
             }
             KeyCode::Down => {
-                if let Some(idx) = self.history_index {
-                    if idx + 1 < self.input_history.len() {
-                        // Go to newer history entry
-                        let new_idx = idx + 1;
-                        self.history_index = Some(new_idx);
-                        self.input_buffer = self.input_history[new_idx].clone();
-                        self.cursor_pos = self.input_buffer.chars().count();
-                    } else {
-                        // Return to current draft
-                        self.history_index = None;
-                        self.input_buffer = std::mem::take(&mut self.history_draft);
-                        self.cursor_pos = self.input_buffer.chars().count();
-                    }
+                if let Some(entry) = self.input_history.newer() {
+                    self.input_buffer = entry;
+                    self.cursor_pos = self.input_buffer.chars().count();
                 }
             }
 
@@ -603,8 +741,7 @@ impl App {
             // Conversation scrolling
             KeyCode::PageUp => {
                 let page_size = self.size.1.saturating_sub(INPUT_HEIGHT + 1) / 2;
-                let max_scroll = self.total_lines.saturating_sub(1);
-                self.scroll_offset = (self.scroll_offset + page_size as usize).min(max_scroll);
+                self.scroll.scroll_up(page_size as usize);
                 let _ = self
                     .conductor
                     .user_scrolled(ScrollDirection::Up, page_size as u32)
@@ -612,18 +749,18 @@ impl App {
             }
             KeyCode::PageDown => {
                 let page_size = self.size.1.saturating_sub(INPUT_HEIGHT + 1) / 2;
-                self.scroll_offset = self.scroll_offset.saturating_sub(page_size as usize);
+                self.scroll.scroll_down(page_size as usize);
                 let _ = self
                     .conductor
                     .user_scrolled(ScrollDirection::Down, page_size as u32)
                     .await;
             }
             KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_offset = self.total_lines.saturating_sub(1);
+                self.scroll.scroll_to_top();
                 let _ = self.conductor.user_scrolled(ScrollDirection::Top, 0).await;
             }
             KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_offset = 0;
+                self.scroll.scroll_to_bottom();
                 let _ = self
                     .conductor
                     .user_scrolled(ScrollDirection::Bottom, 0)
@@ -643,13 +780,11 @@ impl App {
     async fn handle_mouse(&mut self, mouse: event::MouseEvent) {
         match mouse.kind {
             MouseEventKind::ScrollUp => {
-                if self.scroll_offset < self.total_lines.saturating_sub(1) {
-                    self.scroll_offset += 3;
-                }
+                self.scroll.scroll_up(3);
                 let _ = self.conductor.user_scrolled(ScrollDirection::Up, 3).await;
             }
             MouseEventKind::ScrollDown => {
-                self.scroll_offset = self.scroll_offset.saturating_sub(3);
+                self.scroll.scroll_down(3);
                 let _ = self.conductor.user_scrolled(ScrollDirection::Down, 3).await;
             }
             _ => {}
@@ -697,15 +832,21 @@ impl App {
             height.saturating_sub(input_and_status_height),
         );
 
+        // Reasoning pane
+        self.compositor.resize_layer(
+            self.layers.reasoning,
+            width.min(REASONING_PANE_WIDTH),
+            REASONING_PANE_HEIGHT,
+        );
+        self.prev_reasoning = None;
+
         // Avatar bounds update
         let (bounds_w, bounds_h) = self.avatar.bounds();
         let max_x = width.saturating_sub(bounds_w + 2);
         let max_y = height.saturating_sub(bounds_h + input_and_status_height + 2);
-        self.avatar_target = (
-            self.avatar_target.0.min(max_x),
-            self.avatar_target.1.min(max_y),
-        );
-        self.avatar_pos = (self.avatar_pos.0.min(max_x), self.avatar_pos.1.min(max_y));
+        self.avatar_motion
+            .clamp_to(f32::from(max_x), f32::from(max_y));
+        self.avatar_pos = self.avatar_motion.nearest_cell();
 
         let _ = self.conductor.resized(width as u32, height as u32).await;
     }
@@ -741,7 +882,8 @@ impl App {
         }
 
         // Smoothly move towards target
-        self.move_towards_target();
+        self.avatar_motion.step(delta);
+        self.avatar_pos = self.avatar_motion.nearest_cell();
 
         // Update layer visibility
         self.compositor
@@ -777,10 +919,11 @@ impl App {
         };
         self.avatar.set_size(size);
 
-        // Set activity overlay based on conductor state
-        let activity = match self.display.conductor_state {
-            ConductorState::Thinking | ConductorState::Responding => Activity::Thinking,
-            _ => Activity::None,
+        // Set activity overlay as directed by the Conductor
+        let activity = match self.display.avatar.activity {
+            AvatarActivity::None => Activity::None,
+            AvatarActivity::Thinking => Activity::Thinking,
+            AvatarActivity::Talking => Activity::Talking,
         };
         self.avatar.set_activity(activity);
     }
@@ -808,7 +951,7 @@ impl App {
                 (x.max(2), y.max(1))
             }
         };
-        self.avatar_target = (x, y);
+        self.avatar_motion.set_target(f32::from(x), f32::from(y));
     }
 
     /// Pick a new random position for Yollayah to wander to
@@ -839,31 +982,7 @@ impl App {
             (x, y)
         };
 
-        self.avatar_target = (x, y);
-    }
-
-    /// Smoothly move avatar towards target position
-    fn move_towards_target(&mut self) {
-        let (cx, cy) = self.avatar_pos;
-        let (tx, ty) = self.avatar_target;
-
-        let new_x = if cx < tx {
-            (cx + 1).min(tx)
-        } else if cx > tx {
-            cx.saturating_sub(1).max(tx)
-        } else {
-            cx
-        };
-
-        let new_y = if cy < ty {
-            (cy + 1).min(ty)
-        } else if cy > ty {
-            cy.saturating_sub(1).max(ty)
-        } else {
-            cy
-        };
-
-        self.avatar_pos = (new_x, new_y);
+        self.avatar_motion.set_target(f32::from(x), f32::from(y));
     }
 
     /// Render the UI
@@ -873,21 +992,34 @@ impl App {
     ) -> anyhow::Result<()> {
         self.render_conversation();
         self.render_tasks();
+        self.render_reasoning();
         self.render_input();
         self.render_status();
         self.render_avatar();
 
-        terminal.draw(|frame| {
-            let output = self.compositor.composite();
-
-            // Use Ratatui's Buffer::merge() instead of cell-by-cell cloning
-            // This leverages the framework's bulk operations and avoids 10k+ Cell::clone() calls
-            frame.buffer_mut().merge(output);
-        })?;
+        // Diff against the last flushed frame ourselves and hand crossterm
+        // only the changed cells, rather than copying the whole composite
+        // into a ratatui frame every tick
+        let output = self.compositor.composite();
+        let delta = self.frame_diff.diff(output);
+        if delta.full_redraw {
+            terminal.clear()?;
+            terminal.hide_cursor()?;
+        }
+        if !delta.cells.is_empty() {
+            let backend = terminal.backend_mut();
+            backend.draw(delta.cells.into_iter())?;
+            Backend::flush(backend)?;
+        }
 
         Ok(())
     }
 
+    /// How many cells the render path has written to the terminal
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_diff.stats()
+    }
+
     /// Render conversation layer
     fn render_conversation(&mut self) {
         let width = self.size.0.saturating_sub(2) as usize;
@@ -910,18 +1042,24 @@ impl App {
         // ❌ Cache miss - rebuild lines from messages
         let mut all_lines: Vec<LineMeta> = Vec::new();
 
-        for msg in &self.display.messages {
-            let (prefix, base_style) = match msg.role {
-                DisplayRole::User => ("You: ", Style::default().fg(Color::Green)),
-                DisplayRole::Assistant => ("Yollayah: ", Style::default().fg(YOLLAYAH_MAGENTA)),
-                DisplayRole::System => ("", Style::default().fg(Color::DarkGray)),
+        for group in self.display.message_groups() {
+            let msg = group.message;
+            let base_style = match msg.role {
+                DisplayRole::User => Style::default().fg(Color::Green),
+                DisplayRole::Assistant => Style::default().fg(YOLLAYAH_MAGENTA),
+                DisplayRole::System => Style::default().fg(Color::DarkGray),
+                DisplayRole::Welcome => Style::default()
+                    .fg(YOLLAYAH_MAGENTA)
+                    .add_modifier(Modifier::ITALIC),
             };
 
+            // A turn's later replies sit under its first header
+            let prefix = group.prefix();
             let prefix_len = prefix.len();
             let content = if msg.streaming {
-                format!("{}{}_", prefix, msg.content)
+                format!("{}{}_", prefix, group.content())
             } else {
-                format!("{}{}", prefix, msg.content)
+                format!("{}{}", prefix, group.content())
             };
 
             // ✅ Use cached wrapping - saves 1200+ textwrap::wrap() calls/sec
@@ -960,6 +1098,30 @@ impl App {
             });
         }
 
+        // Latest notification goes last, with its guidance styled apart
+        if let Some(ref notification) = self.display.notification {
+            let message_style = match notification.level {
+                NotifyLevel::Error => Style::default().fg(ERROR_RED),
+                NotifyLevel::Warning => Style::default().fg(Color::Yellow),
+                NotifyLevel::Success | NotifyLevel::Info => Style::default().fg(Color::DarkGray),
+            };
+            let guidance_style = Style::default()
+                .fg(GUIDANCE_COLOR)
+                .add_modifier(Modifier::ITALIC);
+            for (part, text) in notification.lines(width) {
+                all_lines.push(LineMeta {
+                    text,
+                    base_style: match part {
+                        NotificationPart::Message => message_style,
+                        NotificationPart::Guidance => guidance_style,
+                    },
+                    prefix_len: 0,
+                    role: None,
+                    is_streaming: false,
+                });
+            }
+        }
+
         // ✅ OPTIMIZATION (Sprint 2): Cache the built lines for next frame
         self.cached_conversation_lines = all_lines;
         self.last_render_width = width;
@@ -971,25 +1133,21 @@ impl App {
 
     /// Render conversation using cached lines (Sprint 2 optimization)
     fn render_cached_conversation_lines(&mut self, height: usize) {
-        self.total_lines = self.cached_conversation_lines.len();
-
-        // Clamp scroll offset
-        let max_scroll = self.total_lines.saturating_sub(height);
-        if self.scroll_offset > max_scroll {
-            self.scroll_offset = max_scroll;
-        }
+        // Keeps the viewport in place if the user has scrolled up
+        self.scroll.set_total_lines(self.cached_conversation_lines.len(), height);
 
         // Calculate visible range
-        let visible_end = self.total_lines.saturating_sub(self.scroll_offset);
+        let visible_end = self.scroll.total_lines().saturating_sub(self.scroll.offset());
         let visible_start = visible_end.saturating_sub(height);
 
         let has_content_above = visible_start > 0;
-        let has_content_below = self.scroll_offset > 0;
+        let has_content_below = !self.scroll.is_following();
 
         if let Some(buf) = self.compositor.layer_buffer_mut(self.layers.conversation) {
             buf.reset();
             let area = buf.area;
 
+            let assistant_color = mood_accent_color(self.display.avatar.mood);
             let visible_lines: Vec<_> = self
                 .cached_conversation_lines
                 .iter()
@@ -1037,10 +1195,12 @@ impl App {
                                 // Brighter for streaming messages
                                 STREAMING_CURSOR_COLOR
                             } else {
-                                ASSISTANT_PREFIX_COLOR
+                                // Follows the avatar's mood
+                                assistant_color
                             }
                         }
                         DisplayRole::System => Color::DarkGray,
+                        DisplayRole::Welcome => YOLLAYAH_MAGENTA,
                     };
 
                     // Render prefix with static color
@@ -1159,7 +1319,10 @@ impl App {
         // Check if status changed
         let status_changed = self.display.conductor_state != self.prev_conductor_state
             || active_task_count != self.prev_task_count
-            || self.scroll_offset != self.prev_scroll_offset;
+            || self.scroll.offset() != self.prev_scroll_offset
+            || self.scroll.has_unseen_below() != self.prev_unseen_below
+            || self.display.warmup_elapsed_ms != self.prev_warmup_elapsed_ms
+            || self.display.unread_elsewhere() != self.prev_unread_elsewhere;
 
         // Only render if status changed
         if status_changed {
@@ -1248,9 +1411,27 @@ impl App {
             buf.set_string(x_pos, area.y, state_str, status_style);
             x_pos += state_str.len() as u16;
 
+            // Loading indicator while the main model warms up
+            if let Some(elapsed_ms) = self.display.warmup_elapsed_ms {
+                let frame = WARMUP_SPINNER[(elapsed_ms / 500) as usize % WARMUP_SPINNER.len()];
+                let loading = format!(" {frame} Loading model ({}s)", elapsed_ms / 1000);
+                buf.set_string(x_pos, area.y, &loading, Style::default().fg(YOLLAYAH_MAGENTA));
+                x_pos += loading.chars().count() as u16;
+            }
+
+            // Responses waiting in other conversations
+            let unread = self.display.unread_elsewhere();
+            if unread > 0 {
+                let waiting = format!(" ✉ {unread} unread");
+                buf.set_string(x_pos, area.y, &waiting, Style::default().fg(YOLLAYAH_MAGENTA));
+                x_pos += waiting.chars().count() as u16;
+            }
+
             // Rest of status bar
-            let scroll_info = if self.scroll_offset > 0 {
-                format!(" [^{} lines]", self.scroll_offset)
+            let scroll_info = if self.scroll.has_unseen_below() {
+                format!(" [^{} lines] new messages ↓", self.scroll.offset())
+            } else if !self.scroll.is_following() {
+                format!(" [^{} lines]", self.scroll.offset())
             } else {
                 String::new()
             };
@@ -1270,7 +1451,10 @@ impl App {
             // Update previous state
             self.prev_conductor_state = self.display.conductor_state;
             self.prev_task_count = active_task_count;
-            self.prev_scroll_offset = self.scroll_offset;
+            self.prev_scroll_offset = self.scroll.offset();
+            self.prev_unseen_below = self.scroll.has_unseen_below();
+            self.prev_warmup_elapsed_ms = self.display.warmup_elapsed_ms;
+            self.prev_unread_elsewhere = self.display.unread_elsewhere();
         }
     }
 
@@ -1292,9 +1476,9 @@ impl App {
     fn render_tasks(&mut self) {
         let tasks: Vec<_> = self
             .display
-            .tasks
-            .iter()
-            .filter(|t| t.status.is_active())
+            .task_view
+            .apply(&self.display.tasks)
+            .into_iter()
             .cloned()
             .collect();
 
@@ -1322,6 +1506,46 @@ impl App {
         }
     }
 
+    /// Render the reasoning pane while a response streams its reasoning
+    fn render_reasoning(&mut self) {
+        let shown = self.display.live_reasoning().map(|_| {
+            (
+                self.display.reasoning_id.clone(),
+                self.display.reasoning.len(),
+            )
+        });
+        if shown == self.prev_reasoning {
+            return;
+        }
+        self.compositor
+            .set_visible(self.layers.reasoning, shown.is_some());
+
+        if let Some(buf) = self.compositor.layer_buffer_mut(self.layers.reasoning) {
+            buf.reset();
+            let area = buf.area;
+            let width = area.width.saturating_sub(2) as usize;
+            let height = area.height.saturating_sub(1) as usize;
+            let lines = self.display.live_reasoning_lines(width, height);
+            if !lines.is_empty() {
+                buf.set_string(
+                    area.x + 1,
+                    area.y,
+                    "--- Thinking ---",
+                    Style::default().fg(Color::DarkGray),
+                );
+                let style = Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC);
+                for (row, line) in (1..).zip(&lines) {
+                    buf.set_string(area.x + 1, area.y + row, line, style);
+                }
+            }
+        }
+
+        self.compositor.mark_layer_dirty(self.layers.reasoning);
+        self.prev_reasoning = shown;
+    }
+
     /// Render tasks from display state to a buffer
     fn render_display_tasks_to_buffer(
         buf: &mut ratatui::buffer::Buffer,
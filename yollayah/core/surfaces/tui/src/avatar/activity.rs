@@ -2,6 +2,7 @@
 //!
 //! Provides visual indicators for what Yollayah is doing:
 //! - Thinking: thought bubbles, question marks, gears turning
+//! - Talking: sound waves while a response streams in
 //! - Construction: hard hat, tools, building
 //! - Study: glasses, books, graduation cap
 //! - Engineering: gears, circuits, wrenches
@@ -24,6 +25,8 @@ pub enum Activity {
     None,
     /// Thinking/processing - thought bubbles, pondering
     Thinking,
+    /// Talking - sound waves while responding
+    Talking,
     /// Construction work - building something
     Construction,
     /// Studying/learning - reading, researching
@@ -50,6 +53,7 @@ impl Activity {
         match self {
             Activity::None => "idle",
             Activity::Thinking => "thinking",
+            Activity::Talking => "talking",
             Activity::Construction => "building",
             Activity::Study => "studying",
             Activity::Engineering => "engineering",
@@ -156,6 +160,11 @@ impl ActivityOverlay {
         self.frames.get(self.current_frame)
     }
 
+    /// Whether further updates can't change the frame
+    pub fn is_settled(&self) -> bool {
+        self.frames.len() <= 1 || (!self.looping && self.current_frame + 1 >= self.frames.len())
+    }
+
     /// Reset animation to start
     pub fn reset(&mut self) {
         self.current_frame = 0;
@@ -295,6 +304,40 @@ pub fn thinking_overlay(size: OverlaySize) -> ActivityOverlay {
     }
 }
 
+/// Create talking overlay - sound waves pulsing out
+pub fn talking_overlay(size: OverlaySize) -> ActivityOverlay {
+    let palette = vec![
+        ('(', '(', THOUGHT_BUBBLE), // Wave
+        ('~', '~', THOUGHT_DOT),    // Murmur
+        ('*', '*', SPARKLE),        // Sparkle
+    ];
+
+    match size {
+        OverlaySize::Tiny | OverlaySize::Small => ActivityOverlay {
+            frames: vec![
+                build_overlay_frame(&["( "], &palette, 200),
+                build_overlay_frame(&["(("], &palette, 200),
+            ],
+            current_frame: 0,
+            frame_time: Duration::ZERO,
+            looping: true,
+            offset: (-2, 0),
+        },
+        OverlaySize::Medium | OverlaySize::Large => ActivityOverlay {
+            frames: vec![
+                build_overlay_frame(&["    ", "  ( ", "    "], &palette, 180),
+                build_overlay_frame(&["  ( ", " (( ", "  ( "], &palette, 180),
+                build_overlay_frame(&[" ~( ", "((( ", " ~( "], &palette, 180),
+                build_overlay_frame(&["*~  ", "((  ", " ~  "], &palette, 180),
+            ],
+            current_frame: 0,
+            frame_time: Duration::ZERO,
+            looping: true,
+            offset: (-4, 0),
+        },
+    }
+}
+
 /// Create construction overlay - hard hat and tools
 pub fn construction_overlay(size: OverlaySize) -> ActivityOverlay {
     let palette = vec![
@@ -552,11 +595,19 @@ impl ActivityManager {
         self.overlay.as_ref()
     }
 
+    /// Whether the overlay (if any) has stopped animating
+    pub fn is_settled(&self) -> bool {
+        self.overlay
+            .as_ref()
+            .is_none_or(ActivityOverlay::is_settled)
+    }
+
     /// Create overlay for activity
     fn create_overlay(&self, activity: Activity) -> Option<ActivityOverlay> {
         match activity {
             Activity::None => None,
             Activity::Thinking => Some(thinking_overlay(self.size)),
+            Activity::Talking => Some(talking_overlay(self.size)),
             Activity::Construction => Some(construction_overlay(self.size)),
             Activity::Study => Some(study_overlay(self.size)),
             Activity::Engineering => Some(engineering_overlay(self.size)),
@@ -121,6 +121,23 @@ impl AnimationEngine {
     pub fn current_frame_index(&self) -> usize {
         self.current_frame
     }
+
+    /// Whether further updates can't change the frame
+    ///
+    /// True for single-frame animations and finished one-shots. False when
+    /// the sprite sheet for `size` hasn't been loaded yet.
+    pub fn is_settled(&self, size: AvatarSize) -> bool {
+        let Some(sheet) = self.sheets.get(&size) else {
+            return false;
+        };
+        match sheet.get(&self.current_animation) {
+            Some(animation) => {
+                animation.frames.len() <= 1
+                    || (!animation.looping && self.current_frame + 1 >= animation.frames.len())
+            }
+            None => true,
+        }
+    }
 }
 
 impl Default for AnimationEngine {
@@ -20,6 +20,11 @@
 //! - [`accessibility::MotionPreference`]: Full, Reduced, or None motion modes
 //! - [`accessibility::detect_motion_preference`]: Auto-detect from `REDUCE_MOTION` env var
 //! - [`accessibility::AccessibleAnimator`]: Wrapper that respects preferences
+//!
+//! # Monochrome Fallback
+//!
+//! When the surface reports no color support, [`Avatar::render`] maps the
+//! axolotl palette to ASCII density characters instead of RGB cells.
 
 pub mod accessibility;
 mod activity;
@@ -34,6 +39,7 @@ mod states;
 use std::time::Duration;
 
 use ratatui::buffer::Buffer;
+use ratatui::style::Color;
 
 pub use accessibility::{
     detect_motion_preference, parse_motion_preference, AccessibleAnimator, MotionPreference,
@@ -43,7 +49,9 @@ pub use animation::AnimationEngine;
 pub use animator::{AvatarAnimator, MoodTransition};
 pub use dirty_tracker::{DirtyRect, DirtyTracker, DirtyTrackingExt};
 pub use sizes::AvatarSize;
-pub use sprites::{Animation, CellBlendMode, ColoredCell, Frame};
+pub use sprites::{
+    luminance, monochrome_cell, Animation, CellBlendMode, ColoredCell, Frame, DENSITY_RAMP,
+};
 pub use states::{AvatarState, AvatarStateMachine, AvatarTrigger};
 
 /// The animated axolotl avatar
@@ -54,6 +62,8 @@ pub struct Avatar {
     size: AvatarSize,
     /// Activity overlay manager
     activity: ActivityManager,
+    /// Whether the surface can display color (false = ASCII density fallback)
+    color: bool,
 }
 
 impl Avatar {
@@ -63,11 +73,17 @@ impl Avatar {
             engine: AnimationEngine::new(),
             size: AvatarSize::Medium,
             activity: ActivityManager::new(),
+            color: true,
         }
     }
 
     /// Update animation (call every frame) - returns true if avatar changed
     pub fn update(&mut self, delta: Duration) -> bool {
+        // Nothing can change until a new animation or activity starts
+        if self.is_static() {
+            return false;
+        }
+
         // Track previous state
         let prev_frame = self.engine.current_frame_index();
         let prev_animation = self.engine.current_animation().to_string();
@@ -83,6 +99,14 @@ impl Avatar {
         frame_changed || animation_changed
     }
 
+    /// Whether the avatar looks the same every frame (no redraw needed)
+    ///
+    /// Single-frame animations and finished one-shots are static, as long as
+    /// no animated activity overlay is showing.
+    pub fn is_static(&self) -> bool {
+        self.engine.is_settled(self.size) && self.activity.is_settled()
+    }
+
     /// Play a named animation
     pub fn play(&mut self, name: &str) {
         self.engine.play(name);
@@ -111,6 +135,18 @@ impl Avatar {
         self.activity.current_activity()
     }
 
+    /// Enable or disable color rendering
+    ///
+    /// Without color, sprites render as ASCII density characters.
+    pub fn set_color(&mut self, color: bool) {
+        self.color = color;
+    }
+
+    /// Check if color rendering is enabled
+    pub fn color_enabled(&self) -> bool {
+        self.color
+    }
+
     /// Render the avatar to a buffer with per-cell coloring
     pub fn render(&self, buf: &mut Buffer) {
         let frame = match self.engine.current_frame(self.size) {
@@ -142,11 +178,7 @@ impl Avatar {
                     continue;
                 }
 
-                // Set the cell with its specific color (no allocation)
-                if let Some(target_cell) = buf.cell_mut((x, y)) {
-                    target_cell.set_char(cell.ch);
-                    target_cell.set_fg(cell.fg);
-                }
+                self.paint_cell(buf, x, y, cell);
             }
         }
 
@@ -191,15 +223,28 @@ impl Avatar {
                     continue;
                 }
 
-                // Set the cell with its specific color (no allocation)
-                if let Some(target_cell) = buf.cell_mut((x, y)) {
-                    target_cell.set_char(cell.ch);
-                    target_cell.set_fg(cell.fg);
-                }
+                self.paint_cell(buf, x, y, cell);
             }
         }
     }
 
+    /// Paint a single sprite cell, honoring the color capability
+    fn paint_cell(&self, buf: &mut Buffer, x: u16, y: u16, cell: &ColoredCell) {
+        let Some(target_cell) = buf.cell_mut((x, y)) else {
+            return;
+        };
+
+        if self.color {
+            // Set the cell with its specific color (no allocation)
+            target_cell.set_char(cell.ch);
+            target_cell.set_fg(cell.fg);
+        } else {
+            let mono = monochrome_cell(cell);
+            target_cell.set_char(mono.ch);
+            target_cell.set_fg(Color::Reset);
+        }
+    }
+
     /// Get current size bounds
     pub fn bounds(&self) -> (u16, u16) {
         self.size.max_bounds()
@@ -216,3 +261,80 @@ impl Default for Avatar {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    fn render_to_buffer(avatar: &Avatar) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 20));
+        avatar.render(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_color_render_uses_rgb() {
+        let avatar = Avatar::new();
+        let buf = render_to_buffer(&avatar);
+
+        assert!(buf
+            .content()
+            .iter()
+            .any(|cell| matches!(cell.fg, Color::Rgb(..))));
+    }
+
+    #[test]
+    fn test_monochrome_render_uses_density_ramp() {
+        let mut avatar = Avatar::new();
+        avatar.set_color(false);
+        assert!(!avatar.color_enabled());
+        let buf = render_to_buffer(&avatar);
+
+        let drawn: Vec<_> = buf
+            .content()
+            .iter()
+            .filter(|cell| cell.symbol() != " ")
+            .collect();
+        assert!(!drawn.is_empty(), "Avatar should still be drawn");
+        for cell in drawn {
+            assert_eq!(cell.fg, Color::Reset, "No RGB colors without color support");
+            let ch = cell.symbol().chars().next().unwrap();
+            assert!(DENSITY_RAMP.contains(&ch), "Unexpected glyph {ch:?}");
+        }
+    }
+
+    #[test]
+    fn test_monochrome_cell_brighter_is_denser() {
+        let dark = monochrome_cell(&ColoredCell::new('█', crate::theme::AXOLOTL_EYES));
+        let light = monochrome_cell(&ColoredCell::new('█', crate::theme::AXOLOTL_BODY));
+        let rank = |ch| DENSITY_RAMP.iter().position(|&c| c == ch).unwrap();
+
+        assert!(rank(light.ch) > rank(dark.ch));
+        assert_eq!(light.fg, Color::Reset);
+    }
+
+    #[test]
+    fn test_settled_single_frame_animation_stops_updating() {
+        let mut avatar = Avatar::new();
+        avatar.play("wink");
+        assert!(avatar.is_static());
+
+        for _ in 0..10 {
+            assert!(!avatar.update(Duration::from_secs(1)));
+        }
+        assert_eq!(avatar.current_animation(), "wink");
+    }
+
+    #[test]
+    fn test_looping_animation_is_not_static() {
+        let mut avatar = Avatar::new();
+        avatar.play("idle");
+        assert!(!avatar.is_static());
+
+        // An animated overlay keeps a settled sprite animating
+        avatar.play("wink");
+        avatar.set_activity(Activity::Thinking);
+        assert!(!avatar.is_static());
+    }
+}
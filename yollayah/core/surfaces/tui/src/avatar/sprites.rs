@@ -28,7 +28,9 @@
 
 use std::collections::HashMap;
 
-use conductor_core::avatar::block::{Block, Color as ProtocolColor};
+use conductor_core::avatar::block::{
+    AnchorPoint, Block, Color as ProtocolColor, SizeHint, SpriteResponse,
+};
 use ratatui::style::Color;
 
 /// Blend mode for cell compositing (future use)
@@ -219,6 +221,44 @@ pub fn ratatui_color_to_protocol(c: Color) -> ProtocolColor {
     }
 }
 
+// ============================================================================
+// Monochrome Fallback
+// ============================================================================
+
+/// ASCII density ramp for surfaces without color, from faintest to densest
+pub const DENSITY_RAMP: [char; 9] = ['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+/// Perceived brightness of a color (Rec. 601 luma, 0-255)
+#[must_use]
+pub fn luminance(c: Color) -> u8 {
+    let c = ratatui_color_to_protocol(c);
+    let luma = (299 * u32::from(c.r) + 587 * u32::from(c.g) + 114 * u32::from(c.b)) / 1000;
+    luma as u8
+}
+
+/// Map a colored cell to its monochrome equivalent
+///
+/// The cell's color becomes an ASCII density character (brighter = denser)
+/// and the foreground is reset, so the axolotl stays recognizable by shape
+/// and shading alone. Cells without a color keep their character.
+#[must_use]
+pub fn monochrome_cell(cell: &ColoredCell) -> ColoredCell {
+    if cell.is_empty() || cell.fg == Color::Reset {
+        return ColoredCell {
+            bg: None,
+            ..cell.clone()
+        };
+    }
+
+    let idx = usize::from(luminance(cell.fg)) * DENSITY_RAMP.len() / 256;
+    ColoredCell {
+        ch: DENSITY_RAMP[idx],
+        fg: Color::Reset,
+        bg: None,
+        ..cell.clone()
+    }
+}
+
 // ============================================================================
 // ColoredCell <-> Block Conversions
 // ============================================================================
@@ -254,6 +294,12 @@ impl From<Block> for ColoredCell {
     }
 }
 
+impl From<&Block> for ColoredCell {
+    fn from(block: &Block) -> Self {
+        block.clone().into()
+    }
+}
+
 /// Convert TUI `ColoredCell` to protocol `Block`
 #[must_use]
 pub fn colored_cell_to_block(cell: ColoredCell) -> Block {
@@ -279,6 +325,9 @@ pub fn colored_cell_to_block(cell: ColoredCell) -> Block {
     }
 }
 
+/// Frame duration when the protocol doesn't give one (~10fps)
+pub const DEFAULT_FRAME_MS: u64 = 100;
+
 /// A single animation frame with per-cell coloring
 #[derive(Clone, Debug)]
 pub struct Frame {
@@ -314,6 +363,37 @@ impl Frame {
             .and_then(|row| row.get(x as usize))
             .unwrap_or(&EMPTY)
     }
+
+    /// Where the frame's top-left cell goes so `anchor` lands on (x, y)
+    pub fn origin_for(&self, anchor: AnchorPoint, x: i32, y: i32) -> (i32, i32) {
+        anchor.calculate_top_left(x, y, self.width, self.height)
+    }
+
+    /// The frame's size as a protocol hint (for requesting matching sprites)
+    pub fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.width, self.height)
+    }
+}
+
+/// Convert a protocol sprite (row-major blocks) to a TUI `Frame`
+///
+/// Core-defined sprites render in the TUI as-is; place the frame with
+/// `origin_for(response.anchor, ..)`.
+impl From<&SpriteResponse> for Frame {
+    fn from(response: &SpriteResponse) -> Self {
+        let width = usize::from(response.width());
+        let cells = if width == 0 {
+            Vec::new()
+        } else {
+            response
+                .blocks
+                .chunks(width)
+                .take(usize::from(response.height()))
+                .map(|row| row.iter().map(ColoredCell::from).collect())
+                .collect()
+        };
+        Self::new(cells, DEFAULT_FRAME_MS)
+    }
 }
 
 /// An animation sequence
@@ -415,3 +495,44 @@ pub fn build_animation(
         looping,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprite_response_converts_to_frame() {
+        let pink = ProtocolColor::rgb(255, 182, 193);
+        let black = ProtocolColor::rgb(0, 0, 0);
+        let response = SpriteResponse::new(
+            vec![
+                Block::solid(pink),
+                Block::character('o', black),
+                Block::empty(),
+                Block::colored('▀', pink, black),
+            ],
+            2,
+            2,
+        )
+        .with_anchor(AnchorPoint::BottomCenter);
+
+        let frame = Frame::from(&response);
+        assert_eq!((frame.width, frame.height), (2, 2));
+        assert_eq!(frame.duration_ms, DEFAULT_FRAME_MS);
+
+        let solid = frame.get(0, 0);
+        assert_eq!(solid.ch, Block::FULL_BLOCK);
+        assert_eq!(solid.fg, Color::Rgb(255, 182, 193));
+        assert_eq!(solid.bg, Some(Color::Rgb(255, 182, 193)));
+        let eye = frame.get(1, 0);
+        assert_eq!((eye.ch, eye.fg, eye.bg), ('o', Color::Rgb(0, 0, 0), None));
+        assert!(frame.get(0, 1).is_transparent());
+        let half = frame.get(1, 1);
+        assert_eq!(half.ch, '▀');
+        assert_eq!(half.bg, Some(Color::Rgb(0, 0, 0)));
+
+        // Bottom-center of the sprite lands on the target
+        assert_eq!(frame.origin_for(response.anchor, 10, 10), (9, 8));
+        assert_eq!(frame.size_hint(), SizeHint::exact(2, 2));
+    }
+}
@@ -0,0 +1,165 @@
+//! Command-Line Arguments
+//!
+//! Flags are layered on top of the environment-derived `ConductorConfig`
+//! for the embedded Conductor. A remote daemon reads its own config.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use conductor_core::ConductorConfig;
+
+use crate::events::{InputHistory, SubmitKey, DEFAULT_HISTORY_SIZE};
+use crate::widgets::AutoscrollMode;
+
+/// Yollayah TUI - the cutest AI companion
+#[derive(Parser, Debug, Clone, Default)]
+#[command(name = "yollayah-tui")]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// Don't greet when the TUI connects
+    #[arg(long)]
+    pub no_greeting: bool,
+
+    /// System prompt to use instead of `YOLLAYAH_SYSTEM_PROMPT`
+    #[arg(long, value_name = "PROMPT")]
+    pub system_prompt: Option<String>,
+
+    /// Which Enter combination sends a message (the other inserts a newline)
+    #[arg(long, value_enum, default_value_t = SubmitKey::Enter)]
+    pub submit_key: SubmitKey,
+
+    /// When the conversation jumps back to the latest output
+    #[arg(long, value_enum, default_value_t = AutoscrollMode::Always)]
+    pub autoscroll_mode: AutoscrollMode,
+
+    /// How many sent prompts Up/Down can recall (0 turns history off)
+    #[arg(long, value_name = "N")]
+    pub history_size: Option<usize>,
+
+    /// Save input history to this file and reload it on start
+    #[arg(long, value_name = "PATH")]
+    pub history_file: Option<PathBuf>,
+
+    /// Show repeated notifications and a turn's replies separately
+    #[arg(long)]
+    pub no_grouping: bool,
+
+    /// Stream the model's `<think>` reasoning to its own pane
+    #[arg(long)]
+    pub show_reasoning: bool,
+}
+
+impl Args {
+    /// Override config fields with any flags that were given
+    pub fn apply_to(&self, config: &mut ConductorConfig) {
+        if self.no_greeting {
+            config.greet_on_connect = false;
+        }
+        if let Some(ref prompt) = self.system_prompt {
+            config.system_prompt = Some(prompt.clone());
+        }
+        if self.show_reasoning {
+            config.stream_reasoning = true;
+        }
+    }
+
+    /// Input history sized and persisted as the flags say
+    pub fn input_history(&self) -> InputHistory {
+        let size = self.history_size.unwrap_or(DEFAULT_HISTORY_SIZE);
+        match self.history_file {
+            Some(ref path) => InputHistory::persisted(size, path.clone()),
+            None => InputHistory::new(size),
+        }
+    }
+
+    /// Conductor config from the environment with the flags applied
+    pub fn conductor_config(&self) -> ConductorConfig {
+        let mut config = ConductorConfig::from_env();
+        self.apply_to(&mut config);
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_override_config() {
+        let args = Args::try_parse_from([
+            "yollayah-tui",
+            "--no-greeting",
+            "--system-prompt",
+            "Answer in haiku.",
+            "--show-reasoning",
+        ])
+        .unwrap();
+
+        let mut config = ConductorConfig::default();
+        args.apply_to(&mut config);
+        assert!(!config.greet_on_connect);
+        assert!(config.stream_reasoning);
+        assert_eq!(config.system_prompt.as_deref(), Some("Answer in haiku."));
+    }
+
+    #[test]
+    fn test_no_flags_keep_config() {
+        let args = Args::try_parse_from(["yollayah-tui"]).unwrap();
+        assert!(!args.no_greeting);
+        assert_eq!(args.system_prompt, None);
+        assert_eq!(args.submit_key, SubmitKey::Enter);
+        assert_eq!(args.autoscroll_mode, AutoscrollMode::Always);
+        assert!(!args.no_grouping);
+        assert!(!args.show_reasoning);
+
+        let mut config = ConductorConfig {
+            system_prompt: Some("From the environment".to_string()),
+            ..Default::default()
+        };
+        args.apply_to(&mut config);
+        assert!(config.greet_on_connect);
+        assert_eq!(
+            config.system_prompt.as_deref(),
+            Some("From the environment")
+        );
+    }
+
+    #[test]
+    fn test_submit_key_flag() {
+        let args = Args::try_parse_from(["yollayah-tui", "--submit-key", "shift-enter"]).unwrap();
+        assert_eq!(args.submit_key, SubmitKey::ShiftEnter);
+        assert!(Args::try_parse_from(["yollayah-tui", "--submit-key", "tab"]).is_err());
+    }
+
+    #[test]
+    fn test_autoscroll_mode_flag() {
+        let args =
+            Args::try_parse_from(["yollayah-tui", "--autoscroll-mode", "streaming-only"]).unwrap();
+        assert_eq!(args.autoscroll_mode, AutoscrollMode::StreamingOnly);
+        let args = Args::try_parse_from(["yollayah-tui", "--autoscroll-mode", "never"]).unwrap();
+        assert_eq!(args.autoscroll_mode, AutoscrollMode::Never);
+    }
+
+    #[test]
+    fn test_history_flags() {
+        let history = Args::try_parse_from(["yollayah-tui"])
+            .unwrap()
+            .input_history();
+        assert!(history.is_empty());
+
+        let args = Args::try_parse_from(["yollayah-tui", "--history-size", "0"]).unwrap();
+        let mut history = args.input_history();
+        history.push("hello".to_string());
+        assert!(history.is_empty());
+
+        let args =
+            Args::try_parse_from(["yollayah-tui", "--history-file", "/tmp/history.jsonl"]).unwrap();
+        assert_eq!(args.history_file, Some(PathBuf::from("/tmp/history.jsonl")));
+        assert!(Args::try_parse_from(["yollayah-tui", "--history-size", "lots"]).is_err());
+    }
+
+    #[test]
+    fn test_system_prompt_requires_a_value() {
+        assert!(Args::try_parse_from(["yollayah-tui", "--system-prompt"]).is_err());
+    }
+}
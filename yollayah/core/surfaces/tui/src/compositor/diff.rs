@@ -0,0 +1,185 @@
+//! Frame Diff - Only write what changed
+//!
+//! Keeps a copy of the last frame flushed to the terminal and works out
+//! which cells of a new composite differ from it, so the render path can
+//! hand just those cells to crossterm.
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use unicode_width::UnicodeWidthStr;
+
+/// Cells that changed between two frames
+pub struct FrameDelta<'a> {
+    /// Changed cells as (x, y, cell), in row-major order
+    pub cells: Vec<(u16, u16, &'a Cell)>,
+    /// The frame size changed, so the screen must be cleared first
+    pub full_redraw: bool,
+}
+
+/// Counters for how much the render path writes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Frames diffed (including ones with nothing to write)
+    pub frames: u64,
+    /// Cells written by the most recent frame
+    pub last_frame_cells: usize,
+    /// Cells written since startup
+    pub total_cells: u64,
+}
+
+/// Double buffer for the terminal: the last flushed frame plus write stats
+pub struct FrameDiff {
+    /// What the terminal currently shows
+    last: Buffer,
+    stats: FrameStats,
+}
+
+impl FrameDiff {
+    /// Start with nothing flushed, so the first frame is a full redraw
+    pub fn new() -> Self {
+        Self {
+            last: Buffer::empty(Rect::default()),
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Cells of `next` that differ from the last flushed frame
+    ///
+    /// The returned cells are assumed to be written: the flushed copy is
+    /// updated and counted in the stats.
+    pub fn diff<'a>(&mut self, next: &'a Buffer) -> FrameDelta<'a> {
+        let full_redraw = self.last.area != next.area;
+        if full_redraw {
+            // After a clear the screen matches an empty buffer
+            self.last = Buffer::empty(next.area);
+        }
+
+        let cells = self.last.diff(next);
+        for &(x, y, cell) in &cells {
+            // Wide characters cover the cells after them, which the diff skips
+            let width = cell.symbol().width().max(1) as u16;
+            for dx in 0..width {
+                let pos = (x.saturating_add(dx), y);
+                if let (Some(dst), Some(src)) = (self.last.cell_mut(pos), next.cell(pos)) {
+                    *dst = src.clone();
+                }
+            }
+        }
+
+        self.stats.frames += 1;
+        self.stats.last_frame_cells = cells.len();
+        self.stats.total_cells += cells.len() as u64;
+
+        FrameDelta { cells, full_redraw }
+    }
+
+    /// Forget what's on screen so the next frame is a full redraw
+    pub fn invalidate(&mut self) {
+        self.last = Buffer::empty(Rect::default());
+    }
+
+    /// Write counters so far
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+}
+
+impl Default for FrameDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Style};
+
+    fn frame(text: &[&str]) -> Buffer {
+        Buffer::with_lines(text.iter().copied())
+    }
+
+    #[test]
+    fn test_first_frame_is_a_full_redraw() {
+        let mut diff = FrameDiff::new();
+        let next = frame(&["ab ", "   "]);
+
+        let delta = diff.diff(&next);
+        assert!(delta.full_redraw);
+        // Blank cells already match a cleared screen
+        let positions: Vec<_> = delta.cells.iter().map(|&(x, y, _)| (x, y)).collect();
+        assert_eq!(positions, vec![(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_diff_contains_only_changed_cells() {
+        let mut diff = FrameDiff::new();
+        let first = frame(&["hello world", "second line", "third line "]);
+        diff.diff(&first);
+
+        let mut next = first.clone();
+        next.set_string(6, 1, "LI", Style::default());
+        next[(0, 2)].set_fg(Color::Red);
+
+        let delta = diff.diff(&next);
+        assert!(!delta.full_redraw);
+        let changed: Vec<_> = delta
+            .cells
+            .iter()
+            .map(|&(x, y, cell)| (x, y, cell.symbol().to_string()))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                (6, 1, "L".to_string()),
+                (7, 1, "I".to_string()),
+                (0, 2, "t".to_string()),
+            ]
+        );
+        assert_eq!(diff.stats().last_frame_cells, 3);
+    }
+
+    #[test]
+    fn test_unchanged_frame_writes_nothing() {
+        let mut diff = FrameDiff::new();
+        let first = frame(&["abc", "def"]);
+        let written = diff.diff(&first).cells.len() as u64;
+
+        assert!(diff.diff(&first.clone()).cells.is_empty());
+        let stats = diff.stats();
+        assert_eq!(stats.frames, 2);
+        assert_eq!(stats.last_frame_cells, 0);
+        assert_eq!(stats.total_cells, written);
+    }
+
+    #[test]
+    fn test_resize_and_invalidate_force_full_redraw() {
+        let mut diff = FrameDiff::new();
+        diff.diff(&frame(&["ab", "cd"]));
+
+        let bigger = frame(&["ab ", "cd ", "   "]);
+        let delta = diff.diff(&bigger);
+        assert!(delta.full_redraw);
+        assert_eq!(delta.cells.len(), 4);
+
+        diff.invalidate();
+        assert!(diff.diff(&bigger).full_redraw);
+    }
+
+    #[test]
+    fn test_wide_characters_are_tracked() {
+        let mut diff = FrameDiff::new();
+        let first = frame(&["abcd"]);
+        diff.diff(&first);
+
+        let mut wide = first.clone();
+        wide.set_string(0, 0, "日", Style::default());
+        let delta = diff.diff(&wide);
+        assert_eq!(delta.cells.len(), 1);
+
+        // Going back rewrites both cells the wide character covered
+        let delta = diff.diff(&first);
+        let positions: Vec<_> = delta.cells.iter().map(|&(x, y, _)| (x, y)).collect();
+        assert_eq!(positions, vec![(0, 0), (1, 0)]);
+    }
+}
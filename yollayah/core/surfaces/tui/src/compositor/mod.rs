@@ -4,7 +4,10 @@
 //! and can be positioned, resized, and reordered independently.
 //!
 //! The compositor composites all visible layers into a final output buffer.
+//! [`FrameDiff`] then works out which cells of that buffer actually need
+//! writing to the terminal.
 
+mod diff;
 mod layer;
 
 use std::collections::{HashMap, HashSet};
@@ -12,8 +15,12 @@ use std::collections::{HashMap, HashSet};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 
+pub use diff::{FrameDelta, FrameDiff, FrameStats};
 pub use layer::Layer;
 
+/// Default cap on the number of layers a compositor will hold
+pub const DEFAULT_MAX_LAYERS: usize = 32;
+
 /// Unique identifier for a layer
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct LayerId(u32);
@@ -33,6 +40,8 @@ pub struct Compositor {
     /// Track which layers have changed since last composite
     /// When non-empty, composite() will rebuild the output buffer
     dirty_layers: HashSet<LayerId>,
+    /// Maximum number of layers; further creation is refused
+    max_layers: usize,
 }
 
 impl Compositor {
@@ -45,11 +54,28 @@ impl Compositor {
             output: Buffer::empty(area),
             area,
             dirty_layers: HashSet::new(),
+            max_layers: DEFAULT_MAX_LAYERS,
         }
     }
 
+    /// Set the maximum number of layers
+    pub fn with_max_layers(mut self, max_layers: usize) -> Self {
+        self.max_layers = max_layers;
+        self
+    }
+
     /// Create a new layer and return its ID
-    pub fn create_layer(&mut self, bounds: Rect, z_index: i32) -> LayerId {
+    ///
+    /// Returns `None` (and logs a warning) once `max_layers` layers exist.
+    pub fn create_layer(&mut self, bounds: Rect, z_index: i32) -> Option<LayerId> {
+        if self.layers.len() >= self.max_layers {
+            tracing::warn!(
+                max_layers = self.max_layers,
+                "Compositor layer limit reached, refusing to create another layer"
+            );
+            return None;
+        }
+
         let id = LayerId(self.next_id);
         self.next_id += 1;
 
@@ -57,7 +83,7 @@ impl Compositor {
         self.layers.insert(id, layer);
         self.update_render_order();
 
-        id
+        Some(id)
     }
 
     /// Get mutable access to a layer's buffer for rendering
@@ -234,3 +260,35 @@ impl Compositor {
             .sort_by_key(|id| self.layers.get(id).map(|l| l.z_index).unwrap_or(0));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_layer_refuses_past_limit() {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut compositor = Compositor::new(area).with_max_layers(3);
+
+        let ids: Vec<LayerId> = (0..3)
+            .map(|z| compositor.create_layer(area, z).expect("under the limit"))
+            .collect();
+        assert_eq!(ids.len(), 3);
+
+        assert_eq!(compositor.create_layer(area, 99), None);
+        assert_eq!(compositor.render_order.len(), 3);
+        // Existing layers are unaffected
+        assert!(compositor.layer_buffer_mut(ids[0]).is_some());
+    }
+
+    #[test]
+    fn test_default_layer_limit() {
+        let area = Rect::new(0, 0, 4, 4);
+        let mut compositor = Compositor::new(area);
+
+        for _ in 0..DEFAULT_MAX_LAYERS {
+            assert!(compositor.create_layer(area, 0).is_some());
+        }
+        assert!(compositor.create_layer(area, 0).is_none());
+    }
+}
@@ -23,22 +23,27 @@
 //! # Reconnection
 //!
 //! For remote transports (Unix socket), the client supports automatic reconnection
-//! with exponential backoff. Configure via TransportConfig:
+//! with exponential backoff and jitter. Configure via TransportConfig:
 //! - reconnect_attempts: Number of retry attempts (0 = disabled)
 //! - reconnect_delay_ms: Initial delay between attempts (doubles each retry)
+//! - reconnect_max_delay_ms: Upper bound on the delay
+//! - reconnect_stable_ms: Uptime after which the delay resets to the initial value
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+use crate::theme::detect_color_support;
+
 use conductor_core::{
     transport::{
-        SurfaceTransport, TransportConfig, TransportError, TransportType, UnixSocketClient,
+        ReconnectBackoff, SurfaceTransport, TransportConfig, TransportError, TransportType,
+        UnixSocketClient,
     },
-    Conductor, ConductorConfig, ConductorMessage, ConductorState, OllamaBackend,
-    SurfaceCapabilities, SurfaceEvent, SurfaceType,
+    Conductor, ConductorConfig, ConductorMessage, ConductorState, NotifyLevel, OllamaBackend,
+    OneShotAnimation, PollOutcome, SurfaceCapabilities, SurfaceEvent, SurfaceType,
 };
 
 /// Connection state for remote transports
@@ -52,6 +57,9 @@ pub enum ConnectionState {
     Connected,
     /// Lost connection, attempting to reconnect
     Reconnecting,
+    /// The Conductor refused the handshake (e.g. a protocol version
+    /// mismatch); reconnecting would only be refused again
+    Rejected,
 }
 
 /// Client mode - either embedded Conductor or remote via transport
@@ -78,6 +86,17 @@ pub struct ConductorClient {
     connection_state: ConnectionState,
     /// Number of reconnection attempts made
     reconnect_count: u32,
+    /// Delay schedule between reconnection attempts
+    backoff: ReconnectBackoff,
+    /// Capabilities reported to the Conductor
+    capabilities: SurfaceCapabilities,
+    /// Why the Conductor refused this surface, if it did
+    rejection: Option<String>,
+    /// When to retry after a temporary refusal (the app's tick does it)
+    retry_at: Option<Instant>,
+    /// Stable id sent in every handshake, so the Conductor knows a
+    /// reconnect is this TUI coming back
+    client_id: String,
 }
 
 impl ConductorClient {
@@ -95,6 +114,24 @@ impl ConductorClient {
 
     /// Create a ConductorClient with specific transport configuration
     pub fn with_config(config: TransportConfig) -> Self {
+        Self::with_conductor_config(config, ConductorConfig::from_env())
+    }
+
+    /// Create a ConductorClient with specific transport and Conductor configuration
+    ///
+    /// The Conductor config only applies to the embedded Conductor; a
+    /// remote daemon uses its own.
+    pub fn with_conductor_config(
+        config: TransportConfig,
+        mut conductor_config: ConductorConfig,
+    ) -> Self {
+        let capabilities = SurfaceCapabilities {
+            color: detect_color_support(),
+            ..SurfaceCapabilities::tui()
+        };
+        let backoff = config.reconnect_backoff();
+        let client_id = new_client_id();
+
         match &config.transport {
             TransportType::InProcess => {
                 info!("Starting in embedded mode (Conductor in-process)");
@@ -105,10 +142,8 @@ impl ConductorClient {
                 // Create Ollama backend from environment
                 let backend = OllamaBackend::from_env();
 
-                // Create Conductor config from environment
                 // Disable warmup so TUI starts immediately responsive
                 // The first user message will warm up the model instead
-                let mut conductor_config = ConductorConfig::from_env();
                 conductor_config.warmup_on_start = false;
 
                 // Create the Conductor
@@ -119,6 +154,11 @@ impl ConductorClient {
                     config,
                     connection_state: ConnectionState::Disconnected,
                     reconnect_count: 0,
+                    backoff,
+                    capabilities,
+                    rejection: None,
+                    retry_at: None,
+                    client_id,
                 }
             }
 
@@ -137,6 +177,11 @@ impl ConductorClient {
                     config,
                     connection_state: ConnectionState::Disconnected,
                     reconnect_count: 0,
+                    backoff,
+                    capabilities,
+                    rejection: None,
+                    retry_at: None,
+                    client_id,
                 }
             }
         }
@@ -168,7 +213,7 @@ impl ConductorClient {
                 let event = SurfaceEvent::Connected {
                     event_id: SurfaceEvent::new_event_id(),
                     surface_type: SurfaceType::Tui,
-                    capabilities: SurfaceCapabilities::tui(),
+                    capabilities: self.capabilities.clone(),
                 };
                 conductor.handle_event(event).await?;
                 self.connection_state = ConnectionState::Connected;
@@ -178,17 +223,13 @@ impl ConductorClient {
             ClientMode::UnixSocket { transport } => {
                 // Connect to the Unix socket
                 transport.connect().await.map_err(transport_to_anyhow)?;
-
-                // Send Connected event
-                let event = SurfaceEvent::Connected {
-                    event_id: SurfaceEvent::new_event_id(),
-                    surface_type: SurfaceType::Tui,
-                    capabilities: SurfaceCapabilities::tui(),
-                };
-                transport.send(event).await.map_err(transport_to_anyhow)?;
+                announce(transport, &self.capabilities, &self.client_id)
+                    .await
+                    .map_err(transport_to_anyhow)?;
 
                 self.connection_state = ConnectionState::Connected;
                 self.reconnect_count = 0;
+                self.backoff.on_connected(Instant::now());
                 info!("Connected to Conductor daemon");
                 Ok(())
             }
@@ -202,16 +243,24 @@ impl ConductorClient {
     ///
     /// Uses TransportConfig settings:
     /// - reconnect_attempts: Maximum number of retries (0 = disabled)
-    /// - reconnect_delay_ms: Initial delay, doubles each attempt
+    /// - reconnect_delay_ms: Initial delay, doubles each attempt (with jitter)
+    /// - reconnect_max_delay_ms: Cap on the delay
     ///
-    /// Returns Ok(true) if reconnected, Ok(false) if max attempts reached,
-    /// Err if a non-retriable error occurred.
+    /// Returns Ok(true) if reconnected, Ok(false) if max attempts reached
+    /// or the Conductor refused this surface, Err if a non-retriable error
+    /// occurred.
     pub async fn try_reconnect(&mut self) -> anyhow::Result<bool> {
         // In-process mode doesn't need reconnection
         if matches!(self.mode, ClientMode::InProcess { .. }) {
             return Ok(true);
         }
 
+        // The Conductor would refuse us again
+        if self.connection_state == ConnectionState::Rejected {
+            debug!("Not reconnecting: the Conductor refused this surface");
+            return Ok(false);
+        }
+
         // Check if reconnection is disabled
         if self.config.reconnect_attempts == 0 {
             warn!("Reconnection disabled (reconnect_attempts = 0)");
@@ -230,19 +279,19 @@ impl ConductorClient {
 
         self.connection_state = ConnectionState::Reconnecting;
 
-        // Calculate delay with exponential backoff
-        let delay_ms = self.config.reconnect_delay_ms * (1 << self.reconnect_count.min(6));
+        // Exponential backoff with jitter; keeps growing across quick flaps
+        let delay = self.backoff.next_delay();
         self.reconnect_count += 1;
 
         info!(
             attempt = self.reconnect_count,
             max_attempts = self.config.reconnect_attempts,
-            delay_ms = delay_ms,
+            delay_ms = delay.as_millis() as u64,
             "Attempting reconnection"
         );
 
         // Wait before attempting
-        sleep(Duration::from_millis(delay_ms)).await;
+        sleep(delay).await;
 
         // Attempt reconnection
         match &mut self.mode {
@@ -253,16 +302,14 @@ impl ConductorClient {
                 // Try to connect
                 match transport.connect().await {
                     Ok(()) => {
-                        // Re-send Connected event
-                        let event = SurfaceEvent::Connected {
-                            event_id: SurfaceEvent::new_event_id(),
-                            surface_type: SurfaceType::Tui,
-                            capabilities: SurfaceCapabilities::tui(),
-                        };
-                        transport.send(event).await.map_err(transport_to_anyhow)?;
+                        // Same client id, so the Conductor welcomes us back
+                        announce(transport, &self.capabilities, &self.client_id)
+                            .await
+                            .map_err(transport_to_anyhow)?;
 
                         self.connection_state = ConnectionState::Connected;
                         self.reconnect_count = 0;
+                        self.backoff.on_connected(Instant::now());
                         info!("Reconnected to Conductor daemon");
                         Ok(true)
                     }
@@ -282,6 +329,16 @@ impl ConductorClient {
         self.connection_state
     }
 
+    /// Get the capabilities this surface reports to the Conductor
+    pub fn capabilities(&self) -> &SurfaceCapabilities {
+        &self.capabilities
+    }
+
+    /// Why the Conductor refused this surface, if it did
+    pub fn rejection(&self) -> Option<&str> {
+        self.rejection.as_deref()
+    }
+
     /// Check if a reconnection attempt is in progress
     pub fn is_reconnecting(&self) -> bool {
         self.connection_state == ConnectionState::Reconnecting
@@ -304,6 +361,7 @@ impl ConductorClient {
 
         if self.connection_state == ConnectionState::Connected {
             warn!("Connection to Conductor lost");
+            self.backoff.on_disconnected(Instant::now());
             self.connection_state = ConnectionState::Reconnecting;
             true
         } else {
@@ -347,6 +405,26 @@ impl ConductorClient {
         self.send_event(event).await
     }
 
+    /// Tell Conductor whether the view is following the latest output
+    pub async fn scroll_position_changed(&mut self, at_bottom: bool) -> anyhow::Result<()> {
+        self.send_event(SurfaceEvent::ScrollPositionChanged { at_bottom })
+            .await
+    }
+
+    /// Tell the Conductor how the task panel is sorted/filtered
+    pub async fn set_task_view(
+        &mut self,
+        sort: conductor_core::TaskSort,
+        filter: conductor_core::TaskFilter,
+    ) -> anyhow::Result<()> {
+        let event = SurfaceEvent::SetTaskView {
+            event_id: SurfaceEvent::new_event_id(),
+            sort,
+            filter,
+        };
+        self.send_event(event).await
+    }
+
     /// Notify Conductor that user clicked the avatar
     pub async fn avatar_clicked(&mut self) -> anyhow::Result<()> {
         let event = SurfaceEvent::AvatarClicked {
@@ -355,6 +433,20 @@ impl ConductorClient {
         self.send_event(event).await
     }
 
+    /// Ask Conductor for a fresh state snapshot (after missing tokens)
+    pub async fn request_snapshot(&mut self) -> anyhow::Result<()> {
+        let event = SurfaceEvent::RequestSnapshot {
+            event_id: SurfaceEvent::new_event_id(),
+        };
+        self.send_event(event).await
+    }
+
+    /// Tell Conductor a gesture or reaction finished playing
+    pub async fn animation_complete(&mut self, animation: OneShotAnimation) -> anyhow::Result<()> {
+        self.send_event(SurfaceEvent::AnimationComplete { animation })
+            .await
+    }
+
     /// Notify Conductor that user wants to quit
     pub async fn request_quit(&mut self) -> anyhow::Result<()> {
         let event = SurfaceEvent::QuitRequested {
@@ -377,17 +469,83 @@ impl ConductorClient {
     ///
     /// For in-process mode, this directly polls the embedded Conductor.
     /// For remote modes, streaming is handled by the transport layer.
-    pub async fn poll_streaming(&mut self) -> bool {
+    pub async fn poll_streaming(&mut self) -> PollOutcome {
         match &mut self.mode {
             ClientMode::InProcess { conductor, .. } => conductor.poll_streaming().await,
             ClientMode::UnixSocket { .. } => {
                 // Remote conductor handles streaming internally
                 // Messages arrive via transport
-                false
+                PollOutcome::default()
+            }
+        }
+    }
+
+    /// Run the Conductor's timed work (queued gestures, blinks, warmup)
+    ///
+    /// Unlike `poll_streaming`, this leaves streamed tokens to
+    /// `process_streaming_token`. Remote conductors tick on their own, so
+    /// for them this only retries a connection the daemon turned away.
+    pub async fn tick(&mut self) -> PollOutcome {
+        match &mut self.mode {
+            ClientMode::InProcess { conductor, .. } => conductor.tick().await,
+            ClientMode::UnixSocket { .. } => {
+                self.reconnect_if_due().await;
+                PollOutcome::default()
+            }
+        }
+    }
+
+    /// Retry a temporarily refused connection once its backoff is up
+    async fn reconnect_if_due(&mut self) {
+        let due = self.retry_at.is_some_and(|at| Instant::now() >= at);
+        if !due || self.connection_state != ConnectionState::Reconnecting {
+            return;
+        }
+        self.retry_at = None;
+        let ClientMode::UnixSocket { transport } = &mut self.mode else {
+            return;
+        };
+
+        let _ = transport.disconnect().await;
+        let result = match transport.connect().await {
+            Ok(()) => announce(transport, &self.capabilities, &self.client_id).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                // The count resets once the Conductor lets us in
+                self.connection_state = ConnectionState::Connected;
+                self.backoff.on_connected(Instant::now());
+                info!("Reconnected to Conductor daemon");
+            }
+            Err(e) => {
+                debug!(error = %e, "Reconnection attempt failed");
+                if self.schedule_retry(Duration::ZERO).is_none() {
+                    error!(
+                        "Max reconnection attempts ({}) reached",
+                        self.config.reconnect_attempts
+                    );
+                }
             }
         }
     }
 
+    /// Schedule the next retry, waiting at least `at_least`
+    ///
+    /// Returns the delay, or None once reconnect attempts run out.
+    fn schedule_retry(&mut self, at_least: Duration) -> Option<Duration> {
+        if self.reconnect_count >= self.config.reconnect_attempts {
+            self.connection_state = ConnectionState::Disconnected;
+            self.retry_at = None;
+            return None;
+        }
+        self.reconnect_count += 1;
+        let delay = self.backoff.next_delay().max(at_least);
+        self.retry_at = Some(Instant::now() + delay);
+        self.connection_state = ConnectionState::Reconnecting;
+        Some(delay)
+    }
+
     /// Process next streaming token reactively (non-polling)
     ///
     /// For in-process mode, this awaits and processes the next token.
@@ -406,10 +564,82 @@ impl ConductorClient {
     }
 
     /// Try to receive a message from the Conductor (non-blocking)
+    ///
+    /// A refused handshake comes back as an error notification.
     pub fn try_recv(&mut self) -> Option<ConductorMessage> {
-        match &mut self.mode {
+        let msg = match &mut self.mode {
             ClientMode::InProcess { rx, .. } => rx.try_recv().ok(),
             ClientMode::UnixSocket { transport } => transport.try_recv(),
+        }?;
+        Some(self.check_handshake(msg))
+    }
+
+    /// Stop reconnecting if the Conductor refused the handshake
+    ///
+    /// The refusal is turned into a notification naming the reason and the
+    /// protocol version the Conductor supports, so the user isn't left
+    /// looking at a TUI that never connects. A temporary refusal (the
+    /// daemon is full) backs off and retries instead.
+    fn check_handshake(&mut self, msg: ConductorMessage) -> ConductorMessage {
+        let ConductorMessage::HandshakeAck {
+            accepted: false,
+            rejection_reason,
+            protocol_version,
+            retry_after_ms,
+            ..
+        } = msg
+        else {
+            // Anything else means the Conductor let us in
+            if self.connection_state == ConnectionState::Connected {
+                self.reconnect_count = 0;
+            }
+            return msg;
+        };
+
+        let reason = rejection_reason.unwrap_or_else(|| "no reason given".to_string());
+        if let Some(retry_after_ms) = retry_after_ms {
+            return self.retry_refused(reason, Duration::from_millis(retry_after_ms));
+        }
+        let message = format!(
+            "The Conductor refused this connection: {reason}. \
+             It supports protocol version {protocol_version}."
+        );
+        error!(%reason, protocol_version, "Handshake rejected by Conductor");
+        self.connection_state = ConnectionState::Rejected;
+        self.rejection = Some(message.clone());
+        ConductorMessage::Notify {
+            level: NotifyLevel::Error,
+            title: Some("Can't connect".to_string()),
+            message,
+            guidance: Some(
+                "Update Yollayah so the TUI and the Conductor daemon are the same version."
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Back off after a temporary refusal, giving up once attempts run out
+    fn retry_refused(&mut self, reason: String, retry_after: Duration) -> ConductorMessage {
+        self.backoff.on_disconnected(Instant::now());
+        let Some(delay) = self.schedule_retry(retry_after) else {
+            error!(%reason, "Conductor keeps turning this surface away");
+            return ConductorMessage::Notify {
+                level: NotifyLevel::Error,
+                title: Some("Can't connect".to_string()),
+                message: format!("The Conductor kept turning this connection away: {reason}."),
+                guidance: Some("Close another Yollayah window, then restart this one.".to_string()),
+            };
+        };
+
+        warn!(%reason, delay_ms = delay.as_millis() as u64, "Conductor busy, retrying");
+        ConductorMessage::Notify {
+            level: NotifyLevel::Warning,
+            title: Some("Conductor busy".to_string()),
+            message: format!(
+                "The Conductor turned this connection away: {reason}. Retrying in {}s.",
+                delay.as_secs().max(1)
+            ),
+            guidance: None,
         }
     }
 
@@ -481,7 +711,225 @@ impl Default for ConductorClient {
     }
 }
 
+/// An id for this TUI that no other running TUI shares
+fn new_client_id() -> String {
+    let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    format!("tui-{}-{started}", std::process::id())
+}
+
+/// Introduce this surface on a fresh connection: handshake, then connect
+async fn announce(
+    transport: &mut UnixSocketClient,
+    capabilities: &SurfaceCapabilities,
+    client_id: &str,
+) -> Result<(), TransportError> {
+    transport
+        .send(SurfaceEvent::Handshake {
+            event_id: SurfaceEvent::new_event_id(),
+            protocol_version: 1,
+            surface_type: SurfaceType::Tui,
+            capabilities: capabilities.clone(),
+            auth_token: None,
+            client_id: Some(client_id.to_string()),
+        })
+        .await?;
+    transport
+        .send(SurfaceEvent::Connected {
+            event_id: SurfaceEvent::new_event_id(),
+            surface_type: SurfaceType::Tui,
+            capabilities: capabilities.clone(),
+        })
+        .await
+}
+
 /// Convert TransportError to anyhow::Error
 fn transport_to_anyhow(err: TransportError) -> anyhow::Error {
     anyhow::anyhow!("{}", err)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conductor_core::transport::{ConductorTransport, UnixSocketServer};
+
+    #[tokio::test]
+    async fn test_protocol_rejection_stops_reconnecting() {
+        let path = std::env::temp_dir().join(format!(
+            "yollayah-client-{}-rejected.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let mut server = UnixSocketServer::new(path.clone());
+        server.listen().await.unwrap();
+        let server = tokio::spawn(async move {
+            let (conn_id, events) = server.accept().await.unwrap();
+            server
+                .send_to(
+                    &conn_id,
+                    ConductorMessage::HandshakeAck {
+                        accepted: false,
+                        connection_id: conn_id.to_string(),
+                        rejection_reason: Some(
+                            "Unsupported protocol version: 2 (expected 1)".to_string(),
+                        ),
+                        protocol_version: 1,
+                        frame_checksums: false,
+                        retry_after_ms: None,
+                    },
+                )
+                .await
+                .unwrap();
+            (server, events)
+        });
+
+        let config = TransportConfig {
+            transport: TransportType::UnixSocket {
+                path: Some(path.clone()),
+            },
+            reconnect_attempts: 5,
+            reconnect_delay_ms: 1,
+            ..Default::default()
+        };
+        let mut client = ConductorClient::with_conductor_config(config, ConductorConfig::default());
+        client.connect().await.unwrap();
+        let (mut server, _events) = server.await.unwrap();
+
+        let mut notice = None;
+        for _ in 0..100 {
+            notice = client.try_recv();
+            if notice.is_some() {
+                break;
+            }
+            sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(
+            matches!(
+                notice,
+                Some(ConductorMessage::Notify { level: NotifyLevel::Error, ref message, .. })
+                    if message.contains("Unsupported protocol version: 2")
+                        && message.contains("supports protocol version 1")
+            ),
+            "{notice:?}"
+        );
+        assert_eq!(client.connection_state(), ConnectionState::Rejected);
+        assert!(client.rejection().is_some());
+
+        // Losing the connection afterwards doesn't start a retry loop
+        assert!(!client.handle_connection_loss());
+        assert!(!client.try_reconnect().await.unwrap());
+        assert_eq!(client.reconnect_count, 0);
+        assert_eq!(client.connection_state(), ConnectionState::Rejected);
+
+        server.shutdown().await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_sends_the_same_client_id() {
+        let path = std::env::temp_dir().join(format!(
+            "yollayah-client-{}-client-id.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let mut server = UnixSocketServer::new(path.clone());
+        server.listen().await.unwrap();
+
+        let config = TransportConfig {
+            transport: TransportType::UnixSocket {
+                path: Some(path.clone()),
+            },
+            reconnect_attempts: 5,
+            reconnect_delay_ms: 1,
+            ..Default::default()
+        };
+        let mut client = ConductorClient::with_conductor_config(config, ConductorConfig::default());
+        let mut client_ids = Vec::new();
+        for attempt in 0..2 {
+            if attempt == 0 {
+                client.connect().await.unwrap();
+            } else {
+                assert!(client.try_reconnect().await.unwrap());
+            }
+            let (_conn_id, mut events) = server.accept().await.unwrap();
+            let Some(SurfaceEvent::Handshake { client_id, .. }) = events.recv().await else {
+                panic!("a connection should open with a handshake");
+            };
+            assert!(matches!(
+                events.recv().await,
+                Some(SurfaceEvent::Connected { .. })
+            ));
+            client_ids.push(client_id.expect("the TUI should send a client id"));
+        }
+        assert_eq!(client_ids[0], client_ids[1]);
+
+        server.shutdown().await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_busy_refusal_backs_off_and_retries() {
+        let path =
+            std::env::temp_dir().join(format!("yollayah-client-{}-busy.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut server = UnixSocketServer::new(path.clone());
+        server.listen().await.unwrap();
+
+        let config = TransportConfig {
+            transport: TransportType::UnixSocket {
+                path: Some(path.clone()),
+            },
+            reconnect_attempts: 5,
+            reconnect_delay_ms: 1,
+            ..Default::default()
+        };
+        let mut client = ConductorClient::with_conductor_config(config, ConductorConfig::default());
+        client.connect().await.unwrap();
+        let (conn_id, _events) = server.accept().await.unwrap();
+        server
+            .send_to(
+                &conn_id,
+                ConductorMessage::HandshakeAck {
+                    accepted: false,
+                    connection_id: String::new(),
+                    rejection_reason: Some("100 connections are open".to_string()),
+                    protocol_version: 1,
+                    frame_checksums: false,
+                    retry_after_ms: Some(200),
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut notice = None;
+        for _ in 0..100 {
+            notice = client.try_recv();
+            if notice.is_some() {
+                break;
+            }
+            sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(
+            matches!(
+                notice,
+                Some(ConductorMessage::Notify { level: NotifyLevel::Warning, ref message, .. })
+                    if message.contains("100 connections are open")
+            ),
+            "{notice:?}"
+        );
+        assert_eq!(client.connection_state(), ConnectionState::Reconnecting);
+        assert!(client.rejection().is_none());
+
+        // Nothing happens before the retry is due, then the tick reconnects
+        client.tick().await;
+        assert_eq!(client.connection_state(), ConnectionState::Reconnecting);
+        sleep(std::time::Duration::from_millis(250)).await;
+        client.tick().await;
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+        assert!(server.accept().await.is_ok());
+
+        server.shutdown().await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -9,19 +9,23 @@
 //! Display state is the bridge between ConductorMessages and rendering.
 //!
 //! - DisplayMessage: A rendered conversation message
+//! - MessageGroup: Messages drawn together (repeats, one turn's replies)
 //! - DisplayAvatarState: Current avatar state for rendering
 //! - DisplayTask: Task info for the task panel
 
 use std::cell::RefCell;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use conductor_core::{
-    AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction, AvatarSize, ConductorMessage,
-    ConductorState, MessageId, MessageRole, ResponseMetadata, TaskId, TaskStatus,
+    AvatarActivity, AvatarGesture, AvatarMood, AvatarPosition, AvatarReaction, AvatarSize,
+    CommandParser, ConductorMessage, ConductorState, ConversationListEntry, ExportFormat,
+    MessageId, MessageRole, OneShotAnimation, ResponseMetadata, TaskId, TaskStatus,
 };
 
+use crate::tasks::{TaskView, ViewableTask};
+
 /// Text wrapping cache for a message
 /// Stores wrapped lines by width and content hash
 #[derive(Clone, Debug)]
@@ -77,6 +81,18 @@ impl DisplayMessage {
         }
     }
 
+    /// Create the welcome shown when the TUI connects
+    pub fn welcome(id: MessageId, content: String) -> Self {
+        Self {
+            id,
+            role: DisplayRole::Welcome,
+            content,
+            streaming: false,
+            metadata: None,
+            wrapped_cache: RefCell::new(None),
+        }
+    }
+
     /// Get wrapped lines for formatted message content, using cache when possible
     ///
     /// Takes the fully formatted content (with prefix, streaming indicator, etc.)
@@ -209,6 +225,8 @@ pub enum DisplayRole {
     Assistant,
     /// System message
     System,
+    /// Welcome or greeting shown on connect
+    Welcome,
 }
 
 impl From<MessageRole> for DisplayRole {
@@ -227,7 +245,7 @@ impl DisplayRole {
         match self {
             DisplayRole::User => "You: ",
             DisplayRole::Assistant => "Yollayah: ",
-            DisplayRole::System => "",
+            DisplayRole::System | DisplayRole::Welcome => "",
         }
     }
 }
@@ -249,6 +267,10 @@ pub struct DisplayAvatarState {
     pub current_gesture: Option<ActiveGesture>,
     /// Current reaction (if any)
     pub current_reaction: Option<ActiveReaction>,
+    /// Activity overlay requested by the Conductor
+    pub activity: AvatarActivity,
+    /// Gestures/reactions that ran out and haven't been reported yet
+    finished: Vec<OneShotAnimation>,
 }
 
 impl Default for DisplayAvatarState {
@@ -261,6 +283,8 @@ impl Default for DisplayAvatarState {
             target_position: None,
             current_gesture: None,
             current_reaction: None,
+            activity: AvatarActivity::None,
+            finished: Vec::new(),
         }
     }
 }
@@ -285,6 +309,9 @@ impl DisplayAvatarState {
             ConductorMessage::AvatarWander { enabled } => {
                 self.wandering = *enabled;
             }
+            ConductorMessage::AvatarActivity { activity } => {
+                self.activity = *activity;
+            }
             ConductorMessage::AvatarGesture {
                 gesture,
                 duration_ms,
@@ -317,6 +344,15 @@ impl DisplayAvatarState {
                 });
                 self.wandering = false;
             }
+            ConductorMessage::AvatarReset { state } => {
+                self.mood = state.mood;
+                self.size = state.size;
+                self.visible = state.visible;
+                self.wandering = state.wandering;
+                self.target_position = Some(state.position);
+                self.current_gesture = None;
+                self.current_reaction = None;
+            }
             _ => {}
         }
     }
@@ -326,6 +362,8 @@ impl DisplayAvatarState {
         // Check gesture expiration
         if let Some(ref gesture) = self.current_gesture {
             if gesture.started.elapsed() >= gesture.duration {
+                self.finished
+                    .push(OneShotAnimation::Gesture(gesture.gesture));
                 self.current_gesture = None;
             }
         }
@@ -333,6 +371,8 @@ impl DisplayAvatarState {
         // Check reaction expiration
         if let Some(ref reaction) = self.current_reaction {
             if reaction.started.elapsed() >= reaction.duration {
+                self.finished
+                    .push(OneShotAnimation::Reaction(reaction.reaction));
                 self.current_reaction = None;
             }
         }
@@ -391,6 +431,8 @@ pub struct DisplayTask {
     pub progress: u8,
     /// Optional status message
     pub status_message: Option<String>,
+    /// When the task started running (None while pending)
+    pub started_at: Option<SystemTime>,
 }
 
 impl DisplayTask {
@@ -404,6 +446,7 @@ impl DisplayTask {
             status: DisplayTaskStatus::Pending,
             progress: 0,
             status_message: None,
+            started_at: None,
         }
     }
 
@@ -413,6 +456,7 @@ impl DisplayTask {
         self.status_message = message;
         if self.progress > 0 && self.status == DisplayTaskStatus::Pending {
             self.status = DisplayTaskStatus::Running;
+            self.started_at = Some(SystemTime::now());
         }
     }
 
@@ -445,6 +489,29 @@ impl DisplayTask {
     }
 }
 
+impl ViewableTask for DisplayTask {
+    fn status_rank(&self) -> u8 {
+        match self.status {
+            DisplayTaskStatus::Running => 0,
+            DisplayTaskStatus::Pending => 1,
+            DisplayTaskStatus::Done => 2,
+            DisplayTaskStatus::Failed => 3,
+        }
+    }
+
+    fn progress(&self) -> u8 {
+        self.progress
+    }
+
+    fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    fn is_completed(&self) -> bool {
+        self.status.is_terminal()
+    }
+}
+
 /// Display task status
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum DisplayTaskStatus {
@@ -544,14 +611,32 @@ pub struct DisplayState {
     pub avatar: DisplayAvatarState,
     /// Active tasks
     pub tasks: Vec<DisplayTask>,
+    /// How the task panel is sorted and filtered (restored by the Conductor)
+    pub task_view: TaskView,
     /// Conductor state
     pub conductor_state: ConductorState,
     /// Session info
     pub session_model: String,
     /// Whether system is ready
     pub ready: bool,
+    /// How long the main model has been loading (None once it's warm)
+    pub warmup_elapsed_ms: Option<u64>,
     /// Pending notification (if any)
     pub notification: Option<DisplayNotification>,
+    /// Sequence number of the last token received (0 = none yet)
+    pub last_token_seq: u64,
+    /// Tokens were dropped in transit; a state snapshot should be requested
+    pub resync_needed: bool,
+    /// Collapse repeated notifications and group a turn's replies
+    pub grouping: bool,
+    /// Reasoning streamed for the latest response
+    pub reasoning: String,
+    /// Message the reasoning belongs to
+    pub reasoning_id: Option<MessageId>,
+    /// A `/history` export waiting to be saved
+    pub export: Option<(ExportFormat, String)>,
+    /// Conversations as last listed by the Conductor
+    pub conversations: Vec<ConversationListEntry>,
 }
 
 impl Default for DisplayState {
@@ -561,10 +646,19 @@ impl Default for DisplayState {
             streaming_id: None,
             avatar: DisplayAvatarState::default(),
             tasks: Vec::new(),
+            task_view: TaskView::default(),
             conductor_state: ConductorState::Initializing,
             session_model: String::new(),
             ready: false,
+            warmup_elapsed_ms: None,
             notification: None,
+            last_token_seq: 0,
+            resync_needed: false,
+            grouping: true,
+            reasoning: String::new(),
+            reasoning_id: None,
+            export: None,
+            conversations: Vec::new(),
         }
     }
 }
@@ -580,34 +674,76 @@ impl DisplayState {
         match msg {
             // Conversation messages
             ConductorMessage::Message {
-                id, role, content, ..
+                id,
+                role,
+                content,
+                resend,
+                ..
             } => {
-                self.messages.push(DisplayMessage::new(id, role, content));
+                // A resend repairs our copy instead of duplicating it
+                let existing = resend
+                    .then(|| self.messages.iter_mut().find(|m| m.id == id))
+                    .flatten();
+                match existing {
+                    Some(msg) => msg.content = content,
+                    None => self.messages.push(DisplayMessage::new(id, role, content)),
+                }
             }
-            ConductorMessage::Token { message_id, text } => {
-                // Find or create streaming message
-                if self.streaming_id.as_ref() != Some(&message_id) {
-                    // New streaming message
+            ConductorMessage::StreamStart { message_id, .. } => {
+                // Show the bubble before the first token
+                if !self.messages.iter().any(|m| m.id == message_id) {
                     self.messages
                         .push(DisplayMessage::streaming(message_id.clone()));
+                }
+                self.streaming_id = Some(message_id);
+            }
+            ConductorMessage::Token {
+                message_id,
+                text,
+                seq,
+            } => {
+                self.track_token_seq(seq);
+
+                // Find or create streaming message
+                if self.streaming_id.as_ref() != Some(&message_id) {
+                    if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
+                        // Restored from a snapshot mid-stream
+                        msg.streaming = true;
+                    } else {
+                        // New streaming message
+                        self.messages
+                            .push(DisplayMessage::streaming(message_id.clone()));
+                    }
                     self.streaming_id = Some(message_id.clone());
                 }
                 // Append token
-                if let Some(msg) = self.messages.last_mut() {
-                    if msg.id == message_id {
-                        msg.append(&text);
-                    }
+                if let Some(msg) = self.messages.iter_mut().rev().find(|m| m.id == message_id) {
+                    msg.append(&text);
                 }
             }
+            ConductorMessage::Reasoning { message_id, text } => {
+                if self.reasoning_id.as_ref() != Some(&message_id) {
+                    self.reasoning.clear();
+                    self.reasoning_id = Some(message_id);
+                }
+                self.reasoning.push_str(&text);
+            }
             ConductorMessage::StreamEnd {
                 message_id,
                 final_content,
                 metadata,
             } => {
-                if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
-                    msg.complete(final_content, metadata);
+                self.finish_stream(message_id, final_content, metadata);
+            }
+            ConductorMessage::Welcome { id, content, .. } => {
+                // The streamed greeting's bubble becomes the welcome
+                if self.streaming_id.as_ref() == Some(&id) {
+                    self.streaming_id = None;
+                }
+                match self.messages.iter_mut().find(|m| m.id == id) {
+                    Some(msg) => *msg = DisplayMessage::welcome(id, content),
+                    None => self.messages.push(DisplayMessage::welcome(id, content)),
                 }
-                self.streaming_id = None;
             }
             ConductorMessage::StreamError { message_id, error } => {
                 if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
@@ -616,6 +752,20 @@ impl DisplayState {
                 }
                 self.streaming_id = None;
             }
+            ConductorMessage::StreamCancelled { message_id } => {
+                // Superseded by a newer message; what was shown stays, as it
+                // does in the session
+                if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
+                    msg.streaming = false;
+                }
+                if self.streaming_id.as_ref() == Some(&message_id) {
+                    self.streaming_id = None;
+                }
+                if self.reasoning_id.as_ref() == Some(&message_id) {
+                    self.reasoning.clear();
+                    self.reasoning_id = None;
+                }
+            }
 
             // Avatar messages
             ConductorMessage::AvatarMoveTo { .. }
@@ -625,9 +775,14 @@ impl DisplayState {
             | ConductorMessage::AvatarWander { .. }
             | ConductorMessage::AvatarGesture { .. }
             | ConductorMessage::AvatarReact { .. }
-            | ConductorMessage::AvatarPointAt { .. } => {
+            | ConductorMessage::AvatarPointAt { .. }
+            | ConductorMessage::AvatarActivity { .. }
+            | ConductorMessage::AvatarReset { .. } => {
                 self.avatar.apply_message(&msg);
             }
+            ConductorMessage::AvatarBlink { .. } => {
+                // The TUI's idle sprites already blink on their own
+            }
 
             // Task messages
             ConductorMessage::TaskCreated {
@@ -660,6 +815,9 @@ impl DisplayState {
             ConductorMessage::TaskFocus { .. } => {
                 // UI could highlight the focused task
             }
+            ConductorMessage::TaskView { sort, filter } => {
+                self.task_view = TaskView { sort, filter };
+            }
 
             // System messages
             ConductorMessage::State { state } => {
@@ -668,17 +826,40 @@ impl DisplayState {
             ConductorMessage::SessionInfo { model, ready, .. } => {
                 self.session_model = model;
                 self.ready = ready;
+                if ready {
+                    self.warmup_elapsed_ms = None;
+                }
+            }
+            ConductorMessage::ModelReady { model, primary } => {
+                // Other routing models warming up don't change what's shown
+                if primary {
+                    self.session_model = model;
+                    self.ready = true;
+                    self.warmup_elapsed_ms = None;
+                }
+            }
+            ConductorMessage::WarmupProgress { elapsed_ms } => {
+                self.warmup_elapsed_ms = Some(elapsed_ms);
             }
             ConductorMessage::Notify {
                 level,
                 title,
                 message,
+                guidance,
             } => {
-                self.notification = Some(DisplayNotification {
+                let notification = DisplayNotification {
                     level,
                     title,
                     message,
-                });
+                    guidance,
+                    count: 1,
+                };
+                match self.notification {
+                    Some(ref mut shown) if self.grouping && shown.repeats(&notification) => {
+                        shown.count += 1;
+                    }
+                    _ => self.notification = Some(notification),
+                }
             }
             ConductorMessage::Quit { message } => {
                 // The app will handle quitting
@@ -687,9 +868,38 @@ impl DisplayState {
                         level: conductor_core::NotifyLevel::Info,
                         title: Some("Goodbye".to_string()),
                         message: msg,
+                        guidance: None,
+                        count: 1,
                     });
                 }
             }
+            ConductorMessage::HistoryTruncated { removed } => {
+                self.remove_messages(&removed);
+            }
+            ConductorMessage::MessageEdited {
+                message_id,
+                new_content,
+                removed,
+                ..
+            } => {
+                self.remove_messages(&removed);
+                if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
+                    msg.content = new_content;
+                    msg.invalidate_wrap_cache();
+                }
+            }
+            ConductorMessage::SurfaceFailed { error, .. } => {
+                self.notification = Some(DisplayNotification {
+                    level: conductor_core::NotifyLevel::Warning,
+                    title: Some("Another display disconnected".to_string()),
+                    message: error,
+                    guidance: None,
+                    count: 1,
+                });
+            }
+            ConductorMessage::SystemPromptChanged { .. } => {
+                // Prompt only affects the Conductor's future requests
+            }
             ConductorMessage::Ack { .. } | ConductorMessage::QueryCapabilities => {
                 // No display state change needed
             }
@@ -698,11 +908,49 @@ impl DisplayState {
             ConductorMessage::HandshakeAck { .. } => {
                 // Handshake handled by transport
             }
+            ConductorMessage::NegotiatedCapabilities { .. } => {
+                // The TUI only claims what it can render
+            }
             ConductorMessage::Ping { .. } => {
                 // Heartbeat handled by transport (would send Pong back)
             }
-            ConductorMessage::StateSnapshot { .. } => {
-                // State snapshot handled by transport layer during reconnection
+            ConductorMessage::StateSnapshot {
+                conversation_history,
+                ..
+            } => {
+                // Reconcile with the Conductor's view: fix up messages we have,
+                // add the ones we missed, keep older local scrollback. A turn
+                // inside the snapshot's window that it no longer has was
+                // taken back while we weren't listening, so it goes too.
+                let first_kept = self
+                    .messages
+                    .iter()
+                    .position(|m| conversation_history.iter().any(|s| s.id == m.id));
+                if let Some(first_kept) = first_kept {
+                    let mut index = 0;
+                    let streaming_id = self.streaming_id.clone();
+                    self.messages.retain(|m| {
+                        index += 1;
+                        index <= first_kept
+                            || !matches!(m.role, DisplayRole::User | DisplayRole::Assistant)
+                            || streaming_id.as_ref() == Some(&m.id)
+                            || conversation_history.iter().any(|s| s.id == m.id)
+                    });
+                }
+                for snapshot_msg in conversation_history {
+                    if let Some(msg) = self.messages.iter_mut().find(|m| m.id == snapshot_msg.id) {
+                        msg.content = snapshot_msg.content;
+                    } else {
+                        self.messages.push(DisplayMessage::new(
+                            snapshot_msg.id,
+                            snapshot_msg.role,
+                            snapshot_msg.content,
+                        ));
+                    }
+                }
+                // The next token starts a new baseline
+                self.last_token_seq = 0;
+                self.resync_needed = false;
             }
 
             // Layout hints - future: could control panel visibility
@@ -742,18 +990,96 @@ impl DisplayState {
                 ..
             } => {
                 // For now, treat like regular stream end
-                if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
-                    msg.complete(final_content, metadata);
-                }
-                self.streaming_id = None;
+                self.finish_stream(message_id, final_content, metadata);
             }
             ConductorMessage::SummaryReady { .. } => {
                 // TODO: show summary view
             }
+            ConductorMessage::ExportReady { format, content } => {
+                self.export = Some((format, content));
+            }
             ConductorMessage::ConversationRemoved { .. } => {
                 // TODO: remove conversation from view
             }
+            ConductorMessage::ConversationList { conversations } => {
+                self.conversations = conversations;
+            }
+            ConductorMessage::Health { .. } | ConductorMessage::ModelInfo { .. } => {
+                // Only sent to surfaces that ask; the TUI never does
+            }
+            ConductorMessage::AvatarSprite { .. } => {
+                // The TUI draws its own sprites and never asks for frames
+            }
+        }
+    }
+
+    /// Note a token's sequence number, flagging a resync if any were skipped
+    fn track_token_seq(&mut self, seq: u64) {
+        // Unnumbered tokens (older Conductors) can't be checked
+        if seq == 0 {
+            return;
         }
+        if self.last_token_seq != 0 && seq != self.last_token_seq + 1 {
+            tracing::warn!(
+                expected = self.last_token_seq + 1,
+                received = seq,
+                "Token sequence gap, requesting a state snapshot"
+            );
+            self.resync_needed = true;
+        }
+        self.last_token_seq = seq;
+    }
+
+    /// Whether a state snapshot should be requested (clears the flag)
+    pub fn take_resync_request(&mut self) -> bool {
+        std::mem::take(&mut self.resync_needed)
+    }
+
+    /// Responses waiting in conversations other than the focused one
+    pub fn unread_elsewhere(&self) -> u32 {
+        self.conversations
+            .iter()
+            .filter(|c| !c.focused)
+            .map(|c| c.unread)
+            .sum()
+    }
+
+    /// The `/history` export to save, if one arrived (clears it)
+    pub fn take_export(&mut self) -> Option<(ExportFormat, String)> {
+        self.export.take()
+    }
+
+    /// Gestures/reactions that finished playing since the last call
+    pub fn take_finished_animations(&mut self) -> Vec<OneShotAnimation> {
+        std::mem::take(&mut self.avatar.finished)
+    }
+
+    /// Settle a streamed message on the Conductor's final content
+    ///
+    /// The final content is authoritative. Accumulated tokens can drift from
+    /// it (e.g. a command split across tokens), so the buffer is replaced
+    /// rather than trusted, and a message whose tokens never arrived is added.
+    fn finish_stream(
+        &mut self,
+        message_id: MessageId,
+        final_content: String,
+        metadata: ResponseMetadata,
+    ) {
+        if let Some(msg) = self.messages.iter_mut().find(|m| m.id == message_id) {
+            if msg.content != final_content {
+                tracing::debug!(
+                    streamed_len = msg.content.len(),
+                    final_len = final_content.len(),
+                    "Reconciled streamed message with final content"
+                );
+            }
+            msg.complete(final_content, metadata);
+        } else {
+            let mut msg = DisplayMessage::streaming(message_id);
+            msg.complete(final_content, metadata);
+            self.messages.push(msg);
+        }
+        self.streaming_id = None;
     }
 
     /// Update timers and animations
@@ -761,6 +1087,24 @@ impl DisplayState {
         self.avatar.update(delta);
     }
 
+    /// Reasoning of the message still streaming, if it has any
+    pub fn live_reasoning(&self) -> Option<&str> {
+        let live = self.reasoning_id.is_some() && self.reasoning_id == self.streaming_id;
+        (live && !self.reasoning.is_empty()).then_some(self.reasoning.as_str())
+    }
+
+    /// The last `height` lines of live reasoning wrapped to `width`
+    pub fn live_reasoning_lines(&self, width: usize, height: usize) -> Vec<String> {
+        let Some(reasoning) = self.live_reasoning() else {
+            return Vec::new();
+        };
+        let lines: Vec<String> = textwrap::wrap(reasoning.trim(), width.max(1))
+            .into_iter()
+            .map(|line| line.into_owned())
+            .collect();
+        lines[lines.len().saturating_sub(height)..].to_vec()
+    }
+
     /// Check if there are active tasks
     pub fn has_active_tasks(&self) -> bool {
         self.tasks.iter().any(|t| t.status.is_active())
@@ -776,14 +1120,108 @@ impl DisplayState {
         self.notification = None;
     }
 
+    /// Drop messages the Conductor took out of the conversation
+    fn remove_messages(&mut self, removed: &[MessageId]) {
+        self.messages.retain(|m| !removed.contains(&m.id));
+        if self
+            .streaming_id
+            .as_ref()
+            .is_some_and(|id| removed.contains(id))
+        {
+            self.streaming_id = None;
+        }
+    }
+
     /// Check if currently streaming
     pub fn is_streaming(&self) -> bool {
         self.streaming_id.is_some()
     }
+
+    /// Messages as they're drawn
+    ///
+    /// With grouping on, identical system messages in a row collapse into
+    /// one with a count, and an assistant message that follows another
+    /// continues its turn under the same header.
+    pub fn message_groups(&self) -> Vec<MessageGroup<'_>> {
+        let mut groups: Vec<MessageGroup<'_>> = Vec::new();
+        for message in &self.messages {
+            match groups.last_mut().filter(|_| self.grouping) {
+                Some(group)
+                    if message.role == DisplayRole::System
+                        && group.message.role == DisplayRole::System
+                        && group.message.content == message.content =>
+                {
+                    group.count += 1;
+                }
+                previous => {
+                    let continues_turn = message.role == DisplayRole::Assistant
+                        && previous.is_some_and(|g| g.message.role == DisplayRole::Assistant);
+                    groups.push(MessageGroup {
+                        message,
+                        count: 1,
+                        continues_turn,
+                    });
+                }
+            }
+        }
+        groups
+    }
+
+    /// Show the avatar commands and their syntax (the `/commands` view)
+    pub fn show_command_reference(&mut self) {
+        let mut reference = String::from("Avatar commands:");
+        for spec in CommandParser::supported_commands() {
+            reference.push_str(&format!("\n  {} - {}", spec.usage(), spec.description));
+            if !spec.aliases.is_empty() {
+                reference.push_str(&format!(" (shorthand: {})", spec.aliases.join(", ")));
+            }
+        }
+        self.messages.push(DisplayMessage::new(
+            MessageId::new(),
+            MessageRole::System,
+            reference,
+        ));
+    }
+}
+
+/// One or more messages drawn as one
+#[derive(Clone, Copy, Debug)]
+pub struct MessageGroup<'a> {
+    /// The message drawn for the group
+    pub message: &'a DisplayMessage,
+    /// How many identical messages in a row it stands for
+    pub count: usize,
+    /// Continues the previous reply's turn, so it's drawn without a header
+    pub continues_turn: bool,
+}
+
+impl MessageGroup<'_> {
+    /// Header drawn before the content
+    pub fn prefix(&self) -> &'static str {
+        if self.continues_turn {
+            ""
+        } else {
+            self.message.role.prefix()
+        }
+    }
+
+    /// Content with the repeat count, if any
+    pub fn content(&self) -> String {
+        with_count(&self.message.content, self.count)
+    }
+}
+
+/// `text` with a `×N` suffix when it stands for more than one
+fn with_count(text: &str, count: usize) -> String {
+    if count > 1 {
+        format!("{text} ×{count}")
+    } else {
+        text.to_string()
+    }
 }
 
 /// A notification to display
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DisplayNotification {
     /// Notification level
     pub level: conductor_core::NotifyLevel,
@@ -791,12 +1229,66 @@ pub struct DisplayNotification {
     pub title: Option<String>,
     /// Message content
     pub message: String,
+    /// What the user can do about it (known failures only)
+    pub guidance: Option<String>,
+    /// Times in a row it arrived
+    pub count: u32,
+}
+
+/// Which part of a notification a rendered line belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationPart {
+    /// The notification itself
+    Message,
+    /// Recovery guidance, set apart under the message
+    Guidance,
+}
+
+impl DisplayNotification {
+    /// Whether `other` says the same thing
+    fn repeats(&self, other: &DisplayNotification) -> bool {
+        self.level == other.level
+            && self.title == other.title
+            && self.message == other.message
+            && self.guidance == other.guidance
+    }
+
+    /// Lines wrapped to `width`, guidance indented under an arrow
+    pub fn lines(&self, width: usize) -> Vec<(NotificationPart, String)> {
+        let icon = match self.level {
+            conductor_core::NotifyLevel::Error => "✗",
+            conductor_core::NotifyLevel::Warning => "⚠",
+            conductor_core::NotifyLevel::Success => "✓",
+            conductor_core::NotifyLevel::Info => "•",
+        };
+        let message = with_count(&self.message, self.count as usize);
+        let text = match self.title {
+            Some(ref title) => format!("{icon} {title}: {message}"),
+            None => format!("{icon} {message}"),
+        };
+
+        let mut lines: Vec<_> = textwrap::wrap(&text, width)
+            .into_iter()
+            .map(|line| (NotificationPart::Message, line.into_owned()))
+            .collect();
+        if let Some(ref guidance) = self.guidance {
+            let options = textwrap::Options::new(width)
+                .initial_indent("  → ")
+                .subsequent_indent("    ");
+            lines.extend(
+                textwrap::wrap(guidance, options)
+                    .into_iter()
+                    .map(|line| (NotificationPart::Guidance, line.into_owned())),
+            );
+        }
+        lines
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use conductor_core::{NotifyLevel, SessionId};
+    use conductor_core::{AvatarStateSnapshot, NotifyLevel, SessionId, TaskFilter, TaskSort};
     use pretty_assertions::assert_eq;
 
     // ========================================================================
@@ -878,6 +1370,124 @@ mod tests {
         assert_eq!(DisplayRole::User.prefix(), "You: ");
         assert_eq!(DisplayRole::Assistant.prefix(), "Yollayah: ");
         assert_eq!(DisplayRole::System.prefix(), "");
+        assert_eq!(DisplayRole::Welcome.prefix(), "");
+    }
+
+    #[test]
+    fn test_welcome_is_its_own_role() {
+        use conductor_core::messages::ContentType;
+
+        let mut state = DisplayState::new();
+        state.apply_message(ConductorMessage::Welcome {
+            id: MessageId::new(),
+            content: "¡Hola!".to_string(),
+            is_dynamic: true,
+        });
+        state.apply_message(ConductorMessage::Message {
+            id: MessageId::new(),
+            role: MessageRole::Assistant,
+            content: "Later".to_string(),
+            content_type: ContentType::Plain,
+            resend: false,
+        });
+
+        assert_eq!(state.messages.len(), 2);
+        assert_eq!(state.messages[0].role, DisplayRole::Welcome);
+        assert_eq!(state.messages[0].content, "¡Hola!");
+        assert_eq!(state.messages[1].role, DisplayRole::Assistant);
+    }
+
+    #[test]
+    fn test_stream_start_shows_bubble_before_tokens() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+        state.apply_message(ConductorMessage::StreamStart {
+            message_id: id.clone(),
+            role: MessageRole::Assistant,
+        });
+        assert_eq!(state.messages.len(), 1);
+        assert!(state.messages[0].streaming);
+
+        state.apply_message(ConductorMessage::Token {
+            message_id: id.clone(),
+            text: "Hi".to_string(),
+            seq: 1,
+        });
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].content, "Hi");
+        assert_eq!(state.streaming_id, Some(id));
+    }
+
+    #[test]
+    fn test_greeting_bubble_becomes_welcome() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+        state.apply_message(ConductorMessage::StreamStart {
+            message_id: id.clone(),
+            role: MessageRole::Assistant,
+        });
+        state.apply_message(ConductorMessage::Token {
+            message_id: id.clone(),
+            text: "¡Ho".to_string(),
+            seq: 1,
+        });
+        state.apply_message(ConductorMessage::Welcome {
+            id: id.clone(),
+            content: "¡Hola!".to_string(),
+            is_dynamic: true,
+        });
+
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].role, DisplayRole::Welcome);
+        assert_eq!(state.messages[0].content, "¡Hola!");
+        assert!(!state.messages[0].streaming);
+        assert_eq!(state.streaming_id, None);
+    }
+
+    #[test]
+    fn test_resync_does_not_duplicate_greeting() {
+        use conductor_core::{SessionSnapshot, SnapshotMessage};
+
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+        state.apply_message(ConductorMessage::Welcome {
+            id: id.clone(),
+            content: "¡Hola!".to_string(),
+            is_dynamic: true,
+        });
+        state.apply_message(ConductorMessage::StateSnapshot {
+            conversation_history: vec![SnapshotMessage::new(
+                id,
+                MessageRole::Assistant,
+                "¡Hola!".to_string(),
+            )],
+            avatar_state: AvatarStateSnapshot::default(),
+            session_info: SessionSnapshot::new(
+                SessionId::new(),
+                "test-model".to_string(),
+                true,
+                ConductorState::Ready,
+                0,
+                1,
+            ),
+        });
+
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].role, DisplayRole::Welcome);
+    }
+
+    #[test]
+    fn test_command_reference_lists_every_command() {
+        let mut state = DisplayState::new();
+        state.show_command_reference();
+
+        let reference = &state.messages[0];
+        assert_eq!(reference.role, DisplayRole::System);
+        for spec in CommandParser::supported_commands() {
+            assert!(reference.content.contains(&spec.usage()), "{}", spec.name);
+        }
+        assert!(reference.content.contains("[yolla:mood <happy|"));
+        assert!(reference.content.contains("(shorthand: laugh, gasp"));
     }
 
     // ========================================================================
@@ -944,6 +1554,41 @@ mod tests {
         assert!(!state.wandering);
     }
 
+    #[test]
+    fn test_display_avatar_apply_activity_message() {
+        let mut state = DisplayAvatarState::default();
+        assert_eq!(state.activity, AvatarActivity::None);
+        state.apply_message(&ConductorMessage::AvatarActivity {
+            activity: AvatarActivity::Talking,
+        });
+        assert_eq!(state.activity, AvatarActivity::Talking);
+    }
+
+    #[test]
+    fn test_display_avatar_apply_reset_message() {
+        let mut state = DisplayAvatarState::default();
+        state.apply_message(&ConductorMessage::AvatarMood {
+            mood: AvatarMood::Confused,
+        });
+        state.apply_message(&ConductorMessage::AvatarVisibility { visible: false });
+        state.apply_message(&ConductorMessage::AvatarGesture {
+            gesture: AvatarGesture::Dance,
+            duration_ms: 10_000,
+        });
+        state.apply_message(&ConductorMessage::AvatarActivity {
+            activity: AvatarActivity::Talking,
+        });
+
+        state.apply_message(&ConductorMessage::AvatarReset {
+            state: AvatarStateSnapshot::default(),
+        });
+        assert_eq!(state.mood, AvatarMood::default());
+        assert!(state.visible);
+        assert_eq!(state.target_position, Some(AvatarPosition::default()));
+        assert!(state.current_gesture.is_none());
+        assert_eq!(state.activity, AvatarActivity::Talking);
+    }
+
     #[test]
     fn test_display_avatar_gesture_clears_reaction() {
         let mut state = DisplayAvatarState::default();
@@ -1005,6 +1650,10 @@ mod tests {
 
         state.update(Duration::from_millis(16));
         assert!(state.current_gesture.is_none());
+        assert_eq!(
+            state.finished,
+            [OneShotAnimation::Gesture(AvatarGesture::Wave)]
+        );
     }
 
     #[test]
@@ -1018,6 +1667,10 @@ mod tests {
 
         state.update(Duration::from_millis(16));
         assert!(state.current_reaction.is_none());
+        assert_eq!(
+            state.finished,
+            [OneShotAnimation::Reaction(AvatarReaction::Laugh)]
+        );
     }
 
     #[test]
@@ -1031,6 +1684,7 @@ mod tests {
 
         state.update(Duration::from_millis(16));
         assert!(state.current_gesture.is_some());
+        assert!(state.finished.is_empty());
     }
 
     #[test]
@@ -1265,12 +1919,60 @@ mod tests {
             role: MessageRole::User,
             content: "Hello".to_string(),
             content_type: ContentType::Plain,
+            resend: false,
         });
         assert_eq!(state.messages.len(), 1);
         assert_eq!(state.messages[0].content, "Hello");
         assert_eq!(state.messages[0].id, id);
     }
 
+    #[test]
+    fn test_resent_message_replaces_existing_copy() {
+        use conductor_core::messages::ContentType;
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+        for (content, resend) in [("Hel\u{fffd}o", false), ("Hello", true)] {
+            state.apply_message(ConductorMessage::Message {
+                id: id.clone(),
+                role: MessageRole::Assistant,
+                content: content.to_string(),
+                content_type: ContentType::Plain,
+                resend,
+            });
+        }
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].content, "Hello");
+    }
+
+    #[test]
+    fn test_edited_message_replaced_and_later_turns_dropped() {
+        use conductor_core::messages::ContentType;
+        let mut state = DisplayState::new();
+        let ids = [MessageId::new(), MessageId::new()];
+        for (id, (role, content)) in ids.iter().zip([
+            (MessageRole::User, "Helo"),
+            (MessageRole::Assistant, "Did you mean hello?"),
+        ]) {
+            state.apply_message(ConductorMessage::Message {
+                id: id.clone(),
+                role,
+                content: content.to_string(),
+                content_type: ContentType::Plain,
+                resend: false,
+            });
+        }
+
+        state.apply_message(ConductorMessage::MessageEdited {
+            message_id: ids[0].clone(),
+            old_len: 4,
+            new_content: "Hello".to_string(),
+            removed: vec![ids[1].clone()],
+        });
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].id, ids[0]);
+        assert_eq!(state.messages[0].content, "Hello");
+    }
+
     #[test]
     fn test_display_state_apply_token_creates_streaming() {
         let mut state = DisplayState::new();
@@ -1279,6 +1981,7 @@ mod tests {
         state.apply_message(ConductorMessage::Token {
             message_id: id.clone(),
             text: "Hello ".to_string(),
+            seq: 0,
         });
 
         assert_eq!(state.messages.len(), 1);
@@ -1295,10 +1998,12 @@ mod tests {
         state.apply_message(ConductorMessage::Token {
             message_id: id.clone(),
             text: "Hello ".to_string(),
+            seq: 0,
         });
         state.apply_message(ConductorMessage::Token {
             message_id: id.clone(),
             text: "World!".to_string(),
+            seq: 0,
         });
 
         assert_eq!(state.messages.len(), 1);
@@ -1313,6 +2018,7 @@ mod tests {
         state.apply_message(ConductorMessage::Token {
             message_id: id.clone(),
             text: "Hello".to_string(),
+            seq: 0,
         });
         state.apply_message(ConductorMessage::StreamEnd {
             message_id: id.clone(),
@@ -1326,6 +2032,236 @@ mod tests {
         assert!(!state.messages[0].streaming);
     }
 
+    #[test]
+    fn test_reasoning_shown_live_apart_from_answer() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+        let reasoning = |text: &str| ConductorMessage::Reasoning {
+            message_id: id.clone(),
+            text: text.to_string(),
+        };
+        let token = |text: &str| ConductorMessage::Token {
+            message_id: id.clone(),
+            text: text.to_string(),
+            seq: 0,
+        };
+
+        state.apply_message(ConductorMessage::StreamStart {
+            message_id: id.clone(),
+            role: MessageRole::Assistant,
+        });
+        assert_eq!(state.live_reasoning(), None);
+        state.apply_message(reasoning("The user said hi, "));
+        assert_eq!(state.live_reasoning(), Some("The user said hi, "));
+        state.apply_message(token("Hel"));
+        state.apply_message(reasoning("so greet them"));
+        state.apply_message(token("lo!"));
+
+        assert_eq!(state.messages[0].content, "Hello!");
+        assert_eq!(
+            state.live_reasoning_lines(12, 2),
+            ["said hi, so", "greet them"]
+        );
+
+        state.apply_message(ConductorMessage::StreamEnd {
+            message_id: id.clone(),
+            final_content: "Hello!".to_string(),
+            metadata: ResponseMetadata::default(),
+        });
+        assert_eq!(state.live_reasoning(), None);
+        assert!(state.live_reasoning_lines(12, 2).is_empty());
+    }
+
+    #[test]
+    fn test_stream_end_reconciles_drifted_tokens() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+
+        // A command split across tokens slipped past per-token stripping
+        for text in ["[yolla:wa", "ve]Hi ", "there"] {
+            state.apply_message(ConductorMessage::Token {
+                message_id: id.clone(),
+                text: text.to_string(),
+                seq: 0,
+            });
+        }
+        assert_ne!(state.messages[0].content, "Hi there");
+
+        state.apply_message(ConductorMessage::StreamEnd {
+            message_id: id.clone(),
+            final_content: "Hi there".to_string(),
+            metadata: ResponseMetadata::default(),
+        });
+
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].content, "Hi there");
+        assert!(!state.messages[0].streaming);
+    }
+
+    #[test]
+    fn test_token_gap_requests_resync() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+
+        for (seq, text) in [(1, "Hel"), (2, "lo"), (4, " there")] {
+            state.apply_message(ConductorMessage::Token {
+                message_id: id.clone(),
+                text: text.to_string(),
+                seq,
+            });
+        }
+
+        assert!(state.take_resync_request());
+        assert!(!state.take_resync_request());
+    }
+
+    #[test]
+    fn test_state_snapshot_restores_missed_content() {
+        use conductor_core::{AvatarStateSnapshot, SessionSnapshot, SnapshotMessage};
+
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+
+        for (seq, text) in [(1, "Hel"), (3, " there")] {
+            state.apply_message(ConductorMessage::Token {
+                message_id: id.clone(),
+                text: text.to_string(),
+                seq,
+            });
+        }
+        assert!(state.resync_needed);
+
+        state.apply_message(ConductorMessage::StateSnapshot {
+            conversation_history: vec![SnapshotMessage::new(
+                id.clone(),
+                MessageRole::Assistant,
+                "Hello there".to_string(),
+            )],
+            avatar_state: AvatarStateSnapshot::default(),
+            session_info: SessionSnapshot::new(
+                SessionId::new(),
+                "test-model".to_string(),
+                true,
+                ConductorState::Responding,
+                0,
+                1,
+            ),
+        });
+
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].content, "Hello there");
+        assert!(!state.take_resync_request());
+    }
+
+    #[test]
+    fn test_state_snapshot_drops_messages_taken_back() {
+        use conductor_core::messages::ContentType;
+        use conductor_core::{AvatarStateSnapshot, SessionSnapshot, SnapshotMessage};
+
+        let mut state = DisplayState::new();
+        let question = MessageId::new();
+        let stale = MessageId::new();
+        for (id, role, content) in [
+            (question.clone(), MessageRole::User, "Hi"),
+            (stale.clone(), MessageRole::Assistant, "Old answer"),
+        ] {
+            state.apply_message(ConductorMessage::Message {
+                id,
+                role,
+                content: content.to_string(),
+                content_type: ContentType::Plain,
+                resend: false,
+            });
+        }
+        state.show_command_reference();
+
+        // The HistoryTruncated for the old answer never arrived
+        let answer = MessageId::new();
+        state.apply_message(ConductorMessage::StateSnapshot {
+            conversation_history: vec![
+                SnapshotMessage::new(question.clone(), MessageRole::User, "Hi".to_string()),
+                SnapshotMessage::new(
+                    answer.clone(),
+                    MessageRole::Assistant,
+                    "New answer".to_string(),
+                ),
+            ],
+            avatar_state: AvatarStateSnapshot::default(),
+            session_info: SessionSnapshot::new(
+                SessionId::new(),
+                "test-model".to_string(),
+                true,
+                ConductorState::Ready,
+                0,
+                2,
+            ),
+        });
+
+        let ids: Vec<_> = state.messages.iter().map(|m| m.id.clone()).collect();
+        assert!(!ids.contains(&stale));
+        assert_eq!(ids[0], question);
+        assert_eq!(ids.last(), Some(&answer));
+        // Local system text is ours to keep
+        assert!(state.messages.iter().any(|m| m.role == DisplayRole::System));
+    }
+
+    #[test]
+    fn test_export_ready_is_kept_until_taken() {
+        let mut state = DisplayState::new();
+        assert_eq!(state.take_export(), None);
+
+        state.apply_message(ConductorMessage::ExportReady {
+            format: ExportFormat::Text,
+            content: "You: Hi".to_string(),
+        });
+
+        assert_eq!(
+            state.take_export(),
+            Some((ExportFormat::Text, "You: Hi".to_string()))
+        );
+        assert_eq!(state.take_export(), None);
+    }
+
+    #[test]
+    fn test_conversation_list_counts_unread_elsewhere() {
+        use conductor_core::{ConversationId, ConversationState};
+
+        let entry = |conversation_id, unread, focused| ConversationListEntry {
+            conversation_id,
+            title: "Chat".to_string(),
+            state: ConversationState::Idle,
+            unread,
+            focused,
+        };
+        let mut state = DisplayState::new();
+        assert_eq!(state.unread_elsewhere(), 0);
+
+        state.apply_message(ConductorMessage::ConversationList {
+            conversations: vec![
+                entry(ConversationId::main(), 2, false),
+                entry(ConversationId::new(), 1, true),
+                entry(ConversationId::new(), 3, false),
+            ],
+        });
+        assert_eq!(state.unread_elsewhere(), 5);
+    }
+
+    #[test]
+    fn test_stream_end_without_tokens_adds_message() {
+        let mut state = DisplayState::new();
+
+        state.apply_message(ConductorMessage::StreamEnd {
+            message_id: MessageId::new(),
+            final_content: "Hi there".to_string(),
+            metadata: ResponseMetadata::default(),
+        });
+
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].role, DisplayRole::Assistant);
+        assert_eq!(state.messages[0].content, "Hi there");
+        assert!(!state.is_streaming());
+    }
+
     #[test]
     fn test_display_state_apply_stream_error() {
         let mut state = DisplayState::new();
@@ -1334,6 +2270,7 @@ mod tests {
         state.apply_message(ConductorMessage::Token {
             message_id: id.clone(),
             text: "Partial".to_string(),
+            seq: 0,
         });
         state.apply_message(ConductorMessage::StreamError {
             message_id: id.clone(),
@@ -1345,6 +2282,24 @@ mod tests {
         assert!(!state.messages[0].streaming);
     }
 
+    #[test]
+    fn test_stream_cancelled_keeps_partial_message() {
+        let mut state = DisplayState::new();
+        let id = MessageId::new();
+
+        state.apply_message(ConductorMessage::Token {
+            message_id: id.clone(),
+            text: "Partial".to_string(),
+            seq: 0,
+        });
+        state.apply_message(ConductorMessage::StreamCancelled { message_id: id });
+
+        assert!(!state.is_streaming());
+        assert_eq!(state.messages.len(), 1);
+        assert_eq!(state.messages[0].content, "Partial");
+        assert!(!state.messages[0].streaming);
+    }
+
     #[test]
     fn test_display_state_task_lifecycle() {
         let mut state = DisplayState::new();
@@ -1432,6 +2387,28 @@ mod tests {
         assert_eq!(state.session_model, "yollayah");
     }
 
+    #[test]
+    fn test_warmup_progress_until_model_ready() {
+        let mut state = DisplayState::new();
+        state.apply_message(ConductorMessage::WarmupProgress { elapsed_ms: 500 });
+        state.apply_message(ConductorMessage::WarmupProgress { elapsed_ms: 1000 });
+        assert_eq!(state.warmup_elapsed_ms, Some(1000));
+
+        // A routing model warming up isn't the main model
+        state.apply_message(ConductorMessage::ModelReady {
+            model: "coder".to_string(),
+            primary: false,
+        });
+        assert_eq!(state.warmup_elapsed_ms, Some(1000));
+
+        state.apply_message(ConductorMessage::ModelReady {
+            model: "yollayah".to_string(),
+            primary: true,
+        });
+        assert_eq!(state.warmup_elapsed_ms, None);
+        assert!(state.ready);
+    }
+
     #[test]
     fn test_display_state_notification() {
         let mut state = DisplayState::new();
@@ -1441,6 +2418,7 @@ mod tests {
             level: NotifyLevel::Warning,
             title: Some("Heads up".to_string()),
             message: "Something happened".to_string(),
+            guidance: None,
         });
 
         assert!(state.notification.is_some());
@@ -1449,6 +2427,131 @@ mod tests {
         assert_eq!(notif.message, "Something happened");
     }
 
+    #[test]
+    fn test_notification_guidance_rendered_apart() {
+        let mut state = DisplayState::new();
+        state.apply_message(ConductorMessage::Notify {
+            level: NotifyLevel::Error,
+            title: None,
+            message: "Failed to send message: connection refused".to_string(),
+            guidance: Some(
+                conductor_core::ConductorError::BackendUnreachable
+                    .guidance()
+                    .to_string(),
+            ),
+        });
+
+        let lines = state.notification.as_ref().unwrap().lines(40);
+        assert_eq!(
+            lines[0],
+            (
+                NotificationPart::Message,
+                "✗ Failed to send message: connection".to_string()
+            )
+        );
+        let guidance: Vec<_> = lines
+            .iter()
+            .filter(|(part, _)| *part == NotificationPart::Guidance)
+            .map(|(_, line)| line.as_str())
+            .collect();
+        assert_eq!(
+            guidance,
+            [
+                "  → Start the model server (for Ollama,",
+                "    run `ollama serve`), then try again.",
+            ]
+        );
+    }
+
+    fn backend_unavailable() -> ConductorMessage {
+        ConductorMessage::Notify {
+            level: NotifyLevel::Warning,
+            title: None,
+            message: "Backend not available".to_string(),
+            guidance: None,
+        }
+    }
+
+    #[test]
+    fn test_repeated_notifications_grouped_with_count() {
+        let mut state = DisplayState::new();
+        for _ in 0..3 {
+            state.apply_message(backend_unavailable());
+        }
+
+        let lines = state.notification.as_ref().unwrap().lines(80);
+        assert_eq!(
+            lines,
+            [(
+                NotificationPart::Message,
+                "⚠ Backend not available ×3".to_string()
+            )]
+        );
+
+        // A different notification starts a new group
+        state.apply_message(ConductorMessage::Notify {
+            level: NotifyLevel::Success,
+            title: None,
+            message: "Backend is back".to_string(),
+            guidance: None,
+        });
+        assert_eq!(state.notification.as_ref().unwrap().count, 1);
+
+        let mut ungrouped = DisplayState {
+            grouping: false,
+            ..DisplayState::new()
+        };
+        for _ in 0..3 {
+            ungrouped.apply_message(backend_unavailable());
+        }
+        assert_eq!(ungrouped.notification.as_ref().unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_message_groups() {
+        use conductor_core::messages::ContentType;
+
+        let mut state = DisplayState::new();
+        let messages = [
+            (MessageRole::User, "Hi"),
+            (MessageRole::Assistant, "Hello!"),
+            (MessageRole::Assistant, "Anything else?"),
+            (MessageRole::System, "Backend not available"),
+            (MessageRole::System, "Backend not available"),
+            (MessageRole::System, "Backend not available"),
+            (MessageRole::Assistant, "Back now."),
+        ];
+        for (role, content) in messages {
+            state.apply_message(ConductorMessage::Message {
+                id: MessageId::new(),
+                role,
+                content: content.to_string(),
+                content_type: ContentType::Plain,
+                resend: false,
+            });
+        }
+
+        let drawn: Vec<_> = state
+            .message_groups()
+            .iter()
+            .map(|g| format!("{}{}", g.prefix(), g.content()))
+            .collect();
+        assert_eq!(
+            drawn,
+            [
+                "You: Hi",
+                "Yollayah: Hello!",
+                "Anything else?",
+                "Backend not available ×3",
+                "Yollayah: Back now.",
+            ]
+        );
+
+        state.grouping = false;
+        assert_eq!(state.message_groups().len(), messages.len());
+        assert!(state.message_groups().iter().all(|g| !g.continues_turn));
+    }
+
     #[test]
     fn test_display_state_clear_notification() {
         let mut state = DisplayState::new();
@@ -1456,6 +2559,8 @@ mod tests {
             level: NotifyLevel::Info,
             title: None,
             message: "Test".to_string(),
+            guidance: None,
+            count: 1,
         });
 
         state.clear_notification();
@@ -1474,6 +2579,49 @@ mod tests {
         assert_eq!(notif.message, "Goodbye!");
     }
 
+    #[test]
+    fn test_task_start_time_recorded_when_running() {
+        let mut state = DisplayState::new();
+        state.apply_message(ConductorMessage::TaskCreated {
+            task_id: TaskId::new("t1"),
+            agent: "a".to_string(),
+            description: "d".to_string(),
+        });
+        assert_eq!(state.tasks[0].started_at, None);
+
+        state.apply_message(ConductorMessage::TaskUpdated {
+            task_id: TaskId::new("t1"),
+            progress: 10,
+            status_message: None,
+        });
+        let started = state.tasks[0].started_at;
+        assert!(started.is_some());
+
+        // Later progress doesn't move the start
+        state.apply_message(ConductorMessage::TaskUpdated {
+            task_id: TaskId::new("t1"),
+            progress: 50,
+            status_message: None,
+        });
+        assert_eq!(state.tasks[0].started_at, started);
+    }
+
+    #[test]
+    fn test_task_view_restored_from_conductor() {
+        let mut state = DisplayState::new();
+        state.apply_message(ConductorMessage::TaskView {
+            sort: TaskSort::Progress,
+            filter: TaskFilter::All,
+        });
+        assert_eq!(
+            state.task_view,
+            TaskView {
+                sort: TaskSort::Progress,
+                filter: TaskFilter::All,
+            }
+        );
+    }
+
     #[test]
     fn test_display_state_active_tasks_iterator() {
         let mut state = DisplayState::new();
@@ -0,0 +1,279 @@
+//! Input History
+//!
+//! Sent prompts, recalled with Up/Down like a shell. Whatever was typed
+//! before browsing is kept as a draft and comes back when navigating past
+//! the newest entry.
+//!
+//! History can be saved to a file (one JSON string per line, so multi-line
+//! prompts survive) and is reloaded from it on the next start.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Entries kept when no size is configured
+pub const DEFAULT_HISTORY_SIZE: usize = 50;
+
+/// Ring buffer of sent prompts with shell-style navigation
+#[derive(Clone, Debug)]
+pub struct InputHistory {
+    /// Sent prompts, oldest first
+    entries: VecDeque<String>,
+    /// Most entries kept (0 = history off)
+    capacity: usize,
+    /// Entry shown in the input box (None = the draft)
+    index: Option<usize>,
+    /// What was typed before browsing started
+    draft: String,
+    /// File the history is saved to, if persisted
+    path: Option<PathBuf>,
+}
+
+impl InputHistory {
+    /// Empty in-memory history keeping up to `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            index: None,
+            draft: String::new(),
+            path: None,
+        }
+    }
+
+    /// History saved to `path`, starting from whatever it already holds
+    ///
+    /// A missing or unreadable file starts an empty history.
+    pub fn persisted(capacity: usize, path: PathBuf) -> Self {
+        let mut history = Self::new(capacity);
+        match load_entries(&path) {
+            Ok(entries) => {
+                for entry in entries {
+                    history.remember(entry);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Couldn't load input history");
+            }
+        }
+        history.path = Some(path);
+        history
+    }
+
+    /// Record a sent prompt and stop browsing
+    ///
+    /// Repeating the newest entry doesn't add it twice.
+    pub fn push(&mut self, entry: String) {
+        self.index = None;
+        self.draft.clear();
+        if !self.remember(entry) {
+            return;
+        }
+        if let Some(ref path) = self.path {
+            if let Err(e) = save_entries(path, &self.entries) {
+                tracing::warn!(path = %path.display(), error = %e, "Couldn't save input history");
+            }
+        }
+    }
+
+    /// Step to the next older entry (Up)
+    ///
+    /// `current` is the input box's contents, kept as the draft when
+    /// browsing starts. Returns the entry to show, or None if there's
+    /// nothing older.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let index = match self.index {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(index) => index - 1,
+        };
+        self.index = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Step to the next newer entry (Down)
+    ///
+    /// Past the newest entry the draft comes back. Returns None when not
+    /// browsing.
+    pub fn newer(&mut self) -> Option<String> {
+        let index = self.index?;
+        if index + 1 < self.entries.len() {
+            self.index = Some(index + 1);
+            self.entries.get(index + 1).cloned()
+        } else {
+            self.index = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Whether an entry (rather than the draft) is being shown
+    pub fn is_browsing(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Sent prompts, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Number of remembered prompts
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been sent yet
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Append an entry, evicting the oldest beyond capacity
+    ///
+    /// Returns false if the entry wasn't added.
+    fn remember(&mut self, entry: String) -> bool {
+        if self.capacity == 0 || self.entries.back() == Some(&entry) {
+            return false;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        true
+    }
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_SIZE)
+    }
+}
+
+/// Read saved entries, skipping lines that don't parse
+fn load_entries(path: &Path) -> io::Result<Vec<String>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Write all entries, replacing the file
+fn save_entries(path: &Path, entries: &VecDeque<String>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sent(prompts: &[&str]) -> InputHistory {
+        let mut history = InputHistory::new(DEFAULT_HISTORY_SIZE);
+        for prompt in prompts {
+            history.push(prompt.to_string());
+        }
+        history
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "yollayah-history-{}-{name}.jsonl",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_navigating_up_shows_prior_prompts_and_down_restores_draft() {
+        let mut history = sent(&["first", "second", "third"]);
+        let mut input = "half-typ".to_string();
+
+        for expected in ["third", "second", "first"] {
+            input = history.older(&input).unwrap().to_string();
+            assert_eq!(input, expected);
+        }
+        // Nothing older than the first prompt
+        assert_eq!(history.older(&input), None);
+
+        for expected in ["second", "third", "half-typ"] {
+            input = history.newer().unwrap();
+            assert_eq!(input, expected);
+        }
+        assert!(!history.is_browsing());
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn test_sending_resets_navigation() {
+        let mut history = sent(&["first", "second"]);
+        history.older("draft");
+        history.push("third".to_string());
+
+        assert!(!history.is_browsing());
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older(""), Some("third"));
+        assert_eq!(history.newer().as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_repeated_prompt_is_stored_once() {
+        let history = sent(&["hi", "hi", "bye", "hi"]);
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["hi", "bye", "hi"]);
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let mut history = InputHistory::new(2);
+        for prompt in ["one", "two", "three"] {
+            history.push(prompt.to_string());
+        }
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["two", "three"]);
+
+        let mut off = InputHistory::new(0);
+        off.push("ignored".to_string());
+        assert!(off.is_empty());
+        assert_eq!(off.older("draft"), None);
+    }
+
+    #[test]
+    fn test_persisted_history_survives_restart() {
+        let path = temp_path("restart");
+        let _ = fs::remove_file(&path);
+
+        let mut history = InputHistory::persisted(3, path.clone());
+        for prompt in ["one", "two\nlines", "three", "four"] {
+            history.push(prompt.to_string());
+        }
+
+        let mut reloaded = InputHistory::persisted(3, path.clone());
+        assert_eq!(
+            reloaded.entries().collect::<Vec<_>>(),
+            ["two\nlines", "three", "four"]
+        );
+        assert_eq!(reloaded.older(""), Some("four"));
+
+        // A smaller size keeps only the newest saved entries
+        let smaller = InputHistory::persisted(1, path.clone());
+        assert_eq!(smaller.entries().collect::<Vec<_>>(), ["four"]);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_missing_file_starts_empty() {
+        let path = temp_path("missing");
+        let _ = fs::remove_file(&path);
+        assert!(InputHistory::persisted(5, path).is_empty());
+    }
+}
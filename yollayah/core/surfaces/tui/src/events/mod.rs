@@ -1,6 +1,195 @@
 //! Event Handling
 //!
 //! Keyboard and mouse event processing.
+//!
+//! Most event handling is still done directly in app.rs; this module holds
+//! the pieces that are worth testing without a terminal.
+
+mod history;
+
+pub use history::{InputHistory, DEFAULT_HISTORY_SIZE};
+
+use clap::ValueEnum;
+use crossterm::event::{KeyEvent, KeyModifiers};
+
+/// Which Enter combination sends the input line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SubmitKey {
+    /// Enter sends; Shift+Enter (or Ctrl+Enter) inserts a newline
+    #[default]
+    Enter,
+    /// Shift+Enter (or Ctrl+Enter) sends; Enter inserts a newline
+    ShiftEnter,
+}
+
+/// What an Enter key press does to the input buffer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnterAction {
+    /// Send the input as a user message
+    Submit,
+    /// Insert a literal newline at the cursor
+    Newline,
+}
+
+impl SubmitKey {
+    /// Action for an Enter press with the given modifiers
+    ///
+    /// Ctrl counts the same as Shift, since many terminals can't tell
+    /// Shift+Enter from plain Enter but do report Ctrl.
+    pub fn enter_action(self, modifiers: KeyModifiers) -> EnterAction {
+        let modified = modifiers.intersects(KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        match (self, modified) {
+            (SubmitKey::Enter, false) | (SubmitKey::ShiftEnter, true) => EnterAction::Submit,
+            (SubmitKey::Enter, true) | (SubmitKey::ShiftEnter, false) => EnterAction::Newline,
+        }
+    }
+}
+
+/// Insert a character at a cursor position counted in chars
+pub fn insert_at_cursor(buffer: &mut String, cursor: &mut usize, c: char) {
+    let byte_pos = buffer
+        .char_indices()
+        .nth(*cursor)
+        .map(|(i, _)| i)
+        .unwrap_or(buffer.len());
+    buffer.insert(byte_pos, c);
+    *cursor += 1;
+}
+
+/// Apply an Enter press to the input line
+///
+/// Returns the line to send as a user message if the press submits and the
+/// line isn't empty, leaving the buffer cleared. Otherwise a newline is
+/// inserted at the cursor.
+pub fn apply_enter(
+    policy: SubmitKey,
+    key: &KeyEvent,
+    buffer: &mut String,
+    cursor: &mut usize,
+) -> Option<String> {
+    match policy.enter_action(key.modifiers) {
+        EnterAction::Submit if buffer.is_empty() => None,
+        EnterAction::Submit => {
+            *cursor = 0;
+            Some(std::mem::take(buffer))
+        }
+        EnterAction::Newline => {
+            insert_at_cursor(buffer, cursor, '\n');
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    fn enter(modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(KeyCode::Enter, modifiers)
+    }
+
+    /// Type `text`, press Enter, and return (submitted line, buffer, cursor)
+    fn type_then_enter(
+        policy: SubmitKey,
+        text: &str,
+        modifiers: KeyModifiers,
+    ) -> (Option<String>, String, usize) {
+        let mut buffer = String::new();
+        let mut cursor = 0;
+        for c in text.chars() {
+            insert_at_cursor(&mut buffer, &mut cursor, c);
+        }
+        let submitted = apply_enter(policy, &enter(modifiers), &mut buffer, &mut cursor);
+        (submitted, buffer, cursor)
+    }
+
+    #[test]
+    fn test_enter_policy_submits_on_plain_enter() {
+        let (submitted, buffer, cursor) =
+            type_then_enter(SubmitKey::Enter, "hello", KeyModifiers::NONE);
+        assert_eq!(submitted.as_deref(), Some("hello"));
+        assert!(buffer.is_empty());
+        assert_eq!(cursor, 0);
+
+        for modifiers in [KeyModifiers::SHIFT, KeyModifiers::CONTROL] {
+            let (submitted, buffer, cursor) = type_then_enter(SubmitKey::Enter, "hello", modifiers);
+            assert_eq!(submitted, None);
+            assert_eq!(buffer, "hello\n");
+            assert_eq!(cursor, 6);
+        }
+    }
+
+    #[test]
+    fn test_shift_enter_policy_submits_on_modified_enter() {
+        let (submitted, buffer, cursor) =
+            type_then_enter(SubmitKey::ShiftEnter, "hello", KeyModifiers::NONE);
+        assert_eq!(submitted, None);
+        assert_eq!(buffer, "hello\n");
+        assert_eq!(cursor, 6);
+
+        for modifiers in [KeyModifiers::SHIFT, KeyModifiers::CONTROL] {
+            let (submitted, buffer, _) = type_then_enter(SubmitKey::ShiftEnter, "hello", modifiers);
+            assert_eq!(submitted.as_deref(), Some("hello"));
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_multi_line_input_is_submitted_whole() {
+        let mut buffer = String::new();
+        let mut cursor = 0;
+        let policy = SubmitKey::ShiftEnter;
+        for c in "one".chars() {
+            insert_at_cursor(&mut buffer, &mut cursor, c);
+        }
+        assert_eq!(
+            apply_enter(policy, &enter(KeyModifiers::NONE), &mut buffer, &mut cursor),
+            None
+        );
+        for c in "two".chars() {
+            insert_at_cursor(&mut buffer, &mut cursor, c);
+        }
+        let submitted = apply_enter(
+            policy,
+            &enter(KeyModifiers::SHIFT),
+            &mut buffer,
+            &mut cursor,
+        );
+        assert_eq!(submitted.as_deref(), Some("one\ntwo"));
+    }
+
+    #[test]
+    fn test_empty_input_is_not_submitted() {
+        let (submitted, buffer, _) = type_then_enter(SubmitKey::Enter, "", KeyModifiers::NONE);
+        assert_eq!(submitted, None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_newline_inserted_at_cursor() {
+        let mut buffer = "ab".to_string();
+        let mut cursor = 1;
+        let submitted = apply_enter(
+            SubmitKey::Enter,
+            &enter(KeyModifiers::SHIFT),
+            &mut buffer,
+            &mut cursor,
+        );
+        assert_eq!(submitted, None);
+        assert_eq!(buffer, "a\nb");
+        assert_eq!(cursor, 2);
+    }
 
-// Currently, event handling is done directly in app.rs
-// This module is a placeholder for future refactoring
+    #[test]
+    fn test_other_modifiers_do_not_change_enter() {
+        assert_eq!(
+            SubmitKey::Enter.enter_action(KeyModifiers::ALT),
+            EnterAction::Submit
+        );
+        assert_eq!(
+            SubmitKey::ShiftEnter.enter_action(KeyModifiers::ALT),
+            EnterAction::Newline
+        );
+    }
+}
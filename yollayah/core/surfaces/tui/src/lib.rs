@@ -23,6 +23,7 @@
 pub mod app;
 pub mod avatar;
 pub mod backend;
+pub mod cli;
 pub mod compositor;
 pub mod conductor_client;
 pub mod display;
@@ -33,6 +34,8 @@ pub mod theme;
 pub mod widgets;
 
 pub use app::App;
+pub use cli::Args;
 pub use conductor_client::ConductorClient;
 pub use display::{DisplayAvatarState, DisplayMessage, DisplayState, DisplayTask};
+pub use events::SubmitKey;
 pub use tasks::{BackgroundTask, TaskPanel, TaskState};
@@ -6,25 +6,33 @@
 //!   yollayah-tui [OPTIONS]
 //!
 //! Options:
-//!   --ollama-host <HOST>  Ollama host (default: localhost)
-//!   --ollama-port <PORT>  Ollama port (default: 11434)
-//!   --server-mode         Connect via ai-way server instead of direct Ollama
+//!   --no-greeting            Don't greet when the TUI connects
+//!   --system-prompt <PROMPT> System prompt for the conversation
+//!   --submit-key <KEY>       `enter` (default) or `shift-enter` to send
+//!   --autoscroll-mode <MODE> `always` (default), `streaming-only` or `never`
 
 use std::io;
 use std::panic;
 
+use clap::Parser;
 use crossterm::{
+    event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use yollayah_tui::App;
+use yollayah_tui::{App, Args};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
     // Set up logging
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_target(false))
@@ -53,6 +61,7 @@ async fn main() -> anyhow::Result<()> {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         // Restore terminal before printing panic
+        let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
         let _ = disable_raw_mode();
         let _ = execute!(io::stdout(), LeaveAlternateScreen);
         original_hook(panic_info);
@@ -62,14 +71,25 @@ async fn main() -> anyhow::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // Ask for modifier-aware key reports so Shift+Enter differs from Enter
+    let keyboard_enhanced = supports_keyboard_enhancement().unwrap_or(false);
+    if keyboard_enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
     // Run the app
-    let result = run_app(&mut terminal).await;
+    let result = run_app(&mut terminal, &args).await;
 
     // Restore terminal
+    if keyboard_enhanced {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -78,10 +98,20 @@ async fn main() -> anyhow::Result<()> {
     result
 }
 
-async fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> anyhow::Result<()> {
-    let mut app = App::new().await?;
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    args: &Args,
+) -> anyhow::Result<()> {
+    let mut app = App::with_args(args).await?;
     app.run(terminal).await?;
 
+    let stats = app.frame_stats();
+    tracing::debug!(
+        frames = stats.frames,
+        cells_written = stats.total_cells,
+        "Render stats"
+    );
+
     // Show goodbye message after TUI closes
     if let Some(goodbye) = app.goodbye() {
         // Print with Yollayah styling (magenta)
@@ -2,9 +2,12 @@
 //!
 //! Displays background specialist tasks in a panel on the right side of the TUI.
 //! Shows task progress, status, and which family member is working on it.
+//! The panel can be sorted and filtered (see [`TaskSort`] and [`TaskFilter`]).
 
 mod renderer;
 mod state;
+mod view;
 
 pub use renderer::TaskPanel;
 pub use state::{BackgroundTask, TaskState, TaskStatus};
+pub use view::{apply_view, TaskFilter, TaskSort, TaskView, ViewableTask};
@@ -7,10 +7,13 @@ use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 
 use super::state::{BackgroundTask, TaskState, TaskStatus};
+use super::view::{TaskFilter, TaskSort, TaskView};
 
 /// Task panel widget
 pub struct TaskPanel {
     state: TaskState,
+    /// Current task ordering and filter
+    view: TaskView,
 }
 
 impl TaskPanel {
@@ -18,17 +21,55 @@ impl TaskPanel {
     pub fn new() -> Self {
         Self {
             state: TaskState::new(),
+            view: TaskView::default(),
         }
     }
 
+    /// Create a task panel showing the given tasks (no filesystem)
+    pub fn with_tasks(tasks: Vec<BackgroundTask>) -> Self {
+        let mut panel = Self::new();
+        panel.state.set_tasks(tasks);
+        panel
+    }
+
+    /// Get the current sort order
+    pub fn sort(&self) -> TaskSort {
+        self.view.sort
+    }
+
+    /// Get the current filter
+    pub fn filter(&self) -> TaskFilter {
+        self.view.filter
+    }
+
+    /// Set sort order and filter (e.g. restored from a saved preference)
+    pub fn set_view(&mut self, sort: TaskSort, filter: TaskFilter) {
+        self.view = TaskView { sort, filter };
+    }
+
+    /// Switch to the next sort order (keybinding)
+    pub fn cycle_sort(&mut self) -> TaskSort {
+        self.view.cycle_sort()
+    }
+
+    /// Toggle hiding completed tasks (keybinding)
+    pub fn toggle_filter(&mut self) -> TaskFilter {
+        self.view.toggle_filter()
+    }
+
+    /// Tasks in display order, after sorting and filtering
+    pub fn visible_tasks(&self) -> Vec<&BackgroundTask> {
+        self.view.apply(self.state.all_tasks())
+    }
+
     /// Refresh task state from filesystem
     pub fn refresh(&mut self) {
         self.state.refresh();
     }
 
-    /// Check if panel should be visible (has active tasks)
+    /// Check if panel should be visible (has tasks to show)
     pub fn should_show(&self) -> bool {
-        self.state.has_active_tasks()
+        !self.visible_tasks().is_empty()
     }
 
     /// Get active task count
@@ -47,8 +88,8 @@ impl TaskPanel {
             return;
         }
 
-        let active_tasks = self.state.active_tasks();
-        if active_tasks.is_empty() {
+        let tasks = self.visible_tasks();
+        if tasks.is_empty() {
             return;
         }
 
@@ -74,7 +115,7 @@ impl TaskPanel {
         );
 
         let mut y = task_area.y;
-        for task in active_tasks.iter().take((task_area.height / 3) as usize) {
+        for task in tasks.iter().take((task_area.height / 3) as usize) {
             self.draw_task(buf, task, task_area.x, y, task_area.width);
             y += 3;
             if y >= task_area.y + task_area.height {
@@ -148,3 +189,101 @@ impl Default for TaskPanel {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn task(id: &str, status: TaskStatus, progress: u8, started_secs: u64) -> BackgroundTask {
+        BackgroundTask {
+            id: id.to_string(),
+            agent: "qa-engineer".to_string(),
+            family_name: id.to_string(),
+            description: format!("{id} work"),
+            status,
+            progress,
+            started_at: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(started_secs)),
+        }
+    }
+
+    fn sample_panel() -> TaskPanel {
+        TaskPanel::with_tasks(vec![
+            task("pending", TaskStatus::Pending, 0, 30),
+            task("done", TaskStatus::Done, 100, 10),
+            task("running", TaskStatus::Running, 40, 20),
+            task("failed", TaskStatus::Failed, 70, 40),
+        ])
+    }
+
+    fn rendered_names(panel: &TaskPanel) -> Vec<String> {
+        let area = Rect::new(0, 0, 30, 20);
+        let mut buf = Buffer::empty(area);
+        panel.render(&mut buf, area);
+
+        // Task names are on the first line of each 3-line block
+        (1..area.height - 1)
+            .step_by(3)
+            .map(|y| {
+                (1..area.width - 1)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .filter_map(|line| {
+                ["pending", "done", "running", "failed"]
+                    .into_iter()
+                    .find(|name| line.contains(name))
+                    .map(String::from)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_view_hides_completed_sorted_by_status() {
+        let panel = sample_panel();
+        assert_eq!(panel.sort(), TaskSort::Status);
+        assert_eq!(panel.filter(), TaskFilter::HideCompleted);
+        assert_eq!(rendered_names(&panel), ["running", "pending"]);
+    }
+
+    #[test]
+    fn test_sort_by_status_shows_all() {
+        let mut panel = sample_panel();
+        panel.toggle_filter();
+        assert_eq!(
+            rendered_names(&panel),
+            ["running", "pending", "done", "failed"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_progress() {
+        let mut panel = sample_panel();
+        panel.set_view(TaskSort::Progress, TaskFilter::All);
+        assert_eq!(
+            rendered_names(&panel),
+            ["done", "failed", "running", "pending"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_start_time() {
+        let mut panel = sample_panel();
+        panel.set_view(TaskSort::StartTime, TaskFilter::All);
+        assert_eq!(
+            rendered_names(&panel),
+            ["done", "running", "pending", "failed"]
+        );
+
+        panel.toggle_filter();
+        assert_eq!(rendered_names(&panel), ["running", "pending"]);
+    }
+
+    #[test]
+    fn test_cycle_sort_wraps() {
+        let mut panel = sample_panel();
+        assert_eq!(panel.cycle_sort(), TaskSort::Progress);
+        assert_eq!(panel.cycle_sort(), TaskSort::StartTime);
+        assert_eq!(panel.cycle_sort(), TaskSort::Status);
+    }
+}
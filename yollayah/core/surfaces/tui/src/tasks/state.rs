@@ -4,6 +4,9 @@
 
 use std::fs;
 use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::view::ViewableTask;
 
 /// Status of a background task
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +39,22 @@ impl TaskStatus {
             TaskStatus::Unknown => "❓",
         }
     }
+
+    /// Sort rank (running first, then pending, then done)
+    pub fn rank(&self) -> u8 {
+        match self {
+            TaskStatus::Running => 0,
+            TaskStatus::Pending => 1,
+            TaskStatus::Done => 2,
+            TaskStatus::Failed => 3,
+            TaskStatus::Unknown => 4,
+        }
+    }
+
+    /// Whether the task has finished (done or failed)
+    pub fn is_completed(&self) -> bool {
+        matches!(self, TaskStatus::Done | TaskStatus::Failed)
+    }
 }
 
 /// A background specialist task
@@ -47,6 +66,7 @@ pub struct BackgroundTask {
     pub description: String,
     pub status: TaskStatus,
     pub progress: u8,
+    pub started_at: Option<SystemTime>,
 }
 
 impl BackgroundTask {
@@ -68,6 +88,24 @@ impl BackgroundTask {
     }
 }
 
+impl ViewableTask for BackgroundTask {
+    fn status_rank(&self) -> u8 {
+        self.status.rank()
+    }
+
+    fn progress(&self) -> u8 {
+        self.progress
+    }
+
+    fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    fn is_completed(&self) -> bool {
+        self.status.is_completed()
+    }
+}
+
 /// Manager for reading task state
 pub struct TaskState {
     tasks_dir: PathBuf,
@@ -112,13 +150,12 @@ impl TaskState {
         }
 
         // Sort by status (running first, then pending, then done)
-        self.cached_tasks.sort_by_key(|t| match t.status {
-            TaskStatus::Running => 0,
-            TaskStatus::Pending => 1,
-            TaskStatus::Done => 2,
-            TaskStatus::Failed => 3,
-            TaskStatus::Unknown => 4,
-        });
+        self.cached_tasks.sort_by_key(|t| t.status.rank());
+    }
+
+    /// Replace the task list (e.g. with tasks not read from the filesystem)
+    pub fn set_tasks(&mut self, tasks: Vec<BackgroundTask>) {
+        self.cached_tasks = tasks;
     }
 
     /// Read a single task from its directory
@@ -146,6 +183,11 @@ impl TaskState {
         // Get family name from agent ID
         let family_name = Self::agent_to_family_name(&agent);
 
+        // The task directory is created when the task starts
+        let started_at = fs::metadata(task_dir)
+            .and_then(|m| m.created().or_else(|_| m.modified()))
+            .ok();
+
         Some(BackgroundTask {
             id,
             agent,
@@ -153,6 +195,7 @@ impl TaskState {
             description,
             status: TaskStatus::from_str(&status_str),
             progress,
+            started_at,
         })
     }
 
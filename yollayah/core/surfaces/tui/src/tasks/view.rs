@@ -0,0 +1,68 @@
+//! Task View Sorting and Filtering
+//!
+//! Applies the user's task panel preference (`TaskSort` + `TaskFilter`) to
+//! any task list the TUI renders, whether it came from the filesystem or
+//! from the Conductor.
+
+use std::time::SystemTime;
+
+pub use conductor_core::{TaskFilter, TaskSort};
+
+/// A task that can be ordered and filtered by the task panel
+pub trait ViewableTask {
+    /// Sort rank for `TaskSort::Status` (lower comes first)
+    fn status_rank(&self) -> u8;
+    /// Progress percentage (0-100)
+    fn progress(&self) -> u8;
+    /// When the task started, if known
+    ///
+    /// Tasks without a start time keep their original relative order.
+    fn started_at(&self) -> Option<SystemTime>;
+    /// Whether the task has finished (done or failed)
+    fn is_completed(&self) -> bool;
+}
+
+/// How the task panel is sorted and filtered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaskView {
+    /// Task ordering
+    pub sort: TaskSort,
+    /// Which tasks are shown
+    pub filter: TaskFilter,
+}
+
+impl TaskView {
+    /// Switch to the next sort order (keybinding)
+    pub fn cycle_sort(&mut self) -> TaskSort {
+        self.sort = self.sort.next();
+        self.sort
+    }
+
+    /// Toggle hiding completed tasks (keybinding)
+    pub fn toggle_filter(&mut self) -> TaskFilter {
+        self.filter = self.filter.toggle();
+        self.filter
+    }
+
+    /// Sort and filter tasks for display
+    pub fn apply<'a, T: ViewableTask>(&self, tasks: &'a [T]) -> Vec<&'a T> {
+        apply_view(tasks, self.sort, self.filter)
+    }
+}
+
+/// Sort and filter tasks for display
+pub fn apply_view<T: ViewableTask>(tasks: &[T], sort: TaskSort, filter: TaskFilter) -> Vec<&T> {
+    let mut visible: Vec<&T> = tasks
+        .iter()
+        .filter(|t| filter == TaskFilter::All || !t.is_completed())
+        .collect();
+
+    // Stable sorts keep insertion order for ties
+    match sort {
+        TaskSort::Status => visible.sort_by_key(|t| t.status_rank()),
+        TaskSort::Progress => visible.sort_by_key(|t| std::cmp::Reverse(t.progress())),
+        TaskSort::StartTime => visible.sort_by_key(|t| t.started_at()),
+    }
+
+    visible
+}
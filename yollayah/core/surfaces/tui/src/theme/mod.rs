@@ -11,6 +11,7 @@
 //! interface feel alive. These are gentle sine-wave color transitions
 //! that pulse at different speeds for different UI elements.
 
+use conductor_core::AvatarMood;
 use ratatui::style::Color;
 use std::time::Duration;
 
@@ -89,6 +90,9 @@ pub const BUBBLE: Color = Color::Rgb(200, 230, 255);
 /// Response metadata - subtle dim color
 pub const METADATA_COLOR: Color = Color::Rgb(120, 120, 140);
 
+/// Recovery guidance under an error notification - calm blue
+pub const GUIDANCE_COLOR: Color = Color::Rgb(130, 200, 255);
+
 // ============================================================================
 // Static Colors (Replacing Breathing Effects for Performance)
 // ============================================================================
@@ -99,6 +103,22 @@ pub const USER_PREFIX_COLOR: Color = Color::Rgb(160, 245, 160);
 /// Assistant message prefix ("Yollayah:") - static bright magenta
 pub const ASSISTANT_PREFIX_COLOR: Color = Color::Rgb(255, 140, 255);
 
+/// Assistant name label color for the avatar's current mood
+///
+/// Reinforces the mood outside the avatar itself: happy is yellow,
+/// thinking blue, confused red. Moods without a color of their own keep
+/// the usual magenta.
+#[must_use]
+pub fn mood_accent_color(mood: AvatarMood) -> Color {
+    match mood {
+        AvatarMood::Happy => MOOD_HAPPY,
+        AvatarMood::Thinking | AvatarMood::Curious => MOOD_THINKING,
+        AvatarMood::Confused => MOOD_ERROR,
+        AvatarMood::Excited | AvatarMood::Playful => MOOD_EXCITED,
+        AvatarMood::Shy | AvatarMood::Calm => ASSISTANT_PREFIX_COLOR,
+    }
+}
+
 /// Streaming message cursor - brighter magenta for active streaming
 pub const STREAMING_CURSOR_COLOR: Color = Color::Rgb(255, 180, 255);
 
@@ -319,6 +339,33 @@ pub fn scroll_fade_color(fade_factor: f32) -> Color {
     }
 }
 
+// ============================================================================
+// Color Support Detection
+// ============================================================================
+
+/// Detect whether the terminal can display color
+///
+/// Honors the `NO_COLOR` convention (any non-empty value disables color)
+/// and treats `TERM=dumb` as monochrome.
+#[must_use]
+pub fn detect_color_support() -> bool {
+    parse_color_support(
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+/// Decide color support from `NO_COLOR` and `TERM` values
+///
+/// Used internally by `detect_color_support()` but exposed for testing.
+#[must_use]
+pub fn parse_color_support(no_color: Option<&str>, term: Option<&str>) -> bool {
+    if no_color.is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    !matches!(term, Some("dumb"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -380,6 +427,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mood_accent_color() {
+        for (mood, expected) in [
+            (AvatarMood::Happy, MOOD_HAPPY),
+            (AvatarMood::Thinking, MOOD_THINKING),
+            (AvatarMood::Curious, MOOD_THINKING),
+            (AvatarMood::Confused, MOOD_ERROR),
+            (AvatarMood::Excited, MOOD_EXCITED),
+            (AvatarMood::Playful, MOOD_EXCITED),
+            (AvatarMood::Shy, ASSISTANT_PREFIX_COLOR),
+            (AvatarMood::Calm, ASSISTANT_PREFIX_COLOR),
+        ] {
+            assert_eq!(mood_accent_color(mood), expected, "{mood:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_color_support() {
+        assert!(parse_color_support(None, Some("xterm-256color")));
+        assert!(parse_color_support(Some(""), None));
+        assert!(!parse_color_support(Some("1"), Some("xterm-256color")));
+        assert!(!parse_color_support(None, Some("dumb")));
+    }
+
     #[test]
     fn test_scroll_fade_factor() {
         // No content above/below: always 1.0
@@ -0,0 +1,273 @@
+//! Conversation Scroll State
+//!
+//! Tracks how far the user has scrolled up from the latest message.
+//! While scrolled up, new content doesn't move the viewport; instead a
+//! "new messages" indicator is raised until the user returns to the bottom.
+//! [`AutoscrollMode`] decides which events jump the view back down anyway.
+
+use clap::ValueEnum;
+
+/// When the conversation view snaps back to the latest output
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AutoscrollMode {
+    /// Snap when the user sends a message and when a response starts streaming
+    #[default]
+    Always,
+    /// Snap when a response starts streaming, but not on the user's own sends
+    StreamingOnly,
+    /// Never snap; the view only follows output while already at the bottom
+    Never,
+}
+
+impl AutoscrollMode {
+    /// Whether sending a message jumps to the bottom
+    pub fn snaps_on_send(self) -> bool {
+        self == AutoscrollMode::Always
+    }
+
+    /// Whether a response starting to stream jumps to the bottom
+    pub fn snaps_on_stream(self) -> bool {
+        self != AutoscrollMode::Never
+    }
+}
+
+/// Scroll position of the conversation view
+#[derive(Clone, Debug, Default)]
+pub struct ConversationScroll {
+    /// Lines from the bottom (0 = following the latest output)
+    offset: usize,
+    /// Total rendered lines
+    total_lines: usize,
+    /// New content arrived below the viewport while scrolled up
+    unseen_below: bool,
+}
+
+impl ConversationScroll {
+    /// Create a scroll state following the latest output
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lines from the bottom (0 = latest)
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Total rendered lines
+    pub fn total_lines(&self) -> usize {
+        self.total_lines
+    }
+
+    /// Whether the view auto-scrolls with new output
+    pub fn is_following(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Whether to show the "new messages" indicator
+    pub fn has_unseen_below(&self) -> bool {
+        self.unseen_below
+    }
+
+    /// Scroll towards older content
+    pub fn scroll_up(&mut self, lines: usize) {
+        let max_scroll = self.total_lines.saturating_sub(1);
+        self.offset = (self.offset + lines).min(max_scroll);
+    }
+
+    /// Scroll towards newer content
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.offset = self.offset.saturating_sub(lines);
+        if self.offset == 0 {
+            self.unseen_below = false;
+        }
+    }
+
+    /// Jump to the oldest content
+    pub fn scroll_to_top(&mut self) {
+        self.offset = self.total_lines.saturating_sub(1);
+    }
+
+    /// Jump to the latest content and resume following it
+    pub fn scroll_to_bottom(&mut self) {
+        self.offset = 0;
+        self.unseen_below = false;
+    }
+
+    /// The user sent a message
+    pub fn on_send(&mut self, mode: AutoscrollMode) {
+        if mode.snaps_on_send() {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// A response started streaming
+    ///
+    /// After snapping the view follows the stream like any other output, so
+    /// scrolling up mid-response still holds the viewport in place.
+    pub fn on_stream_start(&mut self, mode: AutoscrollMode) {
+        if mode.snaps_on_stream() {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// Update the line count after a re-render of `viewport_height` lines
+    ///
+    /// When the user is scrolled up, the offset grows with the new lines so
+    /// the text they're reading stays in place.
+    pub fn set_total_lines(&mut self, total_lines: usize, viewport_height: usize) {
+        if total_lines > self.total_lines && !self.is_following() {
+            self.offset += total_lines - self.total_lines;
+            self.unseen_below = true;
+        }
+        self.total_lines = total_lines;
+
+        // Clamp so the viewport never runs past the oldest line
+        let max_scroll = total_lines.saturating_sub(viewport_height);
+        self.offset = self.offset.min(max_scroll);
+        if self.offset == 0 {
+            self.unseen_below = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEIGHT: usize = 10;
+
+    /// First visible line index, as the conversation renderer computes it
+    fn visible_start(scroll: &ConversationScroll) -> usize {
+        scroll
+            .total_lines()
+            .saturating_sub(scroll.offset())
+            .saturating_sub(HEIGHT)
+    }
+
+    #[test]
+    fn test_follows_new_output_at_bottom() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.set_total_lines(55, HEIGHT);
+
+        assert!(scroll.is_following());
+        assert_eq!(visible_start(&scroll), 45);
+        assert!(!scroll.has_unseen_below());
+    }
+
+    #[test]
+    fn test_scrolled_up_viewport_stays_put() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_up(20);
+        let before = visible_start(&scroll);
+
+        // Tokens stream in below
+        scroll.set_total_lines(53, HEIGHT);
+        scroll.set_total_lines(58, HEIGHT);
+
+        assert_eq!(visible_start(&scroll), before);
+        assert!(!scroll.is_following());
+        assert!(scroll.has_unseen_below());
+    }
+
+    #[test]
+    fn test_returning_to_bottom_resumes_auto_scroll() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_up(5);
+        scroll.set_total_lines(52, HEIGHT);
+        assert!(scroll.has_unseen_below());
+
+        scroll.scroll_down(7);
+        assert!(scroll.is_following());
+        assert!(!scroll.has_unseen_below());
+
+        scroll.set_total_lines(60, HEIGHT);
+        assert_eq!(visible_start(&scroll), 50);
+    }
+
+    #[test]
+    fn test_scroll_to_bottom_clears_indicator() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_to_top();
+        scroll.set_total_lines(51, HEIGHT);
+        assert!(scroll.has_unseen_below());
+
+        scroll.scroll_to_bottom();
+        assert!(scroll.is_following());
+        assert!(!scroll.has_unseen_below());
+    }
+
+    #[test]
+    fn test_shrinking_content_clamps_offset() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_up(30);
+
+        // e.g. /clear
+        scroll.set_total_lines(12, HEIGHT);
+        assert_eq!(scroll.offset(), 2);
+    }
+
+    /// Scroll up, send a message, then stream a response in `mode`
+    ///
+    /// Returns (offset after the send, offset after the tokens).
+    fn send_then_stream(mode: AutoscrollMode) -> (usize, usize) {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_up(20);
+
+        scroll.on_send(mode);
+        scroll.set_total_lines(52, HEIGHT);
+        let after_send = scroll.offset();
+
+        scroll.on_stream_start(mode);
+        scroll.set_total_lines(55, HEIGHT);
+        scroll.set_total_lines(58, HEIGHT);
+        (after_send, scroll.offset())
+    }
+
+    #[test]
+    fn test_autoscroll_always_snaps_on_send_and_stream() {
+        assert_eq!(send_then_stream(AutoscrollMode::Always), (0, 0));
+    }
+
+    #[test]
+    fn test_autoscroll_streaming_only_ignores_sends() {
+        // The user's own message lands below without moving the view
+        assert_eq!(send_then_stream(AutoscrollMode::StreamingOnly), (22, 0));
+    }
+
+    #[test]
+    fn test_autoscroll_never_keeps_viewport() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.scroll_up(20);
+        let before = visible_start(&scroll);
+
+        scroll.on_send(AutoscrollMode::Never);
+        scroll.set_total_lines(52, HEIGHT);
+        scroll.on_stream_start(AutoscrollMode::Never);
+        scroll.set_total_lines(58, HEIGHT);
+
+        assert_eq!(visible_start(&scroll), before);
+        assert!(scroll.has_unseen_below());
+        assert_eq!(send_then_stream(AutoscrollMode::Never), (22, 28));
+    }
+
+    #[test]
+    fn test_autoscroll_follows_stream_after_scrolling_up_mid_response() {
+        let mut scroll = ConversationScroll::new();
+        scroll.set_total_lines(50, HEIGHT);
+        scroll.on_stream_start(AutoscrollMode::StreamingOnly);
+        scroll.set_total_lines(55, HEIGHT);
+        assert!(scroll.is_following());
+
+        // Reading back mid-stream isn't interrupted by later tokens
+        scroll.scroll_up(10);
+        scroll.set_total_lines(60, HEIGHT);
+        assert_eq!(scroll.offset(), 15);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Borderless, scrollable widgets for the Yollayah UI.
 
+mod conversation_scroll;
 mod text_block;
 
+pub use conversation_scroll::{AutoscrollMode, ConversationScroll};
 pub use text_block::{TextBlock, TextBlockState};
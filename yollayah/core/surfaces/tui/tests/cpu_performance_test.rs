@@ -253,6 +253,7 @@ impl LlmBackend for CpuTestMockBackend {
             // Send completion
             let _ = tx.send(StreamingToken::Complete {
                 message: full_message,
+                finish_reason: None,
             }).await;
         });
 
@@ -34,10 +34,11 @@ use tokio::sync::mpsc;
 use tokio::time::timeout;
 
 use conductor_core::{
-    backend::{LlmBackend, LlmRequest, LlmResponse, ModelInfo, StreamingToken},
-    Conductor, ConductorConfig, ConductorMessage, ConductorState, EventId, MessageRole,
+    backend::{CancellationToken, LlmBackend, LlmRequest, LlmResponse, ModelInfo, StreamingToken},
+    Conductor, ConductorConfig, ConductorMessage, ConductorState, EventId, MessageId, MessageRole,
     SurfaceCapabilities, SurfaceEvent, SurfaceType,
 };
+use yollayah_tui::display::DisplayState;
 
 // ============================================================================
 // Configurable Mock Backend
@@ -74,6 +75,8 @@ pub struct IntegrationMockBackend {
     error_config: Arc<ErrorInjectionConfig>,
     /// Flag to track if warmup request was received
     warmup_received: Arc<AtomicBool>,
+    /// Count of tokens the streaming tasks have emitted
+    tokens_sent: Arc<AtomicUsize>,
 }
 
 impl IntegrationMockBackend {
@@ -83,6 +86,7 @@ impl IntegrationMockBackend {
             token_delay_ms: 0,
             error_config: Arc::new(ErrorInjectionConfig::default()),
             warmup_received: Arc::new(AtomicBool::new(false)),
+            tokens_sent: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -93,6 +97,7 @@ impl IntegrationMockBackend {
             token_delay_ms: delay_ms,
             error_config: Arc::new(ErrorInjectionConfig::default()),
             warmup_received: Arc::new(AtomicBool::new(false)),
+            tokens_sent: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -103,6 +108,7 @@ impl IntegrationMockBackend {
             token_delay_ms: 0,
             error_config: Arc::new(config),
             warmup_received: Arc::new(AtomicBool::new(false)),
+            tokens_sent: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -114,6 +120,7 @@ impl IntegrationMockBackend {
             token_delay_ms: 0,
             error_config: Arc::new(ErrorInjectionConfig::default()),
             warmup_received: warmup_received.clone(),
+            tokens_sent: Arc::new(AtomicUsize::new(0)),
         };
         (backend, warmup_received)
     }
@@ -128,12 +135,17 @@ impl IntegrationMockBackend {
         self.warmup_received.load(Ordering::SeqCst)
     }
 
+    /// Shared count of streamed tokens (still readable once the backend is moved)
+    pub fn tokens_sent(&self) -> Arc<AtomicUsize> {
+        self.tokens_sent.clone()
+    }
+
     /// Generate a response based on the request content
     fn generate_response(&self, request: &LlmRequest) -> Vec<String> {
         let prompt = request.prompt.to_lowercase();
 
-        // Warmup request detection (contains "hi" or "5 words")
-        if prompt.contains("say hi") || prompt.contains("5 words") {
+        // Warmup request detection (an empty prompt just loads the model)
+        if prompt.is_empty() {
             self.warmup_received.store(true, Ordering::SeqCst);
             return vec!["Hi ".to_string(), "there!".to_string()];
         }
@@ -182,6 +194,15 @@ impl LlmBackend for IntegrationMockBackend {
     async fn send_streaming(
         &self,
         request: &LlmRequest,
+    ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
+        self.send_streaming_cancellable(request, CancellationToken::new())
+            .await
+    }
+
+    async fn send_streaming_cancellable(
+        &self,
+        request: &LlmRequest,
+        cancel: CancellationToken,
     ) -> anyhow::Result<mpsc::Receiver<StreamingToken>> {
         self.request_count.fetch_add(1, Ordering::SeqCst);
 
@@ -200,11 +221,17 @@ impl LlmBackend for IntegrationMockBackend {
         let tokens = self.generate_response(request);
         let delay = self.token_delay_ms;
         let error_config = self.error_config.clone();
+        let tokens_sent = self.tokens_sent.clone();
 
         let (tx, rx) = mpsc::channel(10);
         tokio::spawn(async move {
             let mut full_message = String::new();
             for (i, token) in tokens.iter().enumerate() {
+                // Stop generating once the request is cancelled
+                if cancel.is_cancelled() {
+                    return;
+                }
+
                 // Check if we should inject an error after N tokens
                 if let Some(error_after) = error_config.error_after_tokens {
                     if i >= error_after {
@@ -220,13 +247,18 @@ impl LlmBackend for IntegrationMockBackend {
 
                 full_message.push_str(token);
                 let _ = tx.send(StreamingToken::Token(token.clone())).await;
+                tokens_sent.fetch_add(1, Ordering::SeqCst);
                 if delay > 0 {
-                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    tokio::select! {
+                        () = cancel.cancelled() => return,
+                        () = tokio::time::sleep(Duration::from_millis(delay)) => {}
+                    }
                 }
             }
             let _ = tx
                 .send(StreamingToken::Complete {
                     message: full_message,
+                    finish_reason: None,
                 })
                 .await;
         });
@@ -384,6 +416,11 @@ async fn test_conductor_startup_and_greeting() {
                     greeting_found = true;
                 }
             }
+            if let ConductorMessage::Welcome { content, .. } = &msg {
+                if !content.is_empty() {
+                    greeting_found = true;
+                }
+            }
         }
         iterations += 1;
     }
@@ -627,7 +664,8 @@ async fn test_avatar_messages_processed() {
                 ConductorMessage::AvatarGesture { .. } => {
                     avatar_messages_received = true;
                 }
-                ConductorMessage::StreamEnd { .. } => {
+                // The greeting finishes with a Welcome instead of a StreamEnd
+                ConductorMessage::StreamEnd { .. } | ConductorMessage::Welcome { .. } => {
                     stream_completed = true;
                 }
                 _ => {}
@@ -936,6 +974,163 @@ async fn test_exit_during_streaming() {
     );
 }
 
+/// Test 8b: Exit during streaming cancels the backend request
+///
+/// Verifies:
+/// - QuitRequested mid-stream cancels the backend's generation
+/// - The spawned streaming task stops emitting tokens
+#[tokio::test]
+async fn test_exit_during_streaming_cancels_backend() {
+    let (tx, mut rx) = mpsc::channel(100);
+    let config = ConductorConfig {
+        model: "integration-mock".to_string(),
+        warmup_on_start: false,
+        greet_on_connect: false,
+        ..Default::default()
+    };
+
+    let backend = IntegrationMockBackend::with_delay(50);
+    let tokens_sent = backend.tokens_sent();
+    let mut conductor = Conductor::new(backend, config, tx);
+    conductor.start().await.expect("Conductor should start");
+
+    conductor
+        .handle_event(SurfaceEvent::UserMessage {
+            event_id: SurfaceEvent::new_event_id(),
+            content: "Tell me a story".to_string(),
+        })
+        .await
+        .expect("Should handle message");
+
+    // Wait for the first token
+    for _ in 0..20 {
+        conductor.poll_streaming().await;
+        if drain_messages(&mut rx)
+            .iter()
+            .any(|msg| matches!(msg, ConductorMessage::Token { .. }))
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(
+        tokens_sent.load(Ordering::SeqCst) > 0,
+        "Streaming should have started"
+    );
+
+    conductor
+        .handle_event(SurfaceEvent::QuitRequested {
+            event_id: SurfaceEvent::new_event_id(),
+        })
+        .await
+        .expect("Should handle quit during streaming");
+    let at_quit = tokens_sent.load(Ordering::SeqCst);
+
+    // The response has 7 tokens, 50ms apart; none follow the quit
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(
+        tokens_sent.load(Ordering::SeqCst),
+        at_quit,
+        "Backend kept generating after quit"
+    );
+    assert!(at_quit < 7, "Response finished before quitting");
+}
+
+/// Test 8c: A newer message cancels the response still streaming
+///
+/// Verifies:
+/// - `StreamCancelled` for the first response arrives before the second's tokens
+/// - The session keeps what arrived of the first response, marked cancelled
+/// - The TUI keeps it too, so a resync doesn't bring anything back
+#[tokio::test]
+async fn test_newer_message_cancels_streaming_response() {
+    let (tx, mut rx) = mpsc::channel(100);
+    let config = ConductorConfig {
+        model: "integration-mock".to_string(),
+        warmup_on_start: false,
+        greet_on_connect: false,
+        ..Default::default()
+    };
+
+    let backend = IntegrationMockBackend::with_delay(50);
+    let mut conductor = Conductor::new(backend, config, tx);
+    conductor.start().await.expect("Conductor should start");
+    drain_messages(&mut rx);
+
+    let message = |content: &str| SurfaceEvent::UserMessage {
+        event_id: SurfaceEvent::new_event_id(),
+        content: content.to_string(),
+    };
+    let mut seen = Vec::new();
+
+    // Wait for text from a response other than `after`, returning its message ID
+    async fn first_token(
+        conductor: &mut Conductor<IntegrationMockBackend>,
+        rx: &mut mpsc::Receiver<ConductorMessage>,
+        seen: &mut Vec<ConductorMessage>,
+        after: Option<&MessageId>,
+    ) -> MessageId {
+        for _ in 0..50 {
+            conductor.poll_streaming().await;
+            let messages = drain_messages(rx);
+            let token = messages.iter().find_map(|msg| match msg {
+                ConductorMessage::Token {
+                    message_id, text, ..
+                } if !text.is_empty() && Some(message_id) != after => Some(message_id.clone()),
+                _ => None,
+            });
+            seen.extend(messages);
+            if let Some(id) = token {
+                return id;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("No token arrived");
+    }
+
+    conductor.handle_event(message("first")).await.unwrap();
+    let first = first_token(&mut conductor, &mut rx, &mut seen, None).await;
+    conductor.handle_event(message("second")).await.unwrap();
+    let second = first_token(&mut conductor, &mut rx, &mut seen, Some(&first)).await;
+
+    let cancelled = seen
+        .iter()
+        .position(|m| {
+            matches!(m, ConductorMessage::StreamCancelled { message_id } if *message_id == first)
+        })
+        .expect("First response should be cancelled");
+    let second_token = seen
+        .iter()
+        .position(
+            |m| matches!(m, ConductorMessage::Token { message_id, .. } if *message_id == second),
+        )
+        .unwrap();
+    assert!(cancelled < second_token);
+
+    // History keeps what arrived of the first response
+    let partial = conductor
+        .session()
+        .all_messages()
+        .iter()
+        .find(|m| m.id == first)
+        .expect("Partial response should stay in the session");
+    assert!(partial.cancelled);
+    assert!(!partial.content.is_empty());
+
+    // The TUI shows the same history before and after a resync
+    let mut display = DisplayState::new();
+    for msg in seen {
+        display.apply_message(msg);
+    }
+    let ids = |display: &DisplayState| -> Vec<MessageId> {
+        display.messages.iter().map(|m| m.id.clone()).collect()
+    };
+    let shown = ids(&display);
+    assert!(shown.contains(&first));
+    display.apply_message(conductor.create_state_snapshot(0));
+    assert_eq!(ids(&display), shown);
+}
+
 /// Test 9: Multiple rapid exit requests
 ///
 /// Verifies:
@@ -1178,9 +1373,9 @@ async fn test_input_after_slow_response() {
 /// Test 12: Warmup flow verification
 ///
 /// Verifies:
-/// - When warmup_on_start = true, the conductor sends a warmup request to the backend
-/// - State transitions: Initializing -> WarmingUp -> Ready
-/// - Backend receives the warmup prompt before any user interaction
+/// - With a warmup model, the main model loads in the background
+/// - State transitions: Initializing -> Ready
+/// - Backend receives the warmup request before any user interaction
 /// - Warmup completion is tracked correctly
 #[tokio::test]
 async fn test_warmup_flow() {
@@ -1191,7 +1386,7 @@ async fn test_warmup_flow() {
 
     let config = ConductorConfig {
         model: "integration-mock".to_string(),
-        warmup_on_start: true,   // Enable warmup
+        warmup_model: Some("integration-warmup".to_string()),
         greet_on_connect: false, // Disable greeting to isolate warmup
         max_context_messages: 10,
         system_prompt: None,
@@ -1211,25 +1406,35 @@ async fn test_warmup_flow() {
     conductor.start().await.expect("Conductor should start");
 
     // Collect state transitions
-    let mut saw_warming_up = false;
+    let mut saw_initializing = false;
     let mut saw_ready = false;
 
     while let Ok(msg) = rx.try_recv() {
         if let ConductorMessage::State { state } = msg {
             match state {
-                ConductorState::WarmingUp => saw_warming_up = true,
+                // The model warms up while the Conductor is initializing
+                ConductorState::Initializing => saw_initializing = true,
                 ConductorState::Ready => saw_ready = true,
                 _ => {}
             }
         }
     }
 
+    // The main model finishes warming after start returns
+    for _ in 0..100 {
+        if conductor.is_ready() {
+            break;
+        }
+        conductor.tick().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
     // Verify warmup occurred
     assert!(
         warmup_flag.load(Ordering::SeqCst),
         "Backend should have received warmup request"
     );
-    assert!(saw_warming_up, "Should have seen WarmingUp state");
+    assert!(saw_initializing, "Should have seen Initializing state");
     assert!(saw_ready, "Should have transitioned to Ready state");
     assert!(
         conductor.is_ready(),
@@ -2040,6 +2245,8 @@ async fn test_conductor_headless_operation() {
                 clipboard: false,
                 max_width: 0,
                 max_height: 0,
+                frame_checksums: false,
+                sprite_push: false,
             },
         })
         .await
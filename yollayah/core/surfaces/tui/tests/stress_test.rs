@@ -178,6 +178,7 @@ impl LlmBackend for StressTestBackend {
             let _ = tx
                 .send(StreamingToken::Complete {
                     message: accumulated_content,
+                    finish_reason: None,
                 })
                 .await;
 